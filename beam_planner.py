@@ -0,0 +1,235 @@
+"""Beam-search meal planning.
+
+Greedy `plan_meal` commits to the locally best bite. `plan_beam` keeps the
+top few partial plans by SP at each step, expanding each with the best
+ranked candidates, then replays the winning sequence on the real manager.
+
+Exports
+-------
+plan_beam
+"""
+
+import logging
+from dataclasses import (
+    dataclass,
+    field,
+)
+
+from constants import (
+    MAX_ITERATIONS,
+)
+from food_state_manager import (
+    FoodStateManager,
+)
+from models.food import (
+    Food,
+)
+from planner import (
+    _apply_bite,
+    _clone_manager,
+    normalize_name,
+    plan_from_state,
+    rank_candidates,
+    validate_cravings,
+)
+
+logger = logging.getLogger(__name__)
+
+# Ranked candidates expanded from each partial plan
+BEAM_BRANCHING = 4
+
+# Safety cap on simulated bites across the whole search
+MAX_BEAM_EXPANSIONS = 2000
+
+
+@dataclass
+class _BeamState:
+    """One partial plan: a private manager copy plus the bites taken."""
+
+    manager: FoodStateManager
+    current_sp: float
+    remaining_calories: int
+    cravings: list[str]
+    cravings_satisfied: int
+    variety_count_now: int
+    bites: list[Food] = field(default_factory=list)
+
+
+def _expand(
+    state: _BeamState,
+    food: Food,
+    server_mult: float,
+    dinner_party_mult: float,
+) -> _BeamState:
+    """Child state after eating one unit of *food*."""
+    manager = _clone_manager(state.manager)
+    cravings = list(state.cravings)
+    (
+        current_sp,
+        remaining_calories,
+        cravings_satisfied,
+        variety_count_now,
+    ) = _apply_bite(
+        manager,
+        manager.get_food(food.name),
+        current_sp=state.current_sp,
+        remaining_calories=state.remaining_calories,
+        cravings=cravings,
+        cravings_satisfied=state.cravings_satisfied,
+        variety_count_now=state.variety_count_now,
+        meal_plan=[],
+        server_mult=server_mult,
+        dinner_party_mult=dinner_party_mult,
+    )
+    return _BeamState(
+        manager=manager,
+        current_sp=current_sp,
+        remaining_calories=remaining_calories,
+        cravings=cravings,
+        cravings_satisfied=cravings_satisfied,
+        variety_count_now=variety_count_now,
+        bites=[*state.bites, food],
+    )
+
+
+def plan_beam(
+    manager,
+    cravings,
+    cravings_satisfied,
+    remaining_calories,
+    beam_width,
+    *,
+    server_mult: float = 1.0,
+    dinner_party_mult: float = 1.0,
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    max_expansions: int = MAX_BEAM_EXPANSIONS,
+):
+    """Plan a sequence of bites with a small beam search.
+
+    The greedy plan is always among the finalists, so the result never
+    scores below `plan_meal` for the same inputs.
+
+    Parameters
+    ----------
+    manager : FoodStateManager
+        Current food state/availability; updated with the chosen plan.
+    cravings : list of str
+        Requested cravings (case-insensitive names).
+    cravings_satisfied : int
+        Number of cravings already satisfied today.
+    remaining_calories : int
+        Calorie budget for this plan.
+    beam_width : int
+        Partial plans kept per step (``>= 1``).
+    server_mult : float, optional
+        Server skill gain multiplier. Default is 1.0.
+    dinner_party_mult : float, optional
+        Dinner party multiplier (1.0-3.0). Default is 1.0.
+    max_repeats : int, optional
+        Hard cap on units of any one food in the stomach. Default is no cap.
+    min_bite_calories : int, optional
+        Skip foods below this many calories unless nothing larger fits.
+    max_expansions : int, optional
+        Total simulated bites before the search stops early.
+
+    Returns
+    -------
+    list[MealPlanItem]
+        Ordered plan with per-bite deltas and tags.
+
+    Raises
+    ------
+    ValueError
+        If `beam_width` is less than 1.
+    """
+    if beam_width < 1:
+        raise ValueError(f"beam width must be at least 1, got {beam_width}")
+    cravings = [normalize_name(name) for name in cravings]
+    cravings = validate_cravings(manager, cravings)[0]
+    root = _BeamState(
+        manager=_clone_manager(manager),
+        current_sp=manager.get_current_sp(
+            cravings,
+            cravings_satisfied,
+            server_mult=server_mult,
+            dinner_party_mult=dinner_party_mult,
+        ),
+        remaining_calories=remaining_calories,
+        cravings=cravings,
+        cravings_satisfied=cravings_satisfied,
+        variety_count_now=len(manager.unique_variety_foods()),
+    )
+
+    # Seed the finalists with the greedy plan so beam search can't do worse
+    greedy_plan, _ = plan_from_state(
+        manager,
+        cravings,
+        cravings_satisfied,
+        remaining_calories,
+        server_mult=server_mult,
+        dinner_party_mult=dinner_party_mult,
+        max_repeats=max_repeats,
+        min_bite_calories=min_bite_calories,
+    )
+    best_sp = greedy_plan[-1].new_sp if greedy_plan else root.current_sp
+    best_bites = [manager.get_food(item.name) for item in greedy_plan]
+
+    beams = [root]
+    expansion_count = 0
+    for _ in range(MAX_ITERATIONS):
+        children: list[_BeamState] = []
+        for state in beams:
+            ranked = rank_candidates(
+                state.manager,
+                state.remaining_calories,
+                state.cravings,
+                state.cravings_satisfied,
+                max_repeats,
+                min_bite_calories,
+            )
+            if state.remaining_calories <= 0 or not ranked:
+                # Finished plan; compare against the best so far
+                if state.current_sp > best_sp:
+                    best_sp, best_bites = state.current_sp, state.bites
+                continue
+            for food, _ in ranked[:BEAM_BRANCHING]:
+                if expansion_count >= max_expansions:
+                    break
+                children.append(_expand(state, food, server_mult, dinner_party_mult))
+                expansion_count += 1
+
+        if not children:
+            break
+        children.sort(key=lambda child: child.current_sp, reverse=True)
+        beams = children[:beam_width]
+        if expansion_count >= max_expansions:
+            logger.warning("Beam search hit %d expansions.", max_expansions)
+            for state in beams:
+                if state.current_sp > best_sp:
+                    best_sp, best_bites = state.current_sp, state.bites
+            break
+
+    # Replay the winning sequence on the real manager for accurate logs
+    meal_plan: list = []
+    current_sp = root.current_sp
+    variety_count_now = root.variety_count_now
+    for food in best_bites:
+        (
+            current_sp,
+            remaining_calories,
+            cravings_satisfied,
+            variety_count_now,
+        ) = _apply_bite(
+            manager,
+            manager.get_food(food.name),
+            current_sp=current_sp,
+            remaining_calories=remaining_calories,
+            cravings=cravings,
+            cravings_satisfied=cravings_satisfied,
+            variety_count_now=variety_count_now,
+            meal_plan=meal_plan,
+            server_mult=server_mult,
+            dinner_party_mult=dinner_party_mult,
+        )
+    return meal_plan
@@ -7,11 +7,16 @@ Exports
 -------
 sum_weighted_nutrients
 sum_all_weighted_nutrients
+total_nutrients
+check_nutrient_targets
 calculate_balanced_diet_bonus
 calculate_balanced_diet_ratio
 get_tastiness_bonus
 calculate_nutrition_multiplier
+dinner_party_multiplier
 get_sp
+SpBreakdown
+sp_breakdown
 simulate_stomach_with_added_food
 evaluate_bonus_with_addition
 get_sp_delta
@@ -20,28 +25,44 @@ get_variety_bonus
 tastiness_delta_for_added_unit
 variety_fraction_for
 is_variety_qualifying
+variety_progress
 soft_variety_count
 variety_count
+StomachAccumulator
 
 Notes
 -----
 All functions are side-effect free; inputs are treated as read-only.
+`StomachAccumulator` is the one stateful helper; it never mutates its
+seed stomach.
 """
 
 import logging
+import math
+from dataclasses import dataclass
 from typing import Dict
 
 from constants import (
+    BALANCE_METRIC,
     BASE_SKILL_POINTS,
     CRAVING_SATISFIED_FRAC,
+    DINNER_PARTY_MAX,
+    DINNER_PARTY_MODE,
+    DINNER_PARTY_PER_FOOD,
+    NUTRIENT_WEIGHTS,
     TASTINESS_MULTIPLIERS,
     TASTINESS_WEIGHT,
     VARIETY_BONUS_CAP_PP,
     VARIETY_CAL_THRESHOLD,
+    VARIETY_HALF_LIFE,
 )
 from models.food import (
     Food,
 )
+from models.plan import (
+    NutrientTarget,
+    VarietyProgress,
+)
 
 logger = logging.getLogger(__name__)
 
@@ -106,7 +127,8 @@ def sum_all_weighted_nutrients(
     -------
     tuple[dict[str, float], float]
         ``(density_dict, total_calories)`` where the dict has keys
-        ``"carbs"``, ``"protein"``, ``"fat"``, ``"vitamins"``.
+        ``"carbs"``, ``"protein"``, ``"fat"``, ``"vitamins"``, each
+        scaled by its ``NUTRIENT_WEIGHTS`` entry.
     """
     # Delegate per-attr computation to keep logic in one place
     total_cal = _total_calories(stomach)
@@ -121,9 +143,63 @@ def sum_all_weighted_nutrients(
         totals["fat"] += food.fat * calorie_weight
         totals["vitamins"] += food.vitamins * calorie_weight
 
+    for nutrient in totals:
+        totals[nutrient] *= NUTRIENT_WEIGHTS[nutrient]
     return totals, total_cal
 
 
+def total_nutrients(
+    stomach: dict,
+) -> dict[str, float]:
+    """Absolute nutrient intake of a stomach.
+
+    Unlike `sum_all_weighted_nutrients`, this is neither calorie-weighted
+    nor scaled by ``NUTRIENT_WEIGHTS``: each nutrient is simply summed
+    over every unit eaten.
+
+    Parameters
+    ----------
+    stomach : dict[Food, int]
+        Current stomach state.
+
+    Returns
+    -------
+    dict[str, float]
+        Totals keyed ``"carbs"``, ``"protein"``, ``"fat"``, ``"vitamins"``.
+    """
+    totals = {"carbs": 0.0, "protein": 0.0, "fat": 0.0, "vitamins": 0.0}
+    for food, quantity in stomach.items():
+        for nutrient in totals:
+            totals[nutrient] += getattr(food, nutrient) * quantity
+    return totals
+
+
+def check_nutrient_targets(
+    stomach: dict,
+    goals: dict[str, float],
+) -> list[NutrientTarget]:
+    """Compare `total_nutrients` of *stomach* with per-nutrient *goals*.
+
+    Parameters
+    ----------
+    stomach : dict[Food, int]
+        Current stomach state.
+    goals : dict[str, float]
+        Minimum total per nutrient key; nutrients without a goal are
+        left out.
+
+    Returns
+    -------
+    list[NutrientTarget]
+        One entry per goal, in *goals* order.
+    """
+    totals = total_nutrients(stomach)
+    return [
+        NutrientTarget(nutrient, totals[nutrient], goal)
+        for nutrient, goal in goals.items()
+    ]
+
+
 def calculate_balanced_diet_bonus(
     nutrients: list[float],
 ) -> float:
@@ -148,15 +224,19 @@ def calculate_balanced_diet_bonus(
 
 def calculate_balanced_diet_ratio(
     nutrients: list[float],
+    metric: str | None = None,
 ) -> float:
     """Balance ratio of the nutrient distribution.
 
-    Defined as ``min_nonzero / max`` among the nutrient values.
+    Defined as ``min_nonzero / max`` among the nutrient values, or with
+    the ``"cv"`` metric as ``1 - std / mean`` (floored at 0).
 
     Parameters
     ----------
     nutrients : list of float
         Weighted nutrient totals.
+    metric : str, optional
+        ``"minmax"`` or ``"cv"``. Defaults to ``game_rules.balance_metric``.
 
     Returns
     -------
@@ -168,6 +248,12 @@ def calculate_balanced_diet_ratio(
     max_nutrient = max(nutrients)
     if max_nutrient <= 0:
         return 0.0
+    if (metric or BALANCE_METRIC) == "cv":
+        # Population spread relative to the mean: one weak nutrient among
+        # strong ones lowers the ratio without zeroing it
+        mean = sum(nutrients) / len(nutrients)
+        variance = sum((value - mean) ** 2 for value in nutrients) / len(nutrients)
+        return max(0.0, 1.0 - variance**0.5 / mean)
     return min(nutrients) / max_nutrient
 
 
@@ -246,6 +332,106 @@ def calculate_nutrition_multiplier(
     return balanced_diet_pp + variety_pp + tastiness_pp
 
 
+def dinner_party_multiplier(
+    dinner_party_mult: float,
+    variety_count: int,
+) -> float:
+    """Dinner party multiplier actually applied to nutrition SP.
+
+    In ``"fixed"`` mode (the default) *dinner_party_mult* is used as given.
+    In ``"variety"`` mode it is ignored and each variety-qualifying food
+    adds ``DINNER_PARTY_PER_FOOD``, up to ``DINNER_PARTY_MAX``.
+
+    Parameters
+    ----------
+    dinner_party_mult : float
+        Multiplier passed by the caller (e.g., ``plan -d``).
+    variety_count : int
+        Distinct foods meeting the variety threshold.
+
+    Returns
+    -------
+    float
+        Effective multiplier.
+    """
+    if DINNER_PARTY_MODE != "variety":
+        return dinner_party_mult
+    return min(DINNER_PARTY_MAX, 1.0 + DINNER_PARTY_PER_FOOD * variety_count)
+
+
+@dataclass(frozen=True)
+class SpBreakdown:
+    """Every term of the SP formula for one stomach.
+
+    Bonus terms are fractions (``+0.12`` means +12 pp).
+
+    Attributes
+    ----------
+    nutrient_total : float
+        Sum of weighted nutrient densities.
+    balance, variety, taste, craving : float
+        Bonus fractions added to 1.
+    dinner_party_mult, server_mult : float
+        Multipliers applied as in `get_sp`.
+    base : float
+        ``BASE_SKILL_POINTS``.
+    """
+
+    nutrient_total: float
+    balance: float
+    variety: float
+    taste: float
+    craving: float
+    dinner_party_mult: float
+    base: float
+    server_mult: float
+
+    @property
+    def bonus_mult(
+        self,
+    ) -> float:
+        """``1 + balance + variety + taste + craving``."""
+        return 1.0 + self.balance + self.variety + self.taste + self.craving
+
+    @property
+    def total(
+        self,
+    ) -> float:
+        """Final SP."""
+        nutrition_sp = self.nutrient_total * self.bonus_mult * self.dinner_party_mult
+        return (nutrition_sp + self.base) * self.server_mult
+
+
+def sp_breakdown(
+    stomach,
+    cravings_satisfied,
+    unique_foods_24h,
+    *,
+    server_mult: float = 1.0,
+    dinner_party_mult: float = 1.0,
+) -> SpBreakdown:
+    """Compute each term of the SP formula; see `get_sp`.
+
+    Returns
+    -------
+    SpBreakdown
+        Terms whose `total` equals `get_sp` for the same inputs.
+    """
+    density, _ = sum_all_weighted_nutrients(stomach)
+    return SpBreakdown(
+        nutrient_total=sum(density.values()),
+        balance=calculate_balanced_diet_bonus(list(density.values())) / 100.0,
+        variety=get_variety_bonus(len(unique_foods_24h)) / 100.0,
+        taste=get_tastiness_bonus(stomach) / 100.0,
+        craving=cravings_satisfied * CRAVING_SATISFIED_FRAC,
+        dinner_party_mult=dinner_party_multiplier(
+            dinner_party_mult, len(unique_foods_24h)
+        ),
+        base=BASE_SKILL_POINTS,
+        server_mult=server_mult,
+    )
+
+
 def get_sp(
     stomach,
     cravings,
@@ -270,7 +456,9 @@ def get_sp(
     server_mult : float, optional
         Server skill gain multiplier. Default is 1.0.
     dinner_party_mult : float, optional
-        Dinner party multiplier (1.0-3.0). Default is 1.0.
+        Dinner party multiplier (1.0-3.0). Default is 1.0. Replaced by a
+        variety-derived value in ``"variety"`` mode; see
+        `dinner_party_multiplier`.
 
     Returns
     -------
@@ -298,6 +486,9 @@ def get_sp(
     bonus += cravings_satisfied * CRAVING_SATISFIED_FRAC
 
     # Apply dinner party multiplier to nutrition SP, then add base, then server mult
+    dinner_party_mult = dinner_party_multiplier(
+        dinner_party_mult, len(unique_foods_24h)
+    )
     nutrition_sp = density_sum * (1.0 + bonus) * dinner_party_mult
     return (nutrition_sp + BASE_SKILL_POINTS) * server_mult
 
@@ -432,7 +623,7 @@ def get_balanced_diet_ratio(
     Returns
     -------
     float
-        ``min_nonzero / max`` using weighted nutrient totals.
+        `calculate_balanced_diet_ratio` of the weighted nutrient totals.
     """
     density, _ = sum_all_weighted_nutrients(stomach)
     nutrients = [
@@ -441,10 +632,7 @@ def get_balanced_diet_ratio(
         density["fat"],
         density["vitamins"],
     ]
-    max_nutrient = max(nutrients)
-    if max_nutrient <= 0:
-        return 0
-    return min(nutrients) / max_nutrient
+    return calculate_balanced_diet_ratio(nutrients)
 
 
 def get_variety_bonus(
@@ -463,9 +651,11 @@ def get_variety_bonus(
         Variety bonus in percentage points (capped).
     """
 
-    # Exponential cap: each +20 qualifying foods halves the remaining gap
-    # to the cap
-    return VARIETY_BONUS_CAP_PP * (1 - 0.5 ** (unique_food_count / 20))
+    # Exponential cap: each VARIETY_HALF_LIFE qualifying foods halve the
+    # remaining gap to the cap
+    return VARIETY_BONUS_CAP_PP * (
+        1 - 0.5 ** (unique_food_count / VARIETY_HALF_LIFE)
+    )
 
 
 def tastiness_delta_for_added_unit(
@@ -550,6 +740,49 @@ def is_variety_qualifying(
     return (food_item.calories * quantity) >= VARIETY_CAL_THRESHOLD
 
 
+def variety_progress(
+    stomach: Dict[Food, int],
+) -> list[VarietyProgress]:
+    """Each stomach food's progress toward ``VARIETY_CAL_THRESHOLD``.
+
+    Parameters
+    ----------
+    stomach : dict[Food, int]
+        Current stomach state; foods with no units are left out.
+
+    Returns
+    -------
+    list[VarietyProgress]
+        Closest to qualifying first, then by name.
+    """
+    entries = []
+    for food_item, quantity in stomach.items():
+        if quantity <= 0:
+            continue
+        eaten = food_item.calories * quantity
+        qualifies = is_variety_qualifying(food_item, quantity)
+        if qualifies:
+            units_needed: int | None = 0
+        elif food_item.calories <= 0:
+            units_needed = None
+        else:
+            units_needed = math.ceil(
+                (VARIETY_CAL_THRESHOLD - eaten) / food_item.calories
+            )
+        entries.append(
+            VarietyProgress(
+                name=food_item.name,
+                calories=eaten,
+                progress=eaten / VARIETY_CAL_THRESHOLD,
+                qualifies=qualifies,
+                units_needed=units_needed,
+            )
+        )
+    entries.sort(key=lambda entry: entry.name)
+    entries.sort(key=lambda entry: entry.progress, reverse=True)
+    return entries
+
+
 def soft_variety_count(
     stomach: Dict[Food, int],
 ) -> float:
@@ -595,3 +828,102 @@ def variety_count(
         for food_item, quantity in stomach.items()
         if is_variety_qualifying(food_item, quantity)
     )
+
+
+class StomachAccumulator:
+    """Running stomach totals for O(1) SP snapshots while planning.
+
+    Mirrors `get_sp` on the stomach it was seeded from, updated one bite
+    at a time via `add` instead of re-walking the whole stomach.
+
+    Parameters
+    ----------
+    stomach : dict[Food, int], optional
+        Starting stomach state. Default is empty.
+    """
+
+    def __init__(
+        self,
+        stomach: dict | None = None,
+    ) -> None:
+        self.total_calories = 0.0
+        # Sum of nutrient * calories * quantity (unweighted)
+        self.nutrient_calories = {
+            "carbs": 0.0,
+            "protein": 0.0,
+            "fat": 0.0,
+            "vitamins": 0.0,
+        }
+        self.food_calories: dict[Food, float] = {}
+        self.variety_names: set[str] = set()
+        self.taste_numerator = 0.0
+        for food, quantity in (stomach or {}).items():
+            self.add(food, quantity)
+
+    def add(
+        self,
+        food: Food,
+        quantity: int = 1,
+    ) -> None:
+        """Fold *quantity* units of *food* into the running totals."""
+        calories = food.calories * quantity
+        self.total_calories += calories
+        self.nutrient_calories["carbs"] += food.carbs * calories
+        self.nutrient_calories["protein"] += food.protein * calories
+        self.nutrient_calories["fat"] += food.fat * calories
+        self.nutrient_calories["vitamins"] += food.vitamins * calories
+        taste_mult = TASTINESS_MULTIPLIERS.get(food.tastiness, 0.0)
+        self.taste_numerator += taste_mult * calories
+
+        # Variety only changes when this food crosses the threshold
+        before = self.food_calories.get(food, 0.0)
+        self.food_calories[food] = before + calories
+        if before < VARIETY_CAL_THRESHOLD <= self.food_calories[food]:
+            self.variety_names.add(food.name.lower())
+
+    def density(
+        self,
+    ) -> dict[str, float]:
+        """Same weighted densities as `sum_all_weighted_nutrients`."""
+        if self.total_calories == 0:
+            return {nutrient: 0.0 for nutrient in self.nutrient_calories}
+        return {
+            nutrient: total / self.total_calories * NUTRIENT_WEIGHTS[nutrient]
+            for nutrient, total in self.nutrient_calories.items()
+        }
+
+    @property
+    def variety_count(
+        self,
+    ) -> int:
+        """Number of distinct food names meeting the variety threshold."""
+        return len(self.variety_names)
+
+    def tastiness_bonus(
+        self,
+    ) -> float:
+        """Same value as `get_tastiness_bonus` on the tracked stomach."""
+        if self.total_calories <= 0:
+            return 0.0
+        return (self.taste_numerator / self.total_calories) * 100.0 * TASTINESS_WEIGHT
+
+    def sp(
+        self,
+        cravings_satisfied: int = 0,
+        *,
+        server_mult: float = 1.0,
+        dinner_party_mult: float = 1.0,
+    ) -> float:
+        """Same value as `get_sp` on the tracked stomach."""
+        density = self.density()
+        bonus_pp = (
+            calculate_balanced_diet_bonus(list(density.values()))
+            + get_variety_bonus(self.variety_count)
+            + self.tastiness_bonus()
+        )
+        bonus = bonus_pp / 100.0 + cravings_satisfied * CRAVING_SATISFIED_FRAC
+        dinner_party_mult = dinner_party_multiplier(
+            dinner_party_mult, self.variety_count
+        )
+        nutrition_sp = sum(density.values()) * (1.0 + bonus) * dinner_party_mult
+        return (nutrition_sp + BASE_SKILL_POINTS) * server_mult
@@ -35,10 +35,14 @@ class AlgorithmConfig:
     low_calorie_threshold: int = 395
     low_calorie_penalty_strength: float = 2.48
     variety_bonus_cap_pp: float = 55.0
+    variety_half_life: float = 20.0
     tastiness_weight: float = 1.0
     balanced_diet_improvement_strength: float = 1.91
     repetition_penalty_strength: float = 1.25
     min_calorie_floor: int = 120
+    history_penalty_strength: float = 0.5
+    history_lookback: int = 20
+    zero_nutrient_tolerance_sp: float = 5.0
 
 
 @dataclass
@@ -47,6 +51,54 @@ class GameRulesConfig:
 
     variety_cal_threshold: int = 2000
     craving_satisfied_frac: float = 0.10
+    dinner_party_mode: str = "fixed"
+    dinner_party_per_food: float = 0.1
+    dinner_party_max: float = 3.0
+    balance_metric: str = "minmax"
+
+
+# Tastiness ratings scaled by the taste map; 99 (unknown) is always neutral
+TASTE_RATINGS: tuple[int, ...] = (-3, -2, -1, 0, 1, 2, 3)
+UNKNOWN_TASTINESS = 99
+
+
+@dataclass
+class TasteScaleConfig:
+    """Tastiness rating to multiplier mapping (fractions, 0.30 = +30 pp).
+
+    ``min_mult`` and ``max_mult`` set the worst (-3) and favorite (+3)
+    multipliers; ratings in between scale linearly toward 0 at "ok". An
+    explicit ``multipliers`` map overrides both.
+    """
+
+    min_mult: float = -0.30
+    max_mult: float = 0.30
+    multipliers: dict[int, float] | None = None
+
+    def as_multipliers(self) -> dict[int, float]:
+        """Full rating -> multiplier map, including the unknown sentinel."""
+        if self.multipliers is not None:
+            mapping = {int(k): float(v) for k, v in self.multipliers.items()}
+        else:
+            mapping = {
+                rating: round(
+                    (self.min_mult if rating < 0 else self.max_mult) * abs(rating) / 3,
+                    10,
+                )
+                for rating in TASTE_RATINGS
+            }
+        mapping[UNKNOWN_TASTINESS] = 0.0
+        return mapping
+
+
+@dataclass
+class NutrientWeightsConfig:
+    """Per-nutrient weights applied before summing and balancing."""
+
+    carbs: float = 1.0
+    protein: float = 1.0
+    fat: float = 1.0
+    vitamins: float = 1.0
 
 
 @dataclass
@@ -54,7 +106,8 @@ class SafetyConfig:
     """Safety limits."""
 
     max_iterations: int = 100
-    base_skill_points: int = 12
+    base_skill_points: float = 12.0
+    backup_count: int = 3
 
 
 @dataclass
@@ -71,6 +124,10 @@ class Config:
 
     algorithm: AlgorithmConfig = field(default_factory=AlgorithmConfig)
     game_rules: GameRulesConfig = field(default_factory=GameRulesConfig)
+    taste_scale: TasteScaleConfig = field(default_factory=TasteScaleConfig)
+    nutrient_weights: NutrientWeightsConfig = field(
+        default_factory=NutrientWeightsConfig
+    )
     safety: SafetyConfig = field(default_factory=SafetyConfig)
     display: DisplayConfig = field(default_factory=DisplayConfig)
 
@@ -97,20 +154,62 @@ def _validate_config(config: Config) -> list[str]:
         errors.append("algorithm.low_calorie_threshold must be >= 0")
     if config.algorithm.low_calorie_penalty_strength < 0:
         errors.append("algorithm.low_calorie_penalty_strength must be >= 0")
+    # A negative floor would let zero-calorie foods fill the plan
+    if config.algorithm.min_calorie_floor < 0:
+        errors.append("algorithm.min_calorie_floor must be >= 0")
     if config.algorithm.variety_bonus_cap_pp <= 0:
         errors.append("algorithm.variety_bonus_cap_pp must be > 0")
+    if config.algorithm.variety_half_life <= 0:
+        errors.append("algorithm.variety_half_life must be > 0")
+    if config.algorithm.history_penalty_strength < 0:
+        errors.append("algorithm.history_penalty_strength must be >= 0")
+    if config.algorithm.history_lookback < 1:
+        errors.append("algorithm.history_lookback must be >= 1")
+    if config.algorithm.zero_nutrient_tolerance_sp < 0:
+        errors.append("algorithm.zero_nutrient_tolerance_sp must be >= 0")
 
     # Game rules validations
     if config.game_rules.variety_cal_threshold <= 0:
         errors.append("game_rules.variety_cal_threshold must be > 0")
     if not (0.0 <= config.game_rules.craving_satisfied_frac <= 1.0):
         errors.append("game_rules.craving_satisfied_frac must be in [0, 1]")
+    if config.game_rules.dinner_party_mode not in ("fixed", "variety"):
+        errors.append("game_rules.dinner_party_mode must be 'fixed' or 'variety'")
+    if config.game_rules.dinner_party_per_food < 0:
+        errors.append("game_rules.dinner_party_per_food must be >= 0")
+    if config.game_rules.dinner_party_max < 1.0:
+        errors.append("game_rules.dinner_party_max must be >= 1")
+    if config.game_rules.balance_metric not in ("minmax", "cv"):
+        errors.append("game_rules.balance_metric must be 'minmax' or 'cv'")
+
+    # Taste scale validations
+    taste = config.taste_scale
+    if taste.min_mult > 0:
+        errors.append("taste_scale.min_mult must be <= 0")
+    if taste.max_mult < 0:
+        errors.append("taste_scale.max_mult must be >= 0")
+    if taste.multipliers is not None:
+        try:
+            keys = {int(k) for k in taste.multipliers}
+        except (TypeError, ValueError, AttributeError):
+            keys = set()
+        if keys - {UNKNOWN_TASTINESS} != set(TASTE_RATINGS):
+            errors.append("taste_scale.multipliers must map every rating -3..3")
+        elif taste.multipliers.get(UNKNOWN_TASTINESS, 0.0) != 0.0:
+            errors.append("taste_scale.multipliers must keep 99 (unknown) at 0")
+
+    # Nutrient weight validations
+    for name, weight in vars(config.nutrient_weights).items():
+        if weight < 0:
+            errors.append(f"nutrient_weights.{name} must be >= 0")
 
     # Safety validations
     if config.safety.max_iterations < 1:
         errors.append("safety.max_iterations must be >= 1")
     if config.safety.base_skill_points < 0:
         errors.append("safety.base_skill_points must be >= 0")
+    if config.safety.backup_count < 0:
+        errors.append("safety.backup_count must be >= 0")
 
     return errors
 
@@ -162,6 +261,10 @@ def load_config(path: str | Path | None = None) -> Config:
         _merge_dict_into_dataclass(data["algorithm"], config.algorithm)
     if "game_rules" in data:
         _merge_dict_into_dataclass(data["game_rules"], config.game_rules)
+    if "taste_scale" in data:
+        _merge_dict_into_dataclass(data["taste_scale"], config.taste_scale)
+    if "nutrient_weights" in data:
+        _merge_dict_into_dataclass(data["nutrient_weights"], config.nutrient_weights)
     if "safety" in data:
         _merge_dict_into_dataclass(data["safety"], config.safety)
     if "display" in data:
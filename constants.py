@@ -32,18 +32,11 @@ _cfg = get_cached_config()
 
 # --- Tastiness ---------------------------------------------------------------
 
-# Private, mutable source dict (edit values here only)
+# Private, mutable source dict built from the config taste scale
 # Fractions (e.g., +0.20 means +20 percentage points before weighting)
-_TASTINESS_MULTIPLIERS_DICT: Final[dict[int, float]] = {
-    -3: -0.30,
-    -2: -0.20,
-    -1: -0.10,
-    0: 0.00,
-    1: 0.10,
-    2: 0.20,
-    3: 0.30,
-    99: 0.00,  # unknown
-}
+_TASTINESS_MULTIPLIERS_DICT: Final[dict[int, float]] = (
+    _cfg.taste_scale.as_multipliers()
+)
 
 # Human labels for UI/logs; not used in calculations
 _TASTINESS_NAMES_DICT: Final[dict[int, str]] = {
@@ -72,6 +65,16 @@ TASTINESS_NAMES: Final[Mapping[int, str]] = MappingProxyType(_TASTINESS_NAMES_DI
 # Calories required per food for variety bonus eligibility
 VARIETY_CAL_THRESHOLD: Final[int] = _cfg.game_rules.variety_cal_threshold
 
+# Per-nutrient weights applied in `sum_all_weighted_nutrients` (read-only)
+NUTRIENT_WEIGHTS: Final[Mapping[str, float]] = MappingProxyType(
+    {
+        "carbs": _cfg.nutrient_weights.carbs,
+        "protein": _cfg.nutrient_weights.protein,
+        "fat": _cfg.nutrient_weights.fat,
+        "vitamins": _cfg.nutrient_weights.vitamins,
+    }
+)
+
 # ─────────────────────────────────────────────────────────────────────────────
 # Safety limits (from config)
 # ─────────────────────────────────────────────────────────────────────────────
@@ -79,8 +82,11 @@ VARIETY_CAL_THRESHOLD: Final[int] = _cfg.game_rules.variety_cal_threshold
 # Max food additions allowed in a single planning loop (safety)
 MAX_ITERATIONS: Final[int] = _cfg.safety.max_iterations
 
-# Default base SP points
-BASE_SKILL_POINTS: Final[int] = _cfg.safety.base_skill_points
+# Base SP added before the server multiplier
+BASE_SKILL_POINTS: Final[float] = _cfg.safety.base_skill_points
+
+# Rotated backups (.bak, .bak.1, ...) kept before overwriting the state file
+BACKUP_COUNT: Final[int] = _cfg.safety.backup_count
 
 # ─────────────────────────────────────────────────────────────────────────────
 # Tuner-derived constants (from config)
@@ -105,6 +111,14 @@ BALANCED_DIET_IMPROVEMENT_STRENGTH: Final[float] = (
 # Penalty for excessive repetition of same food.
 REPETITION_PENALTY_STRENGTH: Final[float] = _cfg.algorithm.repetition_penalty_strength
 
+# Rank penalty for recently eaten foods (plan --history-aware), and how
+# many logged bites it looks back over.
+HISTORY_PENALTY_STRENGTH: Final[float] = _cfg.algorithm.history_penalty_strength
+HISTORY_LOOKBACK: Final[int] = _cfg.algorithm.history_lookback
+
+# SP a bite may give up to supply a missing nutrient (plan --avoid-zero-nutrient)
+ZERO_NUTRIENT_TOLERANCE_SP: Final[float] = _cfg.algorithm.zero_nutrient_tolerance_sp
+
 # Scalars aren't runtime-frozen; Final + UPPERCASE signals "do not reassign"
 TASTINESS_WEIGHT: Final[float] = _cfg.algorithm.tastiness_weight
 
@@ -126,15 +140,27 @@ LOW_CALORIE_THRESHOLD: Final[int] = _cfg.algorithm.low_calorie_threshold
 # Strength of the low-calorie penalty (>=1; higher = harsher).
 LOW_CALORIE_PENALTY_STRENGTH: Final[float] = _cfg.algorithm.low_calorie_penalty_strength
 
-# Hard floor: skip foods with this many calories or fewer
+# Hard floor: skip foods with this many calories or fewer (>= 0, so
+# zero-calorie foods, which never use up the budget, are always skipped)
 MIN_CALORIE_FLOOR: Final[int] = _cfg.algorithm.min_calorie_floor
 
 # Asymptotic cap for variety bonus (see get_variety_bonus), in pp
 VARIETY_BONUS_CAP_PP: Final[float] = _cfg.algorithm.variety_bonus_cap_pp
 
+# Qualifying-food count that reaches half of VARIETY_BONUS_CAP_PP
+VARIETY_HALF_LIFE: Final[float] = _cfg.algorithm.variety_half_life
+
 # ─────────────────────────────────────────────────────────────────────────────
 # Craving system constants (from config)
 # ─────────────────────────────────────────────────────────────────────────────
 
 # Final SP multiplier: (1 + satisfied_count * CRAVING_SATISFIED_FRAC)
 CRAVING_SATISFIED_FRAC: Final[float] = _cfg.game_rules.craving_satisfied_frac
+
+# "fixed" (use the given multiplier) or "variety" (derive it from variety count)
+DINNER_PARTY_MODE: Final[str] = _cfg.game_rules.dinner_party_mode
+DINNER_PARTY_PER_FOOD: Final[float] = _cfg.game_rules.dinner_party_per_food
+DINNER_PARTY_MAX: Final[float] = _cfg.game_rules.dinner_party_max
+
+# "minmax" (game formula) or "cv" (1 - coefficient of variation) balance ratio
+BALANCE_METRIC: Final[str] = _cfg.game_rules.balance_metric
@@ -6,6 +6,14 @@ consumption, and exposes helpers used by the planner and scoring functions.
 Exports
 -------
 FoodStateManager
+StateSnapshot
+FoodNotFoundError
+MergeStrategy
+CatalogDiff
+diff_catalogs
+nutrient_scale_factors
+normalize_nutrients
+EDITABLE_FIELDS
 
 Notes
 -----
@@ -13,17 +21,196 @@ Computations of SP and bonuses are delegated to `calculations`.
 """
 
 import logging
+from collections.abc import Callable
+from dataclasses import (
+    dataclass,
+)
+from enum import Enum
 
 from calculations import (
     get_sp,
     is_variety_qualifying,
 )
+from constants import (
+    TASTINESS_MULTIPLIERS,
+)
 from models.food import (
     Food,
+    NUTRIENT_FIELDS,
 )
 
 logger = logging.getLogger(__name__)
 
+# Food attributes that may be changed one at a time via `edit_food`
+EDITABLE_FIELDS: tuple[str, ...] = (
+    "calories",
+    "carbs",
+    "protein",
+    "fat",
+    "vitamins",
+    "tastiness",
+    "available",
+)
+
+
+class FoodNotFoundError(LookupError):
+    """Raised when a food name does not match any known food."""
+
+    def __init__(
+        self,
+        name: str,
+    ) -> None:
+        super().__init__(f"Food '{name}' not found")
+        self.name = name
+
+
+class MergeStrategy(str, Enum):
+    """How `FoodStateManager.merge` treats foods present in both catalogs."""
+
+    KEEP_EXISTING = "keep"  # leave the current record untouched
+    OVERWRITE = "overwrite"  # replace it with the incoming record
+    SUM_AVAILABLE = "sum"  # keep it, but add the incoming available count
+
+
+# Food attributes compared by `diff_catalogs`
+DIFF_FIELDS: tuple[str, ...] = (*EDITABLE_FIELDS, "stomach")
+
+
+@dataclass(frozen=True)
+class StateSnapshot:
+    """Stomach and availability counts captured by `FoodStateManager.snapshot`.
+
+    Counts are keyed by lowercased food name, mirroring the manager's
+    sparse maps, so no `Food` records are copied.
+    """
+
+    stomach: dict[str, int]
+    available: dict[str, int]
+
+
+@dataclass
+class CatalogDiff:
+    """What changed between two food catalogs.
+
+    Attributes
+    ----------
+    added : list of str
+        Names only in the second catalog.
+    removed : list of str
+        Names only in the first catalog.
+    changed : dict[str, dict[str, tuple[int, int]]]
+        Per matched food, ``field -> (old, new)`` for each differing field.
+    """
+
+    added: list[str]
+    removed: list[str]
+    changed: dict[str, dict[str, tuple[int, int]]]
+
+    def is_empty(
+        self,
+    ) -> bool:
+        """Whether the catalogs match on every compared field."""
+        return not (self.added or self.removed or self.changed)
+
+
+def diff_catalogs(
+    a: list[Food],
+    b: list[Food],
+) -> CatalogDiff:
+    """Compare catalog *a* (old) with catalog *b* (new).
+
+    Foods are matched by lowercased name, as in `FoodStateManager.merge`.
+
+    Parameters
+    ----------
+    a, b : list[Food]
+        Catalogs to compare.
+
+    Returns
+    -------
+    CatalogDiff
+        Added/removed names (in catalog order) and per-field changes.
+    """
+    old = {food.name.lower(): food for food in a}
+    new = {food.name.lower(): food for food in b}
+    diff = CatalogDiff(
+        added=[food.name for key, food in new.items() if key not in old],
+        removed=[food.name for key, food in old.items() if key not in new],
+        changed={},
+    )
+    for key, before in old.items():
+        after = new.get(key)
+        if after is None:
+            continue
+        fields = {
+            name: (getattr(before, name), getattr(after, name))
+            for name in DIFF_FIELDS
+            if getattr(before, name) != getattr(after, name)
+        }
+        if fields:
+            diff.changed[before.name] = fields
+    return diff
+
+
+def nutrient_scale_factors(
+    foods: list[Food],
+) -> dict[str, float]:
+    """Per-nutrient multipliers that bring every dimension to one range.
+
+    Each nutrient's largest value across *foods* is mapped onto the largest
+    value of any nutrient, so the dominant scale is kept and smaller ones
+    are stretched to match. A nutrient that is zero everywhere keeps 1.0.
+
+    Parameters
+    ----------
+    foods : list[Food]
+        Catalog to measure.
+
+    Returns
+    -------
+    dict[str, float]
+        Factor per name in `NUTRIENT_FIELDS`; divide by it to invert.
+    """
+    peaks = {
+        name: max((getattr(food, name) for food in foods), default=0)
+        for name in NUTRIENT_FIELDS
+    }
+    target = max(peaks.values(), default=0)
+    return {
+        name: target / peak if peak > 0 else 1.0 for name, peak in peaks.items()
+    }
+
+
+def normalize_nutrients(
+    foods: list[Food],
+    factors: dict[str, float],
+) -> list[Food]:
+    """Copies of *foods* with each nutrient multiplied by its factor.
+
+    Calories, tastiness, and counts are unchanged, and *foods* itself is
+    not modified, so the originals can still be saved. Values are rounded
+    like every other field, which keeps the order within each nutrient.
+
+    Parameters
+    ----------
+    foods : list[Food]
+        Records to transform.
+    factors : dict[str, float]
+        Multipliers from `nutrient_scale_factors`.
+
+    Returns
+    -------
+    list[Food]
+        New records in the same order.
+    """
+    normalized = []
+    for food in foods:
+        scaled = Food.from_dict(food.to_dict())
+        for name in NUTRIENT_FIELDS:
+            setattr(scaled, name, round(getattr(food, name) * factors.get(name, 1.0)))
+        normalized.append(scaled)
+    return normalized
+
 
 class FoodStateManager:
     """Manage foods, stomach, and availability.
@@ -41,6 +228,9 @@ class FoodStateManager:
         Counts consumed (sparse: only positive entries stored).
     available : dict[Food, int]
         Counts available to consume (sparse).
+    on_consume : Callable[[Food], None] or None
+        Optional hook called after each successful `consume` (e.g., to
+        append to the consumption history). ``None`` by default.
     """
 
     def __init__(
@@ -61,6 +251,7 @@ class FoodStateManager:
         # Omit zeros to keep dicts small.
         self.stomach = {}
         self.available = {}
+        self.on_consume: Callable[[Food], None] | None = None
         for food in foods:
             # Seed only positive stomach counts
             if food.stomach > 0:
@@ -88,6 +279,305 @@ class FoodStateManager:
         # Case-insensitive by design
         return self.foods.get(name.lower())
 
+    def edit_food(
+        self,
+        name: str,
+        field: str,
+        value: int,
+    ) -> Food:
+        """Change a single field of an existing food.
+
+        Parameters
+        ----------
+        name : str
+            Food name (case-insensitive).
+        field : str
+            One of `EDITABLE_FIELDS`.
+        value : int
+            New value; must be ``>= 0``, and a valid rating for
+            ``tastiness``.
+
+        Returns
+        -------
+        Food
+            The updated canonical food.
+
+        Raises
+        ------
+        FoodNotFoundError
+            If no food matches *name*.
+        ValueError
+            If *field* is not editable or *value* is out of range.
+        """
+        food = self.get_food(name)
+        if food is None:
+            raise FoodNotFoundError(name)
+        if field not in EDITABLE_FIELDS:
+            choices = ", ".join(EDITABLE_FIELDS)
+            raise ValueError(f"Unknown field '{field}' (choose from: {choices})")
+        if field == "tastiness":
+            if value not in TASTINESS_MULTIPLIERS:
+                raise ValueError(
+                    f"Invalid tastiness {value}: use -3 to 3, or 99 for unknown"
+                )
+        elif value < 0:
+            raise ValueError(f"{field} cannot be negative (got {value})")
+
+        if field == "available":
+            self.set_availability(name, value)
+        else:
+            setattr(food, field, value)
+        return food
+
+    def apply_ratings(
+        self,
+        ratings: list[tuple[str, int]],
+    ) -> list[str]:
+        """Set tastiness for several foods at once.
+
+        Every entry is checked before any rating changes, so a bad name or
+        value leaves the catalog untouched.
+
+        Parameters
+        ----------
+        ratings : list[tuple[str, int]]
+            ``(name, rating)`` pairs; names are case-insensitive.
+
+        Returns
+        -------
+        list[str]
+            Canonical names whose rating actually changed, in input order.
+
+        Raises
+        ------
+        FoodNotFoundError
+            If a name matches no food.
+        ValueError
+            If a rating is not on the tastiness scale.
+        """
+        resolved = []
+        for name, rating in ratings:
+            food = self.get_food(name)
+            if food is None:
+                raise FoodNotFoundError(name)
+            if rating not in TASTINESS_MULTIPLIERS:
+                raise ValueError(
+                    f"Invalid tastiness {rating} for '{food.name}': "
+                    "use -3 to 3, or 99 for unknown"
+                )
+            resolved.append((food, rating))
+
+        changed = []
+        for food, rating in resolved:
+            if food.tastiness != rating:
+                food.tastiness = rating
+                changed.append(food.name)
+        return changed
+
+    def set_availability(
+        self,
+        name: str,
+        value: int,
+    ) -> Food:
+        """Set the available count of one food, leaving others unchanged.
+
+        Parameters
+        ----------
+        name : str
+            Food name (case-insensitive).
+        value : int
+            New available count (``>= 0``).
+
+        Returns
+        -------
+        Food
+            The updated canonical food.
+
+        Raises
+        ------
+        FoodNotFoundError
+            If no food matches *name*.
+        ValueError
+            If *value* is negative.
+        """
+        food = self.get_food(name)
+        if food is None:
+            raise FoodNotFoundError(name)
+        if value < 0:
+            raise ValueError(f"available cannot be negative (got {value})")
+
+        food.available = value
+        # Keep the sparse availability map in sync with the canonical record
+        if value > 0:
+            self.available[food] = value
+        else:
+            self.available.pop(food, None)
+        return food
+
+    def merge(
+        self,
+        other: list[Food],
+        strategy: MergeStrategy = MergeStrategy.KEEP_EXISTING,
+    ) -> tuple[int, int]:
+        """Merge another catalog into this one.
+
+        Foods are matched by lowercased name. Unmatched foods are always
+        added; matched foods are handled according to *strategy*. Stomach
+        counts are personal, so incoming ones are never taken.
+
+        Parameters
+        ----------
+        other : list[Food]
+            Incoming foods (e.g., a catalog shared by another player).
+        strategy : MergeStrategy, optional
+            Conflict handling for matched foods, by default
+            ``KEEP_EXISTING``.
+
+        Returns
+        -------
+        tuple[int, int]
+            ``(added, updated)`` counts.
+        """
+        added = 0
+        updated = 0
+        for incoming in other:
+            key = incoming.name.lower()
+            existing = self.foods.get(key)
+            if existing is None:
+                food = Food.from_dict({**incoming.to_dict(), "Stomach": 0})
+                self.foods[key] = food
+                added += 1
+            elif strategy is MergeStrategy.OVERWRITE:
+                for field, value in vars(incoming).items():
+                    if field != "stomach":
+                        setattr(existing, field, value)
+                food = existing
+                updated += 1
+            elif strategy is MergeStrategy.SUM_AVAILABLE:
+                existing.available += incoming.available
+                food = existing
+                updated += 1
+            else:
+                continue
+
+            # Keep the sparse maps in sync with the canonical record
+            for counts, value in (
+                (self.stomach, food.stomach),
+                (self.available, food.available),
+            ):
+                counts.pop(food, None)
+                if value > 0:
+                    counts[food] = value
+        return added, updated
+
+    def reseed_availability(
+        self,
+        catalog: list[Food],
+    ) -> tuple[int, int]:
+        """Load a fresh catalog while keeping today's stomach.
+
+        Unlike `merge`, the incoming file is taken as the new stock list:
+        matched foods get its calories, nutrients, and available count but
+        keep their stomach count and tastiness rating, and known foods it
+        does not list drop to zero availability. Unmatched incoming foods
+        are added with an empty stomach.
+
+        Parameters
+        ----------
+        catalog : list[Food]
+            The new catalog (e.g., this week's food file).
+
+        Returns
+        -------
+        tuple[int, int]
+            ``(added, updated)`` counts.
+        """
+        added = 0
+        updated = 0
+        listed = set()
+        for incoming in catalog:
+            key = incoming.name.lower()
+            listed.add(key)
+            existing = self.foods.get(key)
+            if existing is None:
+                food = Food.from_dict({**incoming.to_dict(), "Stomach": 0})
+                self.foods[key] = food
+                if food.available > 0:
+                    self.available[food] = food.available
+                added += 1
+                continue
+            for field in ("calories", *NUTRIENT_FIELDS):
+                setattr(existing, field, getattr(incoming, field))
+            self.set_availability(existing.name, max(incoming.available, 0))
+            updated += 1
+
+        for key, food in self.foods.items():
+            if key not in listed:
+                self.set_availability(food.name, 0)
+        return added, updated
+
+    def overlay_availability(
+        self,
+        pantry: list[Food],
+    ) -> list[str]:
+        """Take available counts from *pantry*, keeping everything else.
+
+        Foods are matched by lowercased name, as in `merge`. Only the
+        ``available`` field is read from pantry records; nutrients,
+        calories, tastiness, and stomach counts stay as loaded.
+
+        Parameters
+        ----------
+        pantry : list[Food]
+            Stock records (e.g., a weekly-stock file).
+
+        Returns
+        -------
+        list[str]
+            Pantry names that match no known food, in pantry order.
+        """
+        unmatched = []
+        for stock in pantry:
+            if self.get_food(stock.name) is None:
+                unmatched.append(stock.name)
+                continue
+            self.set_availability(stock.name, max(stock.available, 0))
+        return unmatched
+
+    def replace_food(
+        self,
+        food: Food,
+    ) -> Food:
+        """Swap in a new record for an existing food, keeping its counts.
+
+        Parameters
+        ----------
+        food : Food
+            Replacement record; matched to the existing food by name.
+
+        Returns
+        -------
+        Food
+            The record that was replaced.
+
+        Raises
+        ------
+        FoodNotFoundError
+            If no food matches ``food.name``.
+        """
+        key = food.name.lower()
+        if key not in self.foods:
+            raise FoodNotFoundError(food.name)
+        old = self.foods[key]
+        food.stomach = self.stomach.get(old, 0)
+        food.available = self.available.get(old, 0)
+        self.foods[key] = food
+        # Re-key the sparse maps so they hold the new instance
+        for counts in (self.stomach, self.available):
+            if old in counts:
+                counts[food] = counts.pop(old)
+        return old
+
     def consume(
         self,
         food: Food,
@@ -118,8 +608,103 @@ class FoodStateManager:
         self.foods[food.name.lower()].stomach = self.stomach[food]
         self.foods[food.name.lower()].available = self.available[food]
 
+        if self.on_consume is not None:
+            self.on_consume(food)
         return True
 
+    def consume_n(
+        self,
+        name: str,
+        n: int,
+    ) -> int:
+        """Consume up to *n* units of a food in one step.
+
+        Parameters
+        ----------
+        name : str
+            Food name (case-insensitive).
+        n : int
+            Units requested (``>= 0``); capped at the available count.
+
+        Returns
+        -------
+        int
+            Units actually consumed (``0`` when out of stock).
+
+        Raises
+        ------
+        FoodNotFoundError
+            If no food matches *name*.
+        ValueError
+            If *n* is negative.
+        """
+        food = self.get_food(name)
+        if food is None:
+            raise FoodNotFoundError(name)
+        if n < 0:
+            raise ValueError(f"cannot consume a negative amount (got {n})")
+
+        taken = min(n, self.available.get(food, 0))
+        if taken <= 0:
+            return 0
+        self.stomach[food] = self.stomach.get(food, 0) + taken
+        self.available[food] -= taken
+        food.stomach = self.stomach[food]
+        food.available = self.available[food]
+
+        if self.on_consume is not None:
+            for _ in range(taken):
+                self.on_consume(food)
+        return taken
+
+    def snapshot(
+        self,
+    ) -> StateSnapshot:
+        """Capture the current stomach and availability counts.
+
+        Cheaper than copying the manager: only the counts are stored, so
+        roll back with `restore` after a what-if run on the same catalog.
+
+        Returns
+        -------
+        StateSnapshot
+            Counts keyed by lowercased food name.
+        """
+        return StateSnapshot(
+            stomach={food.name.lower(): n for food, n in self.stomach.items()},
+            available={food.name.lower(): n for food, n in self.available.items()},
+        )
+
+    def restore(
+        self,
+        snap: StateSnapshot,
+    ) -> None:
+        """Roll stomach and availability back to a `snapshot`.
+
+        Counts are mirrored onto the canonical `Food` records as `consume`
+        does. The consumption hook is not called.
+
+        Parameters
+        ----------
+        snap : StateSnapshot
+            Snapshot taken from this manager.
+
+        Raises
+        ------
+        ValueError
+            If the snapshot names foods this manager no longer has.
+        """
+        unknown = (snap.stomach.keys() | snap.available.keys()) - self.foods.keys()
+        if unknown:
+            raise ValueError(
+                f"Snapshot has foods not in this catalog: {', '.join(sorted(unknown))}"
+            )
+        self.stomach = {self.foods[key]: n for key, n in snap.stomach.items()}
+        self.available = {self.foods[key]: n for key, n in snap.available.items()}
+        for key, food in self.foods.items():
+            food.stomach = snap.stomach.get(key, 0)
+            food.available = snap.available.get(key, 0)
+
     def can_consume(
         self,
         food: Food,
@@ -197,6 +782,20 @@ class FoodStateManager:
             if is_variety_qualifying(food_item, quantity)
         }
 
+    def stomach_ordered(
+        self,
+    ) -> list[tuple[Food, int]]:
+        """List stomach contents in a stable order for display or export.
+
+        Returns
+        -------
+        list of tuple[Food, int]
+            ``(food, quantity)`` for foods with a positive count, sorted by
+            case-insensitive name rather than by when they were eaten.
+        """
+        eaten = [(food, n) for food, n in self.stomach.items() if n > 0]
+        return sorted(eaten, key=lambda pair: pair[0].name.lower())
+
     def all_available(
         self,
     ) -> list[Food]:
@@ -210,6 +809,19 @@ class FoodStateManager:
         # Return Food objects that still have units available (not names/ids)
         return [food for food in self.available if self.available[food] > 0]
 
+    def available_calories(
+        self,
+    ) -> float:
+        """Total calories of all food still in stock.
+
+        Returns
+        -------
+        float
+            Sum of ``calories * available`` over every food; a budget
+            larger than this cannot be filled.
+        """
+        return float(sum(food.calories * n for food, n in self.available.items()))
+
     def reset_stomach(
         self,
     ) -> None:
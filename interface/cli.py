@@ -1,6 +1,116 @@
 """Command-line argument builder (parser only)."""
 
 import argparse
+import math
+
+from interface.render import (
+    parse_precision,
+)
+from models.food import (
+    NUTRIENT_FIELDS,
+)
+from planner import (
+    MAX_LOOKAHEAD,
+)
+
+
+def parse_serving(
+    text: str,
+) -> tuple[str, float]:
+    """Parse a ``FOOD=FACTOR`` serving override for ``plan --servings``."""
+    name, sep, factor = text.rpartition("=")
+    try:
+        value = float(factor)
+    except ValueError:
+        value = 0.0
+    if not sep or not name.strip() or value <= 0:
+        raise argparse.ArgumentTypeError(
+            f"expected FOOD=FACTOR with a positive factor, got '{text}'"
+        )
+    return name.strip(), value
+
+
+def parse_seed(
+    text: str,
+) -> tuple[str, int]:
+    """Parse a ``FOOD:QTY`` prior bite for ``plan --seed``."""
+    name, sep, quantity = text.rpartition(":")
+    try:
+        value = int(quantity)
+    except ValueError:
+        value = 0
+    if not sep or not name.strip() or value <= 0:
+        raise argparse.ArgumentTypeError(
+            f"expected FOOD:QTY with a positive whole quantity, got '{text}'"
+        )
+    return name.strip(), value
+
+
+def parse_budget_sweep(
+    text: str,
+) -> list[int]:
+    """Parse a ``FROM:TO:STEP`` calorie range for ``plan --budget-sweep``.
+
+    Both ends are included when *STEP* lands on *TO*.
+    """
+    try:
+        start, stop, step = (int(part) for part in text.split(":"))
+    except ValueError:
+        start = stop = step = 0
+    if start <= 0 or stop < start or step <= 0:
+        raise argparse.ArgumentTypeError(
+            f"expected FROM:TO:STEP with 0 < FROM <= TO and STEP > 0, got '{text}'"
+        )
+    return list(range(start, stop + 1, step))
+
+
+def parse_beam_width(
+    text: str,
+) -> int:
+    """Parse the ``plan --beam`` width (at least one partial plan)."""
+    try:
+        value = int(text)
+    except ValueError:
+        value = 0
+    if value < 1:
+        raise argparse.ArgumentTypeError(
+            f"expected a whole number of plans >= 1, got '{text}'"
+        )
+    return value
+
+
+def parse_positive_int(
+    text: str,
+) -> int:
+    """Parse a count cap such as ``plan --max-bites`` (at least 1)."""
+    try:
+        value = int(text)
+    except ValueError:
+        value = 0
+    if value < 1:
+        raise argparse.ArgumentTypeError(f"expected a whole number >= 1, got '{text}'")
+    return value
+
+
+def parse_targets(
+    text: str,
+) -> dict[str, float]:
+    """Parse ``NUTRIENT=GOAL,...`` totals for ``plan --targets``."""
+    goals: dict[str, float] = {}
+    for part in text.split(","):
+        name, sep, goal = part.partition("=")
+        name = name.strip().lower()
+        try:
+            value = float(goal)
+        except ValueError:
+            value = -1.0
+        if not sep or name not in NUTRIENT_FIELDS or not 0 <= value < math.inf:
+            raise argparse.ArgumentTypeError(
+                f"expected NUTRIENT=GOAL[,...] with NUTRIENT one of "
+                f"{', '.join(NUTRIENT_FIELDS)} and a finite GOAL >= 0, got '{text}'"
+            )
+        goals[name] = value
+    return goals
 
 
 def build_parser() -> argparse.ArgumentParser:
@@ -9,9 +119,9 @@ def build_parser() -> argparse.ArgumentParser:
     Returns
     -------
     argparse.ArgumentParser
-        Configured parser with subcommands (``plan``,
-        ``rate-unknowns``, ``reset``) and global options
-        (e.g., verbosity, config file).
+        Configured parser with subcommands (``plan``, ``predict``,
+        ``rate-unknowns``, ``reset``, and the catalog/history utilities)
+        and global options (e.g., verbosity, config file).
     """
     parser = argparse.ArgumentParser(
         prog="eco",
@@ -32,6 +142,33 @@ def build_parser() -> argparse.ArgumentParser:
         default=0,
         help="-v for INFO, -vv for DEBUG",
     )
+    parser.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Hide informational lines and log warnings (errors still print)",
+    )
+    parser.add_argument(
+        "--compact-json",
+        action="store_true",
+        help="Save food_state.json on one line instead of indented",
+    )
+    parser.add_argument(
+        "--file",
+        default=None,
+        metavar="PATH",
+        help=(
+            "Food state file to read and save instead of food_state.json; "
+            "'-' reads stdin and writes the saved state to stdout"
+        ),
+    )
+    parser.add_argument(
+        "--precision",
+        type=parse_precision,
+        default=2,
+        metavar="N",
+        help="Decimals shown for SP values in plan output (default: 2)",
+    )
 
     # Global --config for custom configuration file
     parser.add_argument(
@@ -56,6 +193,13 @@ def build_parser() -> argparse.ArgumentParser:
         metavar="PATH",
         help="Path to mod-exported game_state.json (skips interactive prompts)",
     )
+    plan_parser.add_argument(
+        "--cravings-file",
+        type=str,
+        default=None,
+        metavar="PATH",
+        help="Add cravings from a file (one per line, # for comments)",
+    )
     plan_parser.add_argument(
         "-s",
         "--server-mult",
@@ -70,12 +214,252 @@ def build_parser() -> argparse.ArgumentParser:
         default=1.0,
         help="Dinner party multiplier (1.0-3.0, default: 1.0)",
     )
+    plan_parser.add_argument(
+        "--max-repeats",
+        type=parse_positive_int,
+        default=None,
+        metavar="N",
+        help="Never plan more than N units of any one food (default: no cap)",
+    )
+    plan_parser.add_argument(
+        "--max-bites",
+        type=parse_positive_int,
+        default=None,
+        metavar="N",
+        help="Stomach capacity: stop after N bites in total (default: no cap)",
+    )
+    plan_parser.add_argument(
+        "--fail-on-iteration-cap",
+        action="store_true",
+        help=(
+            "Exit with an error, saving nothing, if planning is cut short by "
+            "safety.max_iterations"
+        ),
+    )
+    plan_parser.add_argument(
+        "--greedy-lookahead",
+        type=int,
+        choices=range(1, MAX_LOOKAHEAD + 1),
+        default=1,
+        metavar="K",
+        help=(
+            "Score each bite by the SP reached K greedy bites later, "
+            f"1-{MAX_LOOKAHEAD} (default: 1, plain greedy)"
+        ),
+    )
+    plan_parser.add_argument(
+        "--max-distinct-foods",
+        type=parse_positive_int,
+        default=None,
+        metavar="N",
+        help=(
+            "Use at most N different foods; once reached, only repeat them "
+            "(default: no cap)"
+        ),
+    )
+    plan_parser.add_argument(
+        "--min-bite-calories",
+        type=int,
+        default=None,
+        metavar="N",
+        help="Skip foods under N calories unless nothing larger fits",
+    )
+    plan_parser.add_argument(
+        "--require",
+        action="append",
+        default=[],
+        metavar="FOOD",
+        help="Eat one unit of FOOD before planning the rest (repeatable)",
+    )
+    plan_parser.add_argument(
+        "--objective",
+        choices=["sp", "variety"],
+        default="sp",
+        help=(
+            "What bite selection maximizes: SP gain, or the number of foods "
+            "reaching the variety calorie threshold"
+        ),
+    )
+    plan_parser.add_argument(
+        "--preserve-stomach",
+        action="store_true",
+        help="Save the file's stomach counts unchanged; planned bites still count",
+    )
+    plan_parser.add_argument(
+        "--overshoot-tolerance",
+        type=int,
+        default=0,
+        metavar="CAL",
+        help="Allow one last bite up to CAL calories over budget if it raises SP",
+    )
+    plan_parser.add_argument(
+        "--explain-tie",
+        action="store_true",
+        help="Log each bite's near-equal finalists and their ranking terms",
+    )
+    plan_parser.add_argument(
+        "--tiebreak-seed",
+        type=int,
+        default=None,
+        metavar="N",
+        help="Break exact ranking ties by remaining stock, seeded with N "
+        "(default: by name)",
+    )
+    plan_parser.add_argument(
+        "--servings",
+        type=parse_serving,
+        action="append",
+        default=[],
+        metavar="FOOD=FACTOR",
+        help="Plan with FOOD's calories and nutrients scaled by FACTOR (repeatable)",
+    )
+    plan_parser.add_argument(
+        "--normalize",
+        action="store_true",
+        help="Rescale each nutrient to a common range for this plan (not saved)",
+    )
+    plan_parser.add_argument(
+        "--seed",
+        type=parse_seed,
+        action="append",
+        default=[],
+        metavar="FOOD:QTY",
+        help="Eat QTY units of FOOD before planning, e.g. earlier meals (repeatable)",
+    )
+    plan_parser.add_argument(
+        "--beam",
+        type=parse_beam_width,
+        default=None,
+        metavar="N",
+        help="Search N partial plans at once instead of greedy (ignored with -i)",
+    )
+    plan_parser.add_argument(
+        "--targets",
+        type=parse_targets,
+        default=None,
+        metavar="NUTRIENT=GOAL,...",
+        help=(
+            "After planning, compare total nutrient intake with goals, "
+            "e.g. protein=150,vitamins=80"
+        ),
+    )
+    plan_parser.add_argument(
+        "--explain-variety",
+        action="store_true",
+        help=(
+            "After planning, show each eaten food's calories toward the "
+            "variety threshold and how many more units it needs"
+        ),
+    )
+    plan_parser.add_argument(
+        "--budget-sweep",
+        type=parse_budget_sweep,
+        default=None,
+        metavar="FROM:TO:STEP",
+        help="Plan at each budget in the range and print SP per budget (not saved)",
+    )
+    plan_parser.add_argument(
+        "--compare",
+        nargs="?",
+        const="tuner_best.json",
+        default=None,
+        metavar="PATH",
+        help="Compare default knobs with the tuner's best (default: tuner_best.json)",
+    )
+    plan_parser.add_argument(
+        "--show-macros",
+        action="store_true",
+        help="Show running nutrient density and balance bonus after each bite",
+    )
+    plan_parser.add_argument(
+        "--aggregate",
+        action="store_true",
+        help="Show repeated bites of the same food as one row (e.g. Apple ×3)",
+    )
+    plan_parser.add_argument(
+        "--output",
+        choices=["table", "markdown"],
+        default="table",
+        help="Plan format: aligned text table or a Markdown table for pasting",
+    )
+    plan_parser.add_argument(
+        "--pantry-from",
+        type=str,
+        default=None,
+        metavar="PATH",
+        help=(
+            "Take available counts from another food file (matched by "
+            "name; - reads stdin and needs --import)"
+        ),
+    )
+    plan_parser.add_argument(
+        "--save-plan",
+        type=str,
+        default=None,
+        metavar="PATH",
+        help=(
+            "Also write the plan and its totals to PATH as JSON; - writes "
+            "them to stdout and moves the rest of the output to stderr"
+        ),
+    )
+    plan_parser.add_argument(
+        "--interactive",
+        "-i",
+        action="store_true",
+        help="Pick each bite yourself from the top-ranked options",
+    )
+    plan_parser.add_argument(
+        "--log",
+        action="store_true",
+        help="Append each planned bite to the consumption history",
+    )
+    plan_parser.add_argument(
+        "--explain-why-not",
+        type=str,
+        default=None,
+        metavar="FOOD",
+        help="Report how FOOD ranked for the first bite and why it lost",
+    )
+    plan_parser.add_argument(
+        "--avoid-zero-nutrient",
+        action="store_true",
+        help="Prefer bites that supply a nutrient the stomach has none of, "
+        "within a small SP cost",
+    )
+    plan_parser.add_argument(
+        "--history-aware",
+        action="store_true",
+        help="Rank recently eaten foods (from the history) a little lower",
+    )
 
     # Subcommand: prompt to rate tastiness for available foods marked unknown
-    subparsers.add_parser(
+    rate_unknowns_parser = subparsers.add_parser(
         "rate-unknowns",
         help="Rate unknown tastiness for available foods",
     )
+    rate_unknowns_parser.add_argument(
+        "--all",
+        action="store_true",
+        help="Re-rate every available food; Enter keeps its current rating",
+    )
+
+    # Subcommand: set (or re-set) tastiness for one named food
+    rate_parser = subparsers.add_parser(
+        "rate",
+        help="Set tastiness for one food (prompts when no value is given)",
+    )
+    rate_parser.add_argument(
+        "name",
+        type=str,
+        help="Name of the food to rate (case-insensitive)",
+    )
+    rate_parser.add_argument(
+        "value",
+        type=int,
+        nargs="?",
+        default=None,
+        help="Rating from -3 to 3, or 99 for unknown",
+    )
 
     # Subcommand: predict SP for a food (validation mode)
     predict_parser = subparsers.add_parser(
@@ -130,6 +514,177 @@ def build_parser() -> argparse.ArgumentParser:
         help="Dinner party multiplier (1.0-3.0, default: 1.0)",
     )
 
+    # Subcommand: rank foods by standalone SP efficiency
+    top_foods_parser = subparsers.add_parser(
+        "top-foods",
+        help="Rank foods by SP per calorie on an empty stomach",
+    )
+    top_foods_parser.add_argument(
+        "-n",
+        type=int,
+        default=10,
+        help="Number of foods to show (default: 10)",
+    )
+
+    # Subcommand: explain what is holding current SP back
+    subparsers.add_parser(
+        "analyze",
+        help="Diagnose the current stomach and suggest a balancing food",
+    )
+
+    # Subcommand: show the SP formula with the current stomach's numbers
+    explain_parser = subparsers.add_parser(
+        "explain",
+        help="Show the SP formula filled in with the current stomach's values",
+    )
+    explain_parser.add_argument(
+        "--satisfied",
+        type=int,
+        default=0,
+        help="Number of cravings already satisfied today (default: 0)",
+    )
+    explain_parser.add_argument(
+        "-s",
+        "--server-mult",
+        type=float,
+        default=1.0,
+        help="Server skill gain multiplier (default: 1.0)",
+    )
+    explain_parser.add_argument(
+        "-d",
+        "--dinner-party",
+        type=float,
+        default=1.0,
+        help="Dinner party multiplier (1.0-3.0, default: 1.0)",
+    )
+
+    # Subcommand: change one field of an existing food
+    edit_parser = subparsers.add_parser(
+        "edit",
+        help="Change one field of an existing food",
+    )
+    edit_parser.add_argument(
+        "name",
+        type=str,
+        help="Name of the food to edit (case-insensitive)",
+    )
+    edit_parser.add_argument(
+        "field",
+        type=str,
+        help=(
+            "Field to change: calories, carbs, protein, fat, vitamins, "
+            "tastiness, or available"
+        ),
+    )
+    edit_parser.add_argument(
+        "value",
+        type=int,
+        help="New integer value",
+    )
+
+    # Subcommand: set availability for one food (or all foods)
+    restock_parser = subparsers.add_parser(
+        "restock",
+        help="Set available units for one food or every food",
+    )
+    restock_parser.add_argument(
+        "amount",
+        type=int,
+        help="New available count",
+    )
+    restock_target = restock_parser.add_mutually_exclusive_group(required=True)
+    restock_target.add_argument(
+        "--food",
+        "-f",
+        type=str,
+        help="Name of the food to restock",
+    )
+    restock_target.add_argument(
+        "--all",
+        action="store_true",
+        help="Restock every food to the same amount",
+    )
+
+    # Subcommand: merge another food file into the current state
+    merge_parser = subparsers.add_parser(
+        "merge",
+        help="Merge foods from another food_state-style JSON or CSV file",
+    )
+    merge_parser.add_argument(
+        "other_file",
+        type=str,
+        help="Path to the food JSON (or .csv) file to merge in; - reads stdin",
+    )
+    merge_parser.add_argument(
+        "--header-map",
+        action="append",
+        default=[],
+        metavar="HEADER=FIELD",
+        help=(
+            "For CSV files: read column HEADER as food FIELD, on top of "
+            "the built-in aliases such as kcal or Prot (repeatable)"
+        ),
+    )
+    merge_parser.add_argument(
+        "--strategy",
+        choices=("keep", "overwrite", "sum"),
+        default="keep",
+        help=(
+            "For foods in both files: keep existing, overwrite with the "
+            "incoming record, or sum available counts (default: keep)"
+        ),
+    )
+    merge_parser.add_argument(
+        "--reseed-availability",
+        action="store_true",
+        help=(
+            "Treat the file as a fresh catalog: take its nutrients and stock, "
+            "keep your stomach and ratings, and zero stock it does not list "
+            "(replaces --strategy)"
+        ),
+    )
+
+    # Subcommand: compare the current state with another food file
+    diff_parser = subparsers.add_parser(
+        "diff",
+        help="Show foods added, removed, or changed in another food file",
+    )
+    diff_parser.add_argument(
+        "other_file",
+        type=str,
+        help="Path to the food JSON file to compare against; - reads stdin",
+    )
+
+    # Subcommand: show the consumption history written by `plan --log`
+    history_parser = subparsers.add_parser(
+        "history",
+        help="Show recently logged bites with running calorie totals",
+    )
+    history_parser.add_argument(
+        "--limit",
+        "-n",
+        type=int,
+        default=20,
+        help="Number of most recent entries to show (default: 20)",
+    )
+
+    # Subcommand: report (and optionally fix) invalid foods in the state file
+    validate_parser = subparsers.add_parser(
+        "validate",
+        help="Report foods with negative values or invalid tastiness",
+    )
+    validate_parser.add_argument(
+        "--fix",
+        action="store_true",
+        help="Clamp negatives to 0 and reset invalid tastiness to unknown",
+    )
+
+    # Subcommand: plan twice from the same state and compare the results
+    subparsers.add_parser(
+        "self-test",
+        help="Check that planning the current foods twice gives the same plan",
+    )
+
     # Subcommand: reset parts of on-disk state (choose which via flags)
     reset_parser = subparsers.add_parser(
         "reset",
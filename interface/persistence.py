@@ -5,25 +5,47 @@ log of data issues, and imports mod-exported game state snapshots.
 
 Exports
 -------
+STDIO_PATH
 read_food_dict
+read_food_dict_reporting
 save_food_dict
+backup_file
 log_data_issues
+validate_food_entries
 load_food_state
 load_game_state_export
+load_foods_eco
+load_foods_csv
+parse_header_map
+append_history_entry
+read_history
+read_cravings_file
+history_logger
+save_plan
+read_plan
 
 Notes
 -----
 JSON I/O is UTF-8. Deduplication during save is case-
-insensitive by Name.
+insensitive by Name. Food files may also be given as ``-``
+(stdin/stdout) or as an open text stream.
 """
 
+import csv
 import json
 import re
+import shutil
+import sys
+from collections.abc import Callable
+from datetime import (
+    datetime,
+)
 from pathlib import (
     Path,
 )
 
 from constants import (
+    BACKUP_COUNT,
     TASTINESS_MULTIPLIERS,
 )
 from food_state_manager import FoodStateManager
@@ -34,10 +56,38 @@ from interface.prompts import (
 from models.food import (
     Food,
 )
+from models.plan import (
+    PlanResult,
+)
 
 # Persisted state next to this file (works when run as a module)
 ROOT_DIR = Path(__file__).resolve().parents[1]  # project root (one level up)
 DATA_PATH = ROOT_DIR / "food_state.json"
+# Append-only consumption log (one JSON object per line); opt-in via --log
+HISTORY_PATH = ROOT_DIR / "food_history.jsonl"
+
+
+# Path argument meaning "use stdin/stdout" instead of a file
+STDIO_PATH = "-"
+
+
+def _is_stream(
+    path,
+) -> bool:
+    """True when *path* is ``-`` or an open text stream rather than a path."""
+    return path == STDIO_PATH or hasattr(path, "read") or hasattr(path, "write")
+
+
+def _read_text(
+    path,
+) -> str:
+    """Whole contents of a path, ``-`` (stdin), or an open text reader."""
+    if path == STDIO_PATH:
+        return sys.stdin.read()
+    if hasattr(path, "read"):
+        return path.read()
+    with open(path, "r", encoding="utf-8") as in_file:
+        return in_file.read()
 
 
 def read_food_dict(
@@ -47,8 +97,8 @@ def read_food_dict(
 
     Parameters
     ----------
-    path : str | os.PathLike
-        Path to the JSON file.
+    path : str | os.PathLike | TextIO
+        Path to the JSON file, ``-`` for stdin, or an open text reader.
 
     Returns
     -------
@@ -58,31 +108,121 @@ def read_food_dict(
     Notes
     -----
     The input JSON is expected to be a list of dicts compatible
-    with ``Food.from_dict``.
+    with ``Food.from_dict``. Empty files and a top-level object (instead
+    of a list) are reported with a specific message. Numeric tastiness
+    outside the scale is reset to unknown (``99``) with a warning naming
+    the affected foods.
     """
 
     # Text mode, UTF-8; fail soft (print + return []) so the CLI can continue
-    with open(
-        path,
-        "r",
-        encoding="utf-8",
-    ) as in_file:
-        try:
-            # Defensive parse: swallow JSON/IO errors here.
-            # Upstream should handle an empty result set.
-            data = json.load(in_file)
-            result = []
-            for entry in data:
-                result.append(Food.from_dict(entry))
-            return result
-        except Exception as exc:
-            print(f"[ERROR] Failed to read food data: {exc}")
-            return []
+    try:
+        # Defensive parse: swallow decode/JSON errors here (a missing file
+        # still raises). Upstream should handle an empty result set.
+        text = _read_text(path)
+        if not text.strip():
+            raise ValueError("food state file is empty")
+        data = json.loads(text)
+        if isinstance(data, dict):
+            raise ValueError(
+                "expected a list of foods but found a single object; "
+                "wrap the contents in [...]"
+            )
+        result = []
+        coerced = []
+        for entry in data:
+            if _tastiness_out_of_range(entry):
+                coerced.append(entry.get("Name", "<unnamed>"))
+                entry = {**entry, "Tastiness": 99}
+            result.append(Food.from_dict(entry))
+        if coerced:
+            print(
+                "[WARN] Out-of-range tastiness reset to unknown (99) for: "
+                + ", ".join(coerced)
+            )
+        return result
+    except OSError:
+        raise
+    except Exception as exc:
+        print(f"[ERROR] Failed to read food data: {exc}")
+        return []
+
+
+def _tastiness_out_of_range(
+    entry,
+) -> bool:
+    """Whether *entry* has a numeric tastiness that is not on the scale."""
+    try:
+        rating = int(entry["Tastiness"])
+    except (KeyError, TypeError, ValueError):
+        return False
+    return rating not in TASTINESS_MULTIPLIERS
+
+
+def backup_file(
+    path,
+    keep,
+):
+    """Rotate backups of *path* and copy its current contents to ``.bak``.
+
+    Older copies shift ``.bak`` → ``.bak.1`` → ... → ``.bak.{keep-1}``;
+    the oldest beyond *keep* is overwritten. Does nothing if *path* does
+    not exist yet or *keep* is ``0``.
+
+    Parameters
+    ----------
+    path : str | os.PathLike
+        File about to be overwritten.
+    keep : int
+        Number of backups to retain.
+    """
+    path = Path(path)
+    if keep <= 0 or not path.exists():
+        return
+
+    def backup_name(index):
+        suffix = ".bak" if index == 0 else f".bak.{index}"
+        return path.with_name(path.name + suffix)
+
+    for index in range(keep - 1, 0, -1):
+        if backup_name(index - 1).exists():
+            backup_name(index - 1).replace(backup_name(index))
+    shutil.copy2(path, backup_name(0))
+
+
+def read_food_dict_reporting(
+    path,
+):
+    """Load foods like `read_food_dict`, reporting duplicate names.
+
+    Names are compared case-insensitively. As in `FoodStateManager`, the
+    last entry for a name wins but keeps the first entry's position.
+
+    Parameters
+    ----------
+    path : str | os.PathLike | TextIO
+        Path to the JSON file, ``-`` for stdin, or an open text reader.
+
+    Returns
+    -------
+    tuple[list[Food], list[str]]
+        Deduplicated foods, and the name of each food that had earlier
+        entries dropped (once per name, in file order).
+    """
+    unique_by_name: dict[str, Food] = {}
+    duplicates: dict[str, str] = {}
+    for food in read_food_dict(path):
+        key = food.name.lower()
+        if key in unique_by_name:
+            duplicates.setdefault(key, food.name)
+        unique_by_name[key] = food
+    return list(unique_by_name.values()), list(duplicates.values())
 
 
 def save_food_dict(
     food_list,
     path,
+    keep_backups=BACKUP_COUNT,
+    compact=False,
 ):
     """Save a deduplicated list of food dicts to a JSON file.
 
@@ -90,8 +230,14 @@ def save_food_dict(
     ----------
     food_list : list[dict]
         Foods as dictionaries. Each entry must include a ``"Name"`` key.
-    path : str | os.PathLike
-        Destination file path.
+    path : str | os.PathLike | TextIO
+        Destination file path, ``-`` for stdout, or an open text writer.
+    keep_backups : int, optional
+        Rotated backups of the previous file to keep (see `backup_file`).
+        Defaults to ``safety.backup_count`` from the config. Streams are
+        never backed up.
+    compact : bool, optional
+        Write the list on a single line instead of indented. Default False.
 
     Notes
     -----
@@ -104,19 +250,30 @@ def save_food_dict(
     unique_by_name = {}
     for food in food_list:
         unique_by_name[food["Name"].lower()] = food
-    with open(
-        path,
-        "w",
-        encoding="utf-8",
-    ) as out_file:
+
+    def dump(out_file) -> None:
         # Persist the last-seen order of unique names.
         # `list(...)` fixes JSON iteration order on older Python versions
         json.dump(
             list(unique_by_name.values()),
             out_file,
-            indent=2,
+            indent=None if compact else 2,
+            separators=(",", ":") if compact else None,
         )
 
+    if _is_stream(path):
+        out_file = sys.stdout if path == STDIO_PATH else path
+        dump(out_file)
+        out_file.write("\n")
+        return
+    backup_file(path, keep_backups)
+    with open(
+        path,
+        "w",
+        encoding="utf-8",
+    ) as out_file:
+        dump(out_file)
+
 
 def log_data_issues(
     all_foods,
@@ -203,11 +360,81 @@ def log_data_issues(
             log_file.write("[INFO] No issues found.\n")
 
 
+#: Keys every food entry must carry (``"Fats"`` is accepted for ``"Fat"``)
+REQUIRED_FOOD_KEYS = (
+    "Name",
+    "Calories",
+    "Carbs",
+    "Protein",
+    "Fat",
+    "Vitamins",
+    "Tastiness",
+)
+
+
+def validate_food_entries(
+    entries,
+    *,
+    fix=False,
+):
+    """Check raw food entries and report the rules each one violates.
+
+    Parameters
+    ----------
+    entries : list[dict]
+        Food entries as read from ``food_state.json``.
+    fix : bool
+        If ``True``, clamp negative values to ``0`` and reset invalid
+        tastiness to unknown (``99``) in place. Entries with missing or
+        non-numeric fields are reported but never changed.
+
+    Returns
+    -------
+    list[tuple[str, list[str]]]
+        ``(name, problems)`` for each entry that was invalid as read.
+    """
+    report = []
+    for index, entry in enumerate(entries):
+        name = entry.get("Name") or f"<entry {index}>"
+        missing = [
+            key
+            for key in REQUIRED_FOOD_KEYS
+            if key not in entry and not (key == "Fat" and "Fats" in entry)
+        ]
+        if missing:
+            report.append((name, [f"missing field {key!r}" for key in missing]))
+            continue
+
+        # Construct with a placeholder tastiness so a bad rating is
+        # reported alongside the other rules instead of raising.
+        try:
+            food = Food.from_dict({**entry, "Tastiness": 99})
+            food.tastiness = int(entry["Tastiness"])
+        except (TypeError, ValueError) as exc:
+            report.append((name, [f"non-numeric field ({exc})"]))
+            continue
+
+        problems = food.validation_errors()
+        if not problems:
+            continue
+        report.append((name, problems))
+
+        if fix:
+            for key in ("Calories", "Carbs", "Protein", "Fat", "Fats", "Vitamins"):
+                if key in entry and int(entry[key]) < 0:
+                    entry[key] = 0
+            if food.tastiness not in TASTINESS_MULTIPLIERS:
+                entry["Tastiness"] = 99
+    return report
+
+
 def load_food_state(
     reset_stomach=False,
     reset_tastiness=False,
     *,
     skip_prompts=False,
+    compact=False,
+    path=None,
 ):
     """Load foods and construct a ``FoodStateManager``.
 
@@ -222,15 +449,31 @@ def load_food_state(
         If ``True``, clear all unknown/known tastiness values to default.
     skip_prompts : bool
         If ``True``, skip interactive prompts (for non-interactive use).
+    compact : bool
+        Save the reset state on one line (see `save_food_dict`).
+    path : str or Path, optional
+        State file to read and reset; ``-`` reads stdin, which also skips
+        prompts. Defaults to ``DATA_PATH``.
 
     Returns
     -------
     FoodStateManager
         Ready manager with current stomach and availability loaded.
     """
+    if path is None:
+        path = DATA_PATH
+    # stdin already carries the catalog, so it cannot answer prompts too
+    if path == STDIO_PATH:
+        skip_prompts = True
+    label = "stdin" if path == STDIO_PATH else Path(path).name
 
     # Start from persisted state (empty list if missing/corrupt)
-    food_dict = read_food_dict(DATA_PATH)
+    food_dict, duplicates = read_food_dict_reporting(path)
+    if duplicates:
+        print(
+            f"[WARN] Duplicate food names in {label}; kept the last "
+            f"entry for: {', '.join(duplicates)}"
+        )
 
     # Optional: clear stomach counts before building the manager
     if reset_stomach:
@@ -269,9 +512,10 @@ def load_food_state(
     if reset_stomach or reset_tastiness:
         save_food_dict(
             manager.to_json_ready(),
-            DATA_PATH,
+            path,
+            compact=compact,
         )
-        print("[INFO] Reset saved to 'food_state.json'.")
+        print(f"[INFO] Reset saved to '{label}'.")
 
     log_data_issues(
         food_dict,
@@ -381,3 +625,436 @@ def load_game_state_export(
         server_mult,
         dinner_party_mult,
     )
+
+
+# Nutrient keys in Eco's food export, with the local Food field they fill
+_ECO_NUTRIENT_KEYS: dict[str, str] = {
+    "Carbs": "carbs",
+    "Protein": "protein",
+    "Fat": "fat",
+    "Vitamins": "vitamins",
+}
+
+
+def _is_number(value) -> bool:
+    """True for int/float JSON values (bools excluded)."""
+    return isinstance(value, (int, float)) and not isinstance(value, bool)
+
+
+def _eco_entry_to_food(entry) -> Food | None:
+    """Map one game-export food entry to a `Food`, or None if unusable.
+
+    Nutrients may sit at the top level or under a nested ``Nutrition``
+    object; any other nested fields (tags, shelf life, ...) are ignored.
+    """
+    if not isinstance(entry, dict):
+        return None
+    name = entry.get("Name") or entry.get("DisplayName")
+    if not isinstance(name, str) or not name.strip():
+        return None
+    calories = entry.get("Calories")
+    if not _is_number(calories):
+        return None
+
+    nutrition = entry.get("Nutrition")
+    if not isinstance(nutrition, dict):
+        nutrition = entry
+    nutrients = {}
+    for key, field in _ECO_NUTRIENT_KEYS.items():
+        value = nutrition.get(key)
+        if key == "Fat" and value is None:
+            value = nutrition.get("Fats")
+        if not _is_number(value):
+            return None
+        nutrients[field] = value
+
+    # Tastiness is per-player and usually absent from the export
+    tastiness = entry.get("Tastiness", 99)
+    if tastiness not in TASTINESS_MULTIPLIERS:
+        tastiness = 99
+    food = Food(
+        name=name.strip(),
+        calories=int(round(calories)),
+        tastiness=tastiness,
+        stomach=0,
+        available=0,
+        **nutrients,
+    )
+    return None if food.validation_errors() else food
+
+
+def load_foods_eco(
+    path: str | Path,
+) -> list[Food]:
+    """Load foods from Eco's in-game food export JSON.
+
+    Accepts either a top-level list of food objects or an object with a
+    ``Foods`` list. Entries missing a name, calories, or any nutrient are
+    skipped; unknown fields are ignored. Imported foods start with an
+    empty stomach, no stock, and unknown tastiness unless the export has
+    a valid ``Tastiness`` value.
+
+    Parameters
+    ----------
+    path : str or Path
+        Path to the exported JSON file.
+
+    Returns
+    -------
+    list[Food]
+        Imported foods in file order.
+
+    Raises
+    ------
+    FileNotFoundError
+        If *path* does not exist.
+    ValueError
+        If the file has no list of food entries.
+    """
+    with open(path, "r", encoding="utf-8") as fh:
+        data = json.load(fh)
+
+    entries = data.get("Foods") if isinstance(data, dict) else data
+    if not isinstance(entries, list):
+        raise ValueError(f"No list of foods found in {path}")
+
+    foods: list[Food] = []
+    skipped = 0
+    for entry in entries:
+        food = _eco_entry_to_food(entry)
+        if food is None:
+            skipped += 1
+        else:
+            foods.append(food)
+
+    print(f"[INFO] Imported {len(foods)} foods from {path} (skipped {skipped}).")
+    return foods
+
+
+# Food fields a CSV row can fill, in the order used for header-less files
+CSV_COLUMNS: tuple[str, ...] = (
+    "Name",
+    "Calories",
+    "Carbs",
+    "Protein",
+    "Fat",
+    "Vitamins",
+    "Tastiness",
+    "Available",
+)
+
+# Columns a CSV must provide; Tastiness and Available have defaults
+_CSV_REQUIRED: tuple[str, ...] = CSV_COLUMNS[:6]
+
+# Built-in header aliases (lowercased) for common spreadsheet exports
+CSV_HEADER_ALIASES: dict[str, str] = {
+    **{column.lower(): column for column in CSV_COLUMNS},
+    "food": "Name",
+    "item": "Name",
+    "cal": "Calories",
+    "cals": "Calories",
+    "kcal": "Calories",
+    "carb": "Carbs",
+    "carbohydrates": "Carbs",
+    "prot": "Protein",
+    "pro": "Protein",
+    "fats": "Fat",
+    "vit": "Vitamins",
+    "vits": "Vitamins",
+    "vitamin": "Vitamins",
+    "taste": "Tastiness",
+    "stock": "Available",
+    "qty": "Available",
+}
+
+
+def parse_header_map(
+    specs: list[str],
+) -> dict[str, str]:
+    """Parse ``HEADER=FIELD`` pairs from the command line.
+
+    Parameters
+    ----------
+    specs : list[str]
+        Pairs such as ``"Energy=Calories"``; *FIELD* is matched
+        case-insensitively against `CSV_COLUMNS`.
+
+    Returns
+    -------
+    dict[str, str]
+        Lowercased CSV header -> food field.
+
+    Raises
+    ------
+    ValueError
+        If a pair is malformed or names an unknown field.
+    """
+    header_map: dict[str, str] = {}
+    fields = {column.lower(): column for column in CSV_COLUMNS}
+    for spec in specs:
+        header, sep, field = spec.partition("=")
+        if not sep or not header.strip():
+            raise ValueError(f"Header map entry {spec!r} is not HEADER=FIELD")
+        column = fields.get(field.strip().lower())
+        if column is None:
+            raise ValueError(
+                f"Unknown food field {field.strip()!r} in header map "
+                f"(expected one of {', '.join(CSV_COLUMNS)})"
+            )
+        header_map[header.strip().lower()] = column
+    return header_map
+
+
+def _csv_number(
+    cell: str,
+) -> float | None:
+    """Parse a numeric CSV cell, or None if it is not a number."""
+    try:
+        value = float(cell.strip())
+    except ValueError:
+        return None
+    return int(value) if value.is_integer() else value
+
+
+def _csv_column_index(
+    header: list[str],
+    header_map: dict[str, str],
+) -> dict[str, int]:
+    """Map each food field to its column, trying *header_map* first."""
+    index: dict[str, int] = {}
+    for position, cell in enumerate(header):
+        key = cell.strip().lower()
+        column = header_map.get(key) or CSV_HEADER_ALIASES.get(key)
+        if column is not None and column not in index:
+            index[column] = position
+    return index
+
+
+def load_foods_csv(
+    path: str | Path,
+    header_map: dict[str, str] | None = None,
+) -> list[Food]:
+    """Load foods from a spreadsheet-style CSV file.
+
+    Headers are matched case-insensitively against *header_map* and then
+    `CSV_HEADER_ALIASES`, so ``Cal``, ``kcal`` or ``Prot`` columns work
+    as-is. A file whose first row has no recognised header is read by
+    position in `CSV_COLUMNS` order. Imported foods start with an empty
+    stomach; missing Tastiness and Available default to unknown and 0.
+
+    Parameters
+    ----------
+    path : str or Path
+        Path to the CSV file.
+    header_map : dict[str, str], optional
+        Extra lowercased header -> field mappings (see `parse_header_map`).
+
+    Returns
+    -------
+    list[Food]
+        Imported foods in file order.
+
+    Raises
+    ------
+    FileNotFoundError
+        If *path* does not exist.
+    ValueError
+        If required columns are unmapped or a row has invalid values.
+    """
+    with open(path, "r", encoding="utf-8", newline="") as fh:
+        rows = [row for row in csv.reader(fh) if any(cell.strip() for cell in row)]
+    if not rows:
+        raise ValueError(f"No rows found in {path}")
+
+    index = _csv_column_index(rows[0], header_map or {})
+    if index:
+        missing = [column for column in _CSV_REQUIRED if column not in index]
+        if missing:
+            raise ValueError(
+                f"Unmapped required columns in {path}: {', '.join(missing)}"
+            )
+        first_data_row = 1
+    else:
+        # No recognised header: read columns by position
+        index = {column: pos for pos, column in enumerate(CSV_COLUMNS)}
+        first_data_row = 0
+
+    foods: list[Food] = []
+    for row_number, row in enumerate(rows[first_data_row:], first_data_row + 1):
+        cells = {
+            column: row[pos].strip()
+            for column, pos in index.items()
+            if pos < len(row) and row[pos].strip()
+        }
+        missing = [column for column in _CSV_REQUIRED if column not in cells]
+        if missing:
+            raise ValueError(f"Row {row_number}: missing {', '.join(missing)}")
+        values: dict[str, object] = {"Name": cells["Name"]}
+        for column in CSV_COLUMNS[1:]:
+            if column not in cells:
+                continue
+            value = _csv_number(cells[column])
+            if value is None:
+                raise ValueError(
+                    f"Row {row_number}: {column} {cells[column]!r} is not a number"
+                )
+            values[column] = value
+        try:
+            food = Food(
+                name=str(values["Name"]),
+                calories=values["Calories"],
+                carbs=values["Carbs"],
+                protein=values["Protein"],
+                fat=values["Fat"],
+                vitamins=values["Vitamins"],
+                tastiness=values.get("Tastiness", 99),
+                stomach=0,
+                available=values.get("Available", 0),
+            )
+        except ValueError as exc:
+            raise ValueError(f"Row {row_number}: {exc}") from None
+        errors = food.validation_errors()
+        if errors:
+            raise ValueError(f"Row {row_number} ({food.name}): {'; '.join(errors)}")
+        foods.append(food)
+
+    print(f"[INFO] Imported {len(foods)} foods from {path}.")
+    return foods
+
+
+def append_history_entry(
+    path: str | Path,
+    food: Food,
+    timestamp: str | None = None,
+) -> None:
+    """Append one consumed bite to the JSONL history file.
+
+    Parameters
+    ----------
+    path : str or Path
+        History file; created if missing.
+    food : Food
+        Food that was consumed.
+    timestamp : str, optional
+        ISO-8601 time of the bite. Defaults to now (local, seconds).
+    """
+    entry = {
+        "Name": food.name,
+        "Calories": food.calories,
+        "Timestamp": timestamp or datetime.now().isoformat(timespec="seconds"),
+    }
+    with open(path, "a", encoding="utf-8") as out_file:
+        out_file.write(json.dumps(entry) + "\n")
+
+
+def read_history(
+    path: str | Path,
+    limit: int | None = None,
+) -> list[dict]:
+    """Read history entries, oldest first.
+
+    Parameters
+    ----------
+    path : str or Path
+        History file. A missing file yields an empty list.
+    limit : int, optional
+        Keep only the last *limit* entries.
+
+    Returns
+    -------
+    list of dict
+        Entries with ``Name``, ``Calories``, and ``Timestamp`` keys.
+        Blank or unparsable lines are skipped.
+    """
+    if not Path(path).exists():
+        return []
+    entries = []
+    with open(path, "r", encoding="utf-8") as in_file:
+        for line in in_file:
+            if not line.strip():
+                continue
+            try:
+                entries.append(json.loads(line))
+            except json.JSONDecodeError:
+                continue
+    if limit is not None:
+        entries = entries[-limit:] if limit > 0 else []
+    return entries
+
+
+def read_cravings_file(
+    path: str | Path,
+) -> list[str]:
+    """Read one craving per line from a plain-text file.
+
+    Parameters
+    ----------
+    path : str or Path
+        Cravings file.
+
+    Returns
+    -------
+    list of str
+        Craving names in file order. Blank lines and ``#`` comments
+        (whole-line or trailing) are skipped.
+    """
+    names = []
+    with open(path, "r", encoding="utf-8") as in_file:
+        for line in in_file:
+            name = line.split("#", 1)[0].strip()
+            if name:
+                names.append(name)
+    return names
+
+
+def history_logger(
+    path: str | Path,
+) -> Callable[[Food], None]:
+    """Build a `FoodStateManager.on_consume` hook that logs to *path*."""
+
+    def _log(food: Food) -> None:
+        append_history_entry(path, food)
+
+    return _log
+
+
+def save_plan(
+    result: PlanResult,
+    path: str | Path,
+) -> None:
+    """Write a plan and its summary to a JSON file.
+
+    Parameters
+    ----------
+    result : PlanResult
+        Plan to save.
+    path : str or Path or TextIO
+        Destination file, overwritten if present; ``-`` writes to stdout,
+        and an open text writer is written to directly.
+    """
+    if _is_stream(path):
+        out_file = sys.stdout if path == STDIO_PATH else path
+        json.dump(result.to_dict(), out_file, indent=2)
+        out_file.write("\n")
+        return
+    with open(path, "w", encoding="utf-8") as out_file:
+        json.dump(result.to_dict(), out_file, indent=2)
+
+
+def read_plan(
+    path: str | Path,
+) -> PlanResult:
+    """Load a plan written by `save_plan`.
+
+    Parameters
+    ----------
+    path : str or Path
+        Plan JSON file.
+
+    Returns
+    -------
+    PlanResult
+        The saved plan and summary.
+    """
+    with open(path, "r", encoding="utf-8") as in_file:
+        return PlanResult.from_dict(json.load(in_file))
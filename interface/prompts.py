@@ -7,10 +7,41 @@ Order enforced by `collect_user_constraints`:
 4) Current craving
 """
 
+import re
+
 from constants import (
     TASTINESS_MULTIPLIERS,
     TASTINESS_NAMES,
 )
+from models.food import (
+    Food,
+)
+
+
+# Digit groups of three split by one repeated separator: "2,500", "12 500",
+# "1'200'000". A dot is left out because "2.500" may be a decimal.
+_GROUPED_NUMBER = re.compile(r"-?\d{1,3}([, '])\d{3}(?:\1\d{3})*")
+
+CALORIE_FORMAT_HINT = "Enter whole calories, e.g. 2500 or 2,500."
+
+
+def parse_whole_number(
+    text: str,
+) -> int:
+    """Parse a whole number typed by the user, allowing thousands separators.
+
+    Accepts ``2500``, ``2,500``, ``2 500`` (including non-breaking spaces),
+    and ``2'500``.
+
+    Raises
+    ------
+    ValueError
+        If *text* is not a whole number in one of those forms.
+    """
+    text = text.strip().replace("\u00a0", " ").replace("\u202f", " ")
+    if _GROUPED_NUMBER.fullmatch(text):
+        text = re.sub(r"[, ']", "", text)
+    return int(text)
 
 
 def prompt_for_cravings_satisfied() -> int:
@@ -40,13 +71,13 @@ def prompt_current_calories() -> int:
     prompt = "How many calories have you already consumed? > "
     while True:
         try:
-            val = int(input(prompt).strip())
+            val = parse_whole_number(input(prompt))
             if val < 0:
                 print("Calories cannot be negative.")
             else:
                 return val
         except ValueError:
-            print("That doesn't seem to be a number.")
+            print(f"That doesn't seem to be a number. {CALORIE_FORMAT_HINT}")
 
 
 def prompt_max_calories(
@@ -56,13 +87,13 @@ def prompt_max_calories(
     prompt = "What is your maximum calorie limit? > "
     while True:
         try:
-            val = int(input(prompt).strip())
+            val = parse_whole_number(input(prompt))
             if val < current_cal:
                 print("Maximum must be greater than or " "or equal to current intake.")
             else:
                 return val
         except ValueError:
-            print("That doesn't seem to be a number.")
+            print(f"That doesn't seem to be a number. {CALORIE_FORMAT_HINT}")
 
 
 def collect_user_constraints() -> tuple[list[str], int, int]:
@@ -92,6 +123,7 @@ def collect_user_constraints() -> tuple[list[str], int, int]:
 
 def prompt_for_tastiness(
     food_name: str,
+    current: int = 99,
 ) -> int:
     """Prompt user for tastiness rating for a given food item.
 
@@ -99,6 +131,9 @@ def prompt_for_tastiness(
     ----------
     food_name : str
         Name of the food item to rate.
+    current : int, optional
+        Rating kept when the user just presses Enter. Defaults to ``99``
+        (unknown).
 
     Returns
     -------
@@ -111,6 +146,8 @@ def prompt_for_tastiness(
         "(-3 to 3, or 99 for unknown):"
     )
     print(prompt_line)
+    if current != 99:
+        print(f"  Current: {current} ({TASTINESS_NAMES[current]}); Enter keeps it")
 
     # Show human labels for valid ratings. Exclude 99 (unknown) from hints.
     hint_items = [
@@ -119,11 +156,12 @@ def prompt_for_tastiness(
     hint_str = ", ".join(hint_items)
     print("  Hints:", hint_str)
 
-    # Blank input keeps tastiness unknown (99). Loop for a valid int.
+    # Blank input keeps the current rating (unknown by default). Loop for a
+    # valid int.
     while True:
         value = input("> ").strip()
         if value == "":
-            return 99  # Skip and keep as unknown
+            return current
         try:
             rating = int(value)
 
@@ -167,3 +205,40 @@ def prompt_yes_no(
         if resp in ("n", "no"):
             return False
         print("Please enter yes or no (y/n).")
+
+
+def prompt_bite_choice(
+    candidates: list[tuple[Food, float]],
+) -> Food | None:
+    """Show ranked bite options and let the user pick the next one.
+
+    Parameters
+    ----------
+    candidates : list[tuple[Food, float]]
+        ``(food, projected SP gain)`` pairs, best first.
+
+    Returns
+    -------
+    Food | None
+        The chosen food (Enter takes the recommendation), or ``None``
+        if the user stops.
+    """
+    name_width = max(len(food.name) for food, _ in candidates)
+    print("\nNext bite options:")
+    for index, (food, sp_delta) in enumerate(candidates, 1):
+        marker = "  (recommended)" if index == 1 else ""
+        print(
+            f"  {index}) {food.name:<{name_width}}  {food.calories:>5} cal  "
+            f"ΔSP {sp_delta:+.2f}{marker}"
+        )
+
+    prompt = f"Pick 1-{len(candidates)} (Enter = 1, q = stop) > "
+    while True:
+        resp = input(prompt).strip().lower()
+        if resp == "":
+            return candidates[0][0]
+        if resp in ("q", "quit", "stop"):
+            return None
+        if resp.isdigit() and 1 <= int(resp) <= len(candidates):
+            return candidates[int(resp) - 1][0]
+        print(f"Enter a number from 1 to {len(candidates)}, or q to stop.")
@@ -1,14 +1,139 @@
+import argparse
+
 from constants import (
     CRAVING_SATISFIED_FRAC,
+    MAX_ITERATIONS,
     TASTINESS_DELTA_THRESHOLD,
+    TIEBREAK_SCORE_WINDOW_SP,
+    VARIETY_CAL_THRESHOLD,
     VARIETY_DELTA_THRESHOLD,
 )
-from planner import fmt_signed
+from models.plan import (
+    AggregatedItem,
+    NutrientTarget,
+    VarietyProgress,
+    WhyNotReport,
+)
+from planner import STOP_ITERATION_CAP, fmt_signed
+
+# Shown under a plan that `planner.plan_meal_with_stop` cut short
+_ITERATION_CAP_NOTE = (
+    f"Note: planning stopped at the {MAX_ITERATIONS}-bite iteration cap; "
+    "raise safety.max_iterations for a complete plan."
+)
+
+
+def parse_precision(
+    text: str,
+) -> int:
+    """Parse the ``--precision`` decimal count (0 to 6) for SP values.
+
+    Shared by the main CLI and the tuner.
+    """
+    try:
+        value = int(text)
+    except ValueError:
+        value = -1
+    if not 0 <= value <= 6:
+        raise argparse.ArgumentTypeError(
+            f"expected a whole number of decimals from 0 to 6, got '{text}'"
+        )
+    return value
+
+
+def _format_macros(
+    item,
+    nutrient_scale: dict[str, float] | None = None,
+    precision: int = 2,
+) -> str:
+    """Running density (C/P/F/V) and balance bonus for one plan row.
+
+    With *nutrient_scale*, densities are divided back to catalog units.
+    """
+    density = getattr(item, "density", {}) or {}
+    scale = nutrient_scale or {}
+    macros = " ".join(
+        f"{label} {density.get(key, 0.0) / scale.get(key, 1.0):5.1f}"
+        for label, key in (
+            ("C", "carbs"),
+            ("P", "protein"),
+            ("F", "fat"),
+            ("V", "vitamins"),
+        )
+    )
+    balance = fmt_signed(getattr(item, "balance_bonus_pp", 0.0), precision)
+    return f" | {macros} | Bal {balance:>6} pp"
+
+
+def _plan_tags(
+    item,
+    precision: int = 2,
+) -> list[str]:
+    """Craving/variety/tastiness tags worth showing for one plan row."""
+    tags = []
+    if getattr(item, "craving", False):
+        percent = int(CRAVING_SATISFIED_FRAC * 100)
+        tags.append(f"[Craving Satisfied +{percent}%]")
+
+    variety_delta = getattr(item, "variety_delta_pp", 0.0)
+    if abs(variety_delta) >= VARIETY_DELTA_THRESHOLD:
+        tags.append(f"Variety Δ {fmt_signed(variety_delta, precision)} pp")
+
+    tastiness_delta = getattr(item, "tastiness_delta_pp", 0.0)
+    if abs(tastiness_delta) >= TASTINESS_DELTA_THRESHOLD:
+        tags.append(f"Tastiness Δ {fmt_signed(tastiness_delta, precision)} pp")
+    return tags
+
+
+def aggregate_plan(
+    meal_plan: list,
+) -> list[AggregatedItem]:
+    """Collapse consecutive bites of the same food into one row each.
+
+    Calories, SP gain and bonus deltas are summed; the resulting SP,
+    density and balance bonus come from the last bite of each run.
+    """
+    aggregated: list[AggregatedItem] = []
+    for item in meal_plan:
+        if aggregated and aggregated[-1].name == item.name:
+            run = aggregated[-1]
+            run.count += 1
+            run.calories += item.calories
+            run.sp_gain += item.sp_gain
+            run.new_sp = item.new_sp
+            run.craving = run.craving or item.craving
+            run.variety_delta_pp += item.variety_delta_pp
+            run.tastiness_delta_pp += item.tastiness_delta_pp
+            run.density = item.density
+            run.balance_bonus_pp = item.balance_bonus_pp
+            continue
+        aggregated.append(
+            AggregatedItem(
+                name=item.name,
+                count=1,
+                calories=item.calories,
+                sp_gain=item.sp_gain,
+                new_sp=item.new_sp,
+                craving=item.craving,
+                variety_delta_pp=item.variety_delta_pp,
+                tastiness_delta_pp=item.tastiness_delta_pp,
+                density=item.density,
+                balance_bonus_pp=item.balance_bonus_pp,
+            )
+        )
+    return aggregated
 
 
 def display_meal_plan(
     meal_plan: list,
     notices: list[str] | None = None,
+    *,
+    show_macros: bool = False,
+    unmet_cravings: list | None = None,
+    nutrient_scale: dict[str, float] | None = None,
+    aggregate: bool = False,
+    precision: int = 2,
+    stop_reason: str | None = None,
 ):
     """Pretty-print the meal plan.
 
@@ -19,6 +144,22 @@ def display_meal_plan(
     notices : list of str, optional
         Lines to print above the table (e.g., invalid cravings).
         Defaults to ``None``.
+    show_macros : bool, optional
+        Add the running nutrient density and balanced-diet bonus after
+        each bite. Defaults to ``False``.
+    unmet_cravings : list[UnmetCraving], optional
+        Cravings the plan missed, listed with their reason below the table.
+    nutrient_scale : dict[str, float], optional
+        Factors the plan's nutrients were normalized by; macros are shown
+        in the catalog's own units.
+    aggregate : bool, optional
+        Show runs of the same food as one row (e.g. ``Apple ×3``) with
+        summed calories and SP; see `aggregate_plan`. Defaults to ``False``.
+    precision : int, optional
+        Decimals shown for SP and percentage-point values. Defaults to ``2``.
+    stop_reason : str, optional
+        Why planning ended (see `planner.plan_meal_with_stop`); a plan cut
+        short by the iteration cap gets a note below the table.
     """
     if notices:
         # print notices (e.g., invalid cravings) above the table
@@ -26,32 +167,22 @@ def display_meal_plan(
             print(f"Note: {note}")
     if not meal_plan:
         print("No meal plan generated.")
+        _print_unmet_cravings(unmet_cravings)
         return
 
     # Build rows with preformatted tag text
+    entries = aggregate_plan(meal_plan) if aggregate else meal_plan
     rows = []
-    for index, item in enumerate(meal_plan, 1):
-        tags = []
-        if getattr(item, "craving", False):
-            percent = int(CRAVING_SATISFIED_FRAC * 100)
-            tags.append(f"[Craving Satisfied +{percent}%]")
-
-        variety_delta = getattr(item, "variety_delta_pp", 0.0)
-        if abs(variety_delta) >= VARIETY_DELTA_THRESHOLD:
-            tags.append(f"Variety Δ {fmt_signed(item.variety_delta_pp)} pp")
-
-        tastiness_delta = getattr(item, "tastiness_delta_pp", 0.0)
-        if abs(tastiness_delta) >= TASTINESS_DELTA_THRESHOLD:
-            tags.append(f"Tastiness Δ {fmt_signed(item.tastiness_delta_pp)} pp")
-
+    for index, item in enumerate(entries, 1):
+        count = getattr(item, "count", 1)
         rows.append(
             (
                 index,
-                item.name,
+                f"{item.name} ×{count}" if count > 1 else item.name,
                 item.calories,
                 item.sp_gain,
                 item.new_sp,
-                ", ".join(tags),
+                ", ".join(_plan_tags(item, precision)),
             )
         )
 
@@ -59,8 +190,8 @@ def display_meal_plan(
     index_width = len(str(len(rows))) if rows else 1
     name_width = max((len(row[1]) for row in rows), default=0)
     calorie_width = max((len(str(row[2])) for row in rows), default=0)
-    delta_width = max((len(fmt_signed(row[3])) for row in rows), default=0)
-    sp_width = max((len(f"{row[4]:.2f}") for row in rows), default=0)
+    delta_width = max((len(fmt_signed(row[3], precision)) for row in rows), default=0)
+    sp_width = max((len(f"{row[4]:.{precision}f}") for row in rows), default=0)
 
     # Compose left prefixes first to align tag column
     prefixes = []
@@ -68,7 +199,12 @@ def display_meal_plan(
         left = f" {row[0]:>{index_width}}. "
         left += f"{row[1]:<{name_width}} - "
         left += f"{row[2]:>{calorie_width}} cal | "
-        right = f"SP {fmt_signed(row[3]):>{delta_width}} ⇒ {row[4]:>{sp_width}.2f}"
+        right = (
+            f"SP {fmt_signed(row[3], precision):>{delta_width}}"
+            f" ⇒ {row[4]:>{sp_width}.{precision}f}"
+        )
+        if show_macros:
+            right += _format_macros(entries[row[0] - 1], nutrient_scale, precision)
         prefixes.append(left + right)
 
     prefix_width = max((len(prefix) for prefix in prefixes), default=0)
@@ -81,3 +217,229 @@ def display_meal_plan(
             padding = " " * (prefix_width - len(prefix))
             print(prefix + padding + "  " + row[5])
     print("================================")
+    if stop_reason == STOP_ITERATION_CAP:
+        print(_ITERATION_CAP_NOTE)
+    _print_unmet_cravings(unmet_cravings)
+
+
+def _print_unmet_cravings(
+    unmet_cravings: list | None,
+) -> None:
+    """List missed cravings and why, if there are any."""
+    if not unmet_cravings:
+        return
+    print("Unsatisfied cravings:")
+    for craving in unmet_cravings:
+        print(f"  - {craving.name}: {craving.reason}")
+
+
+def meal_plan_to_markdown(
+    meal_plan: list,
+    precision: int = 2,
+    stop_reason: str | None = None,
+) -> str:
+    """Render the meal plan as a Markdown table with a summary footer.
+
+    Parameters
+    ----------
+    meal_plan : list[MealPlanItem]
+        Items to render.
+    precision : int, optional
+        Decimals shown for SP values. Defaults to ``2``.
+    stop_reason : str, optional
+        As for `display_meal_plan`; the iteration-cap note follows the
+        totals line.
+
+    Returns
+    -------
+    str
+        Pipe-delimited table (one row per bite) followed by a totals line.
+    """
+    if not meal_plan:
+        return "No meal plan generated."
+
+    lines = [
+        "| # | Food | Calories | SP gain | Total SP | Tags |",
+        "|--:|------|---------:|--------:|---------:|------|",
+    ]
+    for index, item in enumerate(meal_plan, 1):
+        name = item.name.replace("|", "\\|")
+        tags = ", ".join(_plan_tags(item, precision))
+        lines.append(
+            f"| {index} | {name} | {item.calories}"
+            f" | {fmt_signed(item.sp_gain, precision)}"
+            f" | {item.new_sp:.{precision}f} | {tags} |"
+        )
+
+    total_calories = sum(item.calories for item in meal_plan)
+    total_gain = sum(item.sp_gain for item in meal_plan)
+    lines.append("")
+    lines.append(
+        f"**Total:** {len(meal_plan)} bites, {total_calories} cal,"
+        f" SP {fmt_signed(total_gain, precision)}"
+        f" ⇒ {meal_plan[-1].new_sp:.{precision}f}"
+    )
+    if stop_reason == STOP_ITERATION_CAP:
+        lines.append("")
+        lines.append(_ITERATION_CAP_NOTE)
+    return "\n".join(lines)
+
+
+def display_plan_comparison(
+    comparison: dict,
+    precision: int = 2,
+) -> None:
+    """Print default vs tuned plan totals side by side.
+
+    Parameters
+    ----------
+    comparison : dict
+        ``{"default": stats, "tuned": stats}`` as returned by
+        `tune.tuner.compare_plans`.
+    precision : int, optional
+        Decimals shown for SP values. Defaults to ``2``.
+    """
+    default = comparison["default"]
+    tuned = comparison["tuned"]
+    print(f"{'':<14}{'Default':>10}{'Tuned':>10}{'Diff':>10}")
+    for label, key, fmt in (
+        ("Final SP", "final_sp", f"{{:.{precision}f}}"),
+        ("Calories", "calories", "{:d}"),
+        ("Bites", "bites", "{:d}"),
+        ("Variety", "variety_count", "{:d}"),
+    ):
+        diff = tuned[key] - default[key]
+        diff_text = fmt_signed(diff, precision) if key == "final_sp" else f"{diff:+d}"
+        print(
+            f"{label:<14}{fmt.format(default[key]):>10}"
+            f"{fmt.format(tuned[key]):>10}{diff_text:>10}"
+        )
+
+
+def display_why_not(
+    report: WhyNotReport,
+) -> None:
+    """Print where one food stood in the ranking for the next bite.
+
+    Parameters
+    ----------
+    report : WhyNotReport
+        Result of `planner.explain_why_not`.
+    """
+    if report.excluded is not None:
+        print(f"Why not {report.name}: not ranked ({report.excluded})")
+        return
+    print(f"Why not {report.name}: rank score {report.rank_score:.3f}")
+    if report.picked:
+        print("  It is the next ranked bite.")
+        return
+    print(
+        f"  {report.gap:.3f} below the best, {report.best_name} "
+        f"({report.best_rank_score:.3f})"
+    )
+    window = "inside" if report.in_tie_window else "outside"
+    print(f"  {window} the {TIEBREAK_SCORE_WINDOW_SP:.3f} SP tie-break window")
+
+
+def display_nutrient_targets(
+    targets: list[NutrientTarget],
+) -> None:
+    """Print total intake per nutrient against its goal, marked met/under.
+
+    Parameters
+    ----------
+    targets : list[NutrientTarget]
+        Result of `calculations.check_nutrient_targets`.
+    """
+    print("Nutrient targets:")
+    for target in targets:
+        status = "met" if target.met else f"under by {target.shortfall:.0f}"
+        print(
+            f"  {target.nutrient.capitalize():<10}"
+            f"{target.total:>7.0f} / {target.goal:<7.0f}{status}"
+        )
+
+
+def display_variety_progress(
+    entries: list[VarietyProgress],
+) -> None:
+    """Print each stomach food's calories toward the variety threshold.
+
+    Parameters
+    ----------
+    entries : list[VarietyProgress]
+        Result of `calculations.variety_progress`.
+    """
+    if not entries:
+        print("Stomach is empty; no foods count toward variety yet.")
+        return
+    name_width = max(len(entry.name) for entry in entries)
+    print(f"Variety progress ({VARIETY_CAL_THRESHOLD} cal per food):")
+    for entry in entries:
+        if entry.qualifies:
+            status = "qualifies"
+        elif entry.units_needed is None:
+            status = "never qualifies (no calories)"
+        else:
+            status = f"needs {entry.units_needed} more"
+        print(
+            f"  {entry.name:<{name_width}}  {entry.calories:>6} cal"
+            f"  {entry.progress:>6.0%}  {status}"
+        )
+
+
+def display_budget_sweep(
+    points: list,
+    precision: int = 2,
+) -> None:
+    """Print final SP and SP per 100 kcal for each swept budget.
+
+    The marginal column is the SP gained over the previous budget.
+
+    Parameters
+    ----------
+    points : list[BudgetSweepPoint]
+        Results from `planner.sweep_budgets`, in budget order.
+    precision : int, optional
+        Decimals shown for SP values. Defaults to ``2``.
+    """
+    print(f"{'Budget':>8}{'Used':>8}{'Final SP':>10}{'SP/100kcal':>12}{'Marginal':>10}")
+    previous = None
+    for point in points:
+        marginal = (
+            "" if previous is None else fmt_signed(point.final_sp - previous, precision)
+        )
+        print(
+            f"{point.budget:>8}{point.calories:>8}{point.final_sp:>10.{precision}f}"
+            f"{point.sp_per_100kcal:>12.{precision}f}{marginal:>10}"
+        )
+        previous = point.final_sp
+
+
+def display_sp_formula(
+    breakdown,
+) -> None:
+    """Print the SP formula, then the same formula with live values.
+
+    Parameters
+    ----------
+    breakdown : SpBreakdown
+        Terms of the SP formula for the current stomach.
+    """
+    print(
+        "SP = (nutrient_total × (1 + balance + variety + taste + craving)"
+        " × dinner_party + base) × server"
+    )
+    print(
+        f"   = ({breakdown.nutrient_total:.4f} × (1"
+        f" {breakdown.balance:+.4f} {breakdown.variety:+.4f}"
+        f" {breakdown.taste:+.4f} {breakdown.craving:+.4f})"
+        f" × {breakdown.dinner_party_mult:.4f} + {breakdown.base:.4f})"
+        f" × {breakdown.server_mult:.4f}"
+    )
+    print(
+        f"   = ({breakdown.nutrient_total:.4f} × {breakdown.bonus_mult:.4f}"
+        f" × {breakdown.dinner_party_mult:.4f} + {breakdown.base:.4f})"
+        f" × {breakdown.server_mult:.4f}"
+    )
+    print(f"   = {breakdown.total:.2f}")
@@ -12,12 +12,15 @@ def setup_logging(
     Parameters
     ----------
     verbosity : int
-        Count of ``-v`` flags; higher means more verbose.
+        Count of ``-v`` flags; higher means more verbose. Negative (from
+        ``--quiet``) shows errors only.
     log_file : str or None
         Path to a log file to write to, or ``None`` to log to stderr only.
     """
 
-    if verbosity <= 0:
+    if verbosity < 0:
+        level = logging.ERROR
+    elif verbosity == 0:
         level = logging.WARNING
     elif verbosity == 1:
         level = logging.INFO
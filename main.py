@@ -8,7 +8,19 @@ Exports
 -------
 cmd_plan
 cmd_rate_unknowns
+cmd_rate
+cmd_top_foods
+cmd_analyze
+cmd_explain
+cmd_edit
+cmd_restock
+cmd_merge
+cmd_diff
+cmd_history
+cmd_validate
+cmd_self_test
 cmd_reset
+exit_code_for
 main
 
 Notes
@@ -17,6 +29,10 @@ Use `python -m <package> plan` to run planning from the shell.
 """
 
 # Early config path detection - must happen before importing constants
+import contextlib
+import json
+import logging
+import random
 import sys
 
 
@@ -40,46 +56,181 @@ if _early_config:
 
 
 # Now safe to import modules that depend on constants
+from beam_planner import (
+    plan_beam,
+)
 from calculations import (
     calculate_balanced_diet_bonus,
+    check_nutrient_targets,
+    dinner_party_multiplier,
     get_sp,
     get_tastiness_bonus,
     get_variety_bonus,
     is_variety_qualifying,
+    sp_breakdown,
     sum_all_weighted_nutrients,
+    variety_progress,
 )
 from constants import (
+    BASE_SKILL_POINTS,
     CRAVING_SATISFIED_FRAC,
+    TASTINESS_NAMES,
+    ZERO_NUTRIENT_TOLERANCE_SP,
+)
+from food_state_manager import (
+    FoodNotFoundError,
+    MergeStrategy,
+    StateSnapshot,
+    diff_catalogs,
+    normalize_nutrients,
+    nutrient_scale_factors,
 )
 from interface.cli import (
     build_parser,
 )
 from interface.persistence import (
     DATA_PATH,
+    HISTORY_PATH,
+    STDIO_PATH,
+    history_logger,
     load_food_state,
+    load_foods_csv,
     load_game_state_export,
+    parse_header_map,
     prompt_for_tastiness,
+    read_cravings_file,
+    read_food_dict,
+    read_history,
     save_food_dict,
+    save_plan,
+    validate_food_entries,
 )
 from interface.prompts import (
     collect_user_constraints,
+    prompt_bite_choice,
+    prompt_yes_no,
 )
 from interface.render import (
+    display_budget_sweep,
     display_meal_plan,
+    display_nutrient_targets,
+    display_plan_comparison,
+    display_sp_formula,
+    display_variety_progress,
+    display_why_not,
+    meal_plan_to_markdown,
 )
 from logs.logging_utils import (
     setup_logging,
 )
+from models.plan import (
+    PlanResult,
+)
 from planner import (
+    OBJECTIVE_SP,
+    STOP_ITERATION_CAP,
+    diagnose_stomach,
+    explain_why_not,
+    history_penalties,
     normalize_name,
-    plan_meal,
+    plan_from_state,
+    plan_meal_with_stop,
+    rank_foods_by_efficiency,
+    resolve_cravings,
+    sweep_budgets,
+    tie_logger,
+    unmet_cravings,
     validate_cravings,
 )
+from schema import (
+    validate_json,
+)
+from tune.tuner import (
+    compare_plans,
+    load_best_knobs,
+)
+
+# Process exit statuses, distinct per failure kind so scripts can branch
+EXIT_ERROR = 1
+EXIT_FOOD_NOT_FOUND = 2
+EXIT_IO = 3
+EXIT_INVALID_INPUT = 4
+
+# Calorie budget `self-test` plans with, so runs are comparable across setups
+SELF_TEST_BUDGET = 3000
+
+# Foods rated by `rate-unknowns --all` between "Continue rating?" checks
+RATE_ALL_BATCH = 10
+
+# Subcommands that write the food state back (validate only with --fix)
+STATE_SAVING_COMMANDS = frozenset(
+    {"plan", "rate-unknowns", "rate", "edit", "restock", "merge", "reset"}
+)
+
+
+def exit_code_for(
+    exc: BaseException,
+) -> int:
+    """Map an error raised by a subcommand to a process exit status.
+
+    Parameters
+    ----------
+    exc : BaseException
+        The error that stopped the command.
+
+    Returns
+    -------
+    int
+        ``EXIT_FOOD_NOT_FOUND``, ``EXIT_IO`` or ``EXIT_INVALID_INPUT``;
+        ``EXIT_ERROR`` for anything else.
+    """
+    if isinstance(exc, FoodNotFoundError):
+        return EXIT_FOOD_NOT_FOUND
+    if isinstance(exc, OSError):
+        return EXIT_IO
+    if isinstance(exc, ValueError):
+        return EXIT_INVALID_INPUT
+    return EXIT_ERROR
+
+
+def _state_path(
+    args,
+):
+    """State file named by ``--file``, or ``DATA_PATH`` by default."""
+    return getattr(args, "file", None) or DATA_PATH
+
+
+def _state_target(
+    args,
+):
+    """Where saving commands write state: the real stdout for ``--file -``."""
+    return getattr(args, "catalog_out", None) or _state_path(args)
+
+
+def _stdin_state_conflict(
+    command: str,
+    args,
+) -> str | None:
+    """Why *command* cannot run with ``--file -``, or None when it can.
+
+    stdin holds the food state, so it cannot also answer prompts or
+    supply a second file.
+    """
+    if command == "plan" and not getattr(args, "import_path", None):
+        return "--file - needs plan --import; stdin is used for prompts"
+    if command == "rate-unknowns":
+        return "--file - cannot be used with rate-unknowns, which prompts"
+    if command == "rate" and args.value is None:
+        return "--file - needs a rating value; stdin is used for prompts"
+    for flag in ("other_file", "pantry_from", "save_plan"):
+        if getattr(args, flag, None) == STDIO_PATH:
+            return "--file - cannot share stdio with another '-' argument"
+    return None
 
 
 def cmd_plan(
     args,
-) -> None:
+) -> int | None:
     """Execute the ``plan`` subcommand.
 
     Loads/initializes state, gathers user constraints, generates a meal plan,
@@ -89,10 +240,46 @@ def cmd_plan(
     ----------
     args : argparse.Namespace
         Parsed CLI arguments.
+
+    Returns
+    -------
+    int or None
+        Exit status when the command fails; None on success.
     """
 
+    # With --save-plan -, stdout carries only the plan JSON; everything
+    # meant for the user (plan table, warnings, prompts) goes to stderr
+    if getattr(args, "save_plan", None) == STDIO_PATH:
+        plan_out = sys.stdout
+        with contextlib.redirect_stdout(sys.stderr):
+            return _plan(args, plan_out)
+    return _plan(args, None)
+
+
+def _plan(
+    args,
+    plan_out,
+) -> int | None:
+    """Body of `cmd_plan`; *plan_out* receives ``--save-plan -`` JSON."""
+
+    # Read tuned knobs up front so a bad file fails before any prompts
+    compare_path = getattr(args, "compare", None)
+    tuned_knobs = None
+    if compare_path:
+        try:
+            tuned_knobs = load_best_knobs(compare_path)
+        except (OSError, ValueError) as exc:
+            print(f"Error: {exc}")
+            return exit_code_for(exc)
+
     import_path = getattr(args, "import_path", None)
 
+    # The interactive prompts read stdin, so a piped pantry needs --import
+    pantry_path = getattr(args, "pantry_from", None)
+    if pantry_path == STDIO_PATH and not import_path:
+        print("Error: --pantry-from - needs --import; stdin is used for prompts")
+        return EXIT_INVALID_INPUT
+
     if import_path:
         # Import from mod-exported JSON — no interactive prompts
         (
@@ -108,15 +295,92 @@ def cmd_plan(
             server_mult = args.server_mult
         if getattr(args, "dinner_party", 1.0) != 1.0:
             dinner_party_mult = args.dinner_party
-        print(f"[INFO] Imported game state from {import_path}")
+        if not getattr(args, "quiet", False):
+            print(f"[INFO] Imported game state from {import_path}")
     else:
         # Interactive flow: prompt for cravings, satisfied count, remaining calories
-        manager = load_food_state()
+        manager = load_food_state(path=_state_path(args))
         user_constraints = collect_user_constraints()
         cravings, cravings_satisfied, remaining_calories = user_constraints
         server_mult = getattr(args, "server_mult", 1.0)
         dinner_party_mult = getattr(args, "dinner_party", 1.0)
 
+    # Stock comes from the pantry file; nutrients stay from the main catalog
+    if pantry_path:
+        try:
+            pantry = read_food_dict(pantry_path)
+        except OSError as exc:
+            print(f"Error: {exc}")
+            return EXIT_IO
+        for name in manager.overlay_availability(pantry):
+            print(f"[WARN] Ignoring pantry entry '{name}': not in your foods")
+        if not getattr(args, "quiet", False):
+            print(f"[INFO] Availability taken from {pantry_path}")
+
+    # Baseline stomach kept as-is in the saved file with --preserve-stomach
+    baseline = manager.snapshot() if getattr(args, "preserve_stomach", False) else None
+
+    # Scale portions for this plan only; originals are restored before saving
+    originals = []
+    nutrient_scale = None
+    if getattr(args, "normalize", False):
+        catalog = list(manager.foods.values())
+        nutrient_scale = nutrient_scale_factors(catalog)
+        for food in normalize_nutrients(catalog, nutrient_scale):
+            originals.append(manager.replace_food(food))
+        if not getattr(args, "quiet", False):
+            factors = ", ".join(f"{k} ×{v:.2f}" for k, v in nutrient_scale.items())
+            print(f"[INFO] Normalized nutrients: {factors}")
+    for name, factor in getattr(args, "servings", []):
+        food = manager.get_food(name)
+        if food is None:
+            print(f"[WARN] Ignoring serving size for unknown food '{name}'")
+            continue
+        originals.append(manager.replace_food(food.scale(factor)))
+
+    # Start from food already eaten today so SP and variety include it
+    for name, quantity in getattr(args, "seed", []):
+        try:
+            eaten = manager.consume_n(name, quantity)
+        except FoodNotFoundError:
+            print(f"[WARN] Ignoring seed for unknown food '{name}'")
+            continue
+        if eaten < quantity:
+            print(f"[WARN] Only {eaten} of {quantity} '{name}' available to seed")
+
+    # Not enough stock to fill the budget: the plan will stop early
+    stock_calories = manager.available_calories()
+    if remaining_calories > stock_calories:
+        print(
+            f"[WARN] Only {stock_calories:.0f} cal in stock for a "
+            f"{remaining_calories} cal budget; the plan will be stock-limited"
+        )
+
+    # Staples the plan must start with; unknown or out-of-stock ones are dropped
+    required = []
+    for name in getattr(args, "require", []):
+        food = manager.get_food(name)
+        if food is None:
+            print(f"[WARN] Ignoring required food '{name}': not in your foods")
+        elif not manager.can_consume(food):
+            print(f"[WARN] Required food '{food.name}' is out of stock")
+        else:
+            required.append(food.name)
+
+    # Add saved cravings; lines that match no food are reported and skipped
+    cravings_file = getattr(args, "cravings_file", None)
+    if cravings_file:
+        try:
+            file_cravings = read_cravings_file(cravings_file)
+        except OSError as exc:
+            print(f"[WARN] Could not read cravings file: {exc}")
+            file_cravings = []
+        resolved, unresolved = resolve_cravings(manager, file_cravings)
+        for name in unresolved:
+            print(f"[WARN] No food matches craving '{name}' from {cravings_file}")
+        known = {normalize_name(c) for c in cravings}
+        cravings = [*cravings, *(c for c in resolved if c not in known)]
+
     # Block until all cravings are valid (or dropped/replaced by the user)
     while True:
         normalized = [normalize_name(c) for c in cravings]
@@ -145,41 +409,264 @@ def cmd_plan(
         # Loop will re-validate.
         cravings = [*valid, *replacements]
 
+    # Penalties come from bites logged before this plan, so read them first
+    recent_penalties = None
+    if getattr(args, "history_aware", False):
+        recent_penalties = history_penalties(read_history(HISTORY_PATH)) or None
+        if recent_penalties is None and not getattr(args, "quiet", False):
+            print("[INFO] No consumption history; planning without it")
+
+    zero_nutrient_tolerance = None
+    if getattr(args, "avoid_zero_nutrient", False):
+        zero_nutrient_tolerance = ZERO_NUTRIENT_TOLERANCE_SP
+
+    # Plan at each swept budget on copies; nothing is saved
+    budgets = getattr(args, "budget_sweep", None)
+    if budgets:
+        points = sweep_budgets(
+            manager,
+            cravings,
+            cravings_satisfied,
+            budgets,
+            server_mult=server_mult,
+            dinner_party_mult=dinner_party_mult,
+            max_repeats=getattr(args, "max_repeats", None),
+            min_bite_calories=getattr(args, "min_bite_calories", None),
+            max_bites=getattr(args, "max_bites", None),
+            overshoot_tolerance=getattr(args, "overshoot_tolerance", 0),
+            require=required,
+            objective=getattr(args, "objective", OBJECTIVE_SP),
+            zero_nutrient_tolerance=zero_nutrient_tolerance,
+            max_distinct=getattr(args, "max_distinct_foods", None),
+            lookahead=getattr(args, "greedy_lookahead", 1),
+            recent_penalties=recent_penalties,
+        )
+        display_budget_sweep(points, precision=getattr(args, "precision", 2))
+        return
+
+    # Compare default and tuned knobs on copies; nothing is saved
+    if tuned_knobs is not None:
+        comparison = compare_plans(
+            manager,
+            tuned_knobs,
+            cravings,
+            cravings_satisfied,
+            remaining_calories,
+            server_mult=server_mult,
+            dinner_party_mult=dinner_party_mult,
+            max_repeats=getattr(args, "max_repeats", None),
+            min_bite_calories=getattr(args, "min_bite_calories", None),
+            max_bites=getattr(args, "max_bites", None),
+            overshoot_tolerance=getattr(args, "overshoot_tolerance", 0),
+            require=required,
+            objective=getattr(args, "objective", OBJECTIVE_SP),
+            zero_nutrient_tolerance=zero_nutrient_tolerance,
+            max_distinct=getattr(args, "max_distinct_foods", None),
+            lookahead=getattr(args, "greedy_lookahead", 1),
+            recent_penalties=recent_penalties,
+        )
+        display_plan_comparison(comparison, precision=getattr(args, "precision", 2))
+        return
+
+    # Show why each automatic bite won among its near-equal finalists
+    if getattr(args, "explain_tie", False):
+        tie_logger.setLevel(logging.DEBUG)
+
+    # Where the named food stands in the ranking for the first bite
+    why_not = getattr(args, "explain_why_not", None)
+    if why_not:
+        try:
+            report = explain_why_not(
+                manager,
+                why_not,
+                remaining_calories,
+                cravings,
+                cravings_satisfied,
+                getattr(args, "max_repeats", None),
+                getattr(args, "min_bite_calories", None),
+                recent_penalties,
+            )
+        except FoodNotFoundError:
+            print(f"[WARN] Cannot explain '{why_not}': not in your foods")
+        else:
+            display_why_not(report)
+
     # Produce a plan under current constraints and show it
-    meal_plan = plan_meal(
-        manager=manager,
-        cravings=cravings,
-        cravings_satisfied=cravings_satisfied,
-        remaining_calories=remaining_calories,
-        server_mult=server_mult,
-        dinner_party_mult=dinner_party_mult,
+    beam_width = getattr(args, "beam", None)
+    interactive = getattr(args, "interactive", False)
+    max_distinct = getattr(args, "max_distinct_foods", None)
+    if beam_width is not None and not interactive:
+        if required:
+            print("[WARN] --require is not supported with --beam; ignoring it")
+        if getattr(args, "objective", OBJECTIVE_SP) != OBJECTIVE_SP:
+            print("[WARN] --objective is not supported with --beam; using SP")
+        if recent_penalties:
+            print("[WARN] --history-aware is not supported with --beam; ignoring it")
+        if getattr(args, "max_bites", None) is not None:
+            print("[WARN] --max-bites is not supported with --beam; ignoring it")
+        if getattr(args, "avoid_zero_nutrient", False):
+            print("[WARN] --avoid-zero-nutrient is not supported with --beam")
+        if max_distinct is not None:
+            print("[WARN] --max-distinct-foods is not supported with --beam")
+            max_distinct = None
+        if getattr(args, "greedy_lookahead", 1) > 1:
+            print("[WARN] --greedy-lookahead is not supported with --beam")
+        meal_plan = plan_beam(
+            manager,
+            cravings,
+            cravings_satisfied,
+            remaining_calories,
+            beam_width,
+            server_mult=server_mult,
+            dinner_party_mult=dinner_party_mult,
+            max_repeats=getattr(args, "max_repeats", None),
+            min_bite_calories=getattr(args, "min_bite_calories", None),
+        )
+        stop_reason = None
+    else:
+        tiebreak_seed = getattr(args, "tiebreak_seed", None)
+        tiebreak_rng = None
+        if tiebreak_seed is not None:
+            tiebreak_rng = random.Random(tiebreak_seed)
+        meal_plan, stop_reason = plan_meal_with_stop(
+            manager=manager,
+            cravings=cravings,
+            cravings_satisfied=cravings_satisfied,
+            remaining_calories=remaining_calories,
+            server_mult=server_mult,
+            dinner_party_mult=dinner_party_mult,
+            max_repeats=getattr(args, "max_repeats", None),
+            min_bite_calories=getattr(args, "min_bite_calories", None),
+            pick_bite=prompt_bite_choice if interactive else None,
+            rng=tiebreak_rng,
+            max_bites=getattr(args, "max_bites", None),
+            overshoot_tolerance=getattr(args, "overshoot_tolerance", 0),
+            require=required,
+            objective=getattr(args, "objective", OBJECTIVE_SP),
+            recent_penalties=recent_penalties,
+            zero_nutrient_tolerance=zero_nutrient_tolerance,
+            max_distinct=max_distinct,
+            lookahead=getattr(args, "greedy_lookahead", 1),
+        )
+
+    # Report cravings the plan missed while stock/budget still reflect it
+    unmet = unmet_cravings(
+        manager,
+        cravings,
+        meal_plan,
+        remaining_calories - sum(item.calories for item in meal_plan),
+        getattr(args, "max_repeats", None),
+        max_distinct,
     )
 
     # Pretty-print the plan for the user
-    display_meal_plan(meal_plan)
+    if getattr(args, "output", "table") == "markdown":
+        print(
+            meal_plan_to_markdown(
+                meal_plan,
+                precision=getattr(args, "precision", 2),
+                stop_reason=stop_reason,
+            )
+        )
+    else:
+        display_meal_plan(
+            meal_plan,
+            show_macros=getattr(args, "show_macros", False),
+            unmet_cravings=unmet,
+            nutrient_scale=nutrient_scale,
+            aggregate=getattr(args, "aggregate", False),
+            precision=getattr(args, "precision", 2),
+            stop_reason=stop_reason,
+        )
+
+    # A truncated plan is not saved when the user asked to fail instead
+    if stop_reason == STOP_ITERATION_CAP and getattr(
+        args, "fail_on_iteration_cap", False
+    ):
+        print("Error: planning hit the iteration cap; the plan is incomplete")
+        return EXIT_ERROR
+
+    # Opt-in: record each planned bite in the consumption history, in order,
+    # once the plan is known to be kept
+    if getattr(args, "log", False):
+        log_bite = history_logger(HISTORY_PATH)
+        for item in meal_plan:
+            log_bite(manager.get_food(item.name))
+
+    # Whole-day intake (prior stomach included) against --targets goals
+    goals = getattr(args, "targets", None)
+    if goals:
+        display_nutrient_targets(check_nutrient_targets(manager.stomach, goals))
+
+    # Which foods (prior stomach included) count toward variety, and how close
+    if getattr(args, "explain_variety", False):
+        display_variety_progress(variety_progress(manager.stomach))
+
+    save_plan_path = getattr(args, "save_plan", None)
+    if save_plan_path:
+        save_plan(PlanResult.from_plan(meal_plan), plan_out or save_plan_path)
+        if save_plan_path != STDIO_PATH and not getattr(args, "quiet", False):
+            print(f"[INFO] Plan saved to {save_plan_path}")
+
+    # Undo in reverse so servings of normalized foods unwind correctly
+    for original in reversed(originals):
+        manager.replace_food(original)
+
+    # Planned bites counted toward SP above; only the stock change is saved
+    if baseline is not None:
+        manager.restore(
+            StateSnapshot(
+                stomach=baseline.stomach,
+                available=manager.snapshot().available,
+            )
+        )
 
     # Persist updated stomach/availability back to disk
     save_food_dict(
         manager.to_json_ready(),
-        DATA_PATH,
+        _state_target(args),
+        compact=getattr(args, "compact_json", False),
     )
 
 
 def cmd_rate_unknowns(
-    _args,
+    args,
 ) -> None:
     """Execute the ``rate-unknowns`` subcommand.
 
     Prompts for tastiness ratings for any foods marked as unknown and saves
-    the updated state.
+    the updated state. With ``--all``, walks every available food instead,
+    offering its current rating as the default and asking whether to go on
+    after every ``RATE_ALL_BATCH`` foods.
 
     Parameters
     ----------
-    _args : argparse.Namespace
-        Parsed CLI arguments (not otherwise used).
+    args : argparse.Namespace
+        Parsed CLI arguments (``all``, ``compact_json``).
     """
 
-    manager = load_food_state()
+    rate_all = getattr(args, "all", False)
+    manager = load_food_state(skip_prompts=rate_all, path=_state_path(args))
+
+    if rate_all:
+        foods = sorted(manager.all_available(), key=lambda food: food.name.lower())
+        ratings = []
+        for position, food in enumerate(foods, 1):
+            ratings.append((food.name, prompt_for_tastiness(food.name, food.tastiness)))
+            if (
+                position % RATE_ALL_BATCH == 0
+                and position < len(foods)
+                and not prompt_yes_no("Continue rating?")
+            ):
+                break
+        changed = manager.apply_ratings(ratings)
+        save_food_dict(
+            manager.to_json_ready(),
+            _state_target(args),
+            compact=getattr(args, "compact_json", False),
+        )
+        print(f"Updated {len(changed)} of {len(ratings)} ratings.")
+        return
 
     # prompt for tastiness on available foods with unknown ratings
     unknowns = [
@@ -202,13 +689,62 @@ def cmd_rate_unknowns(
     for food in unknowns:
         food.tastiness = prompt_for_tastiness(food.name)
 
-    save_food_dict(manager.to_json_ready(), DATA_PATH)
+    save_food_dict(
+        manager.to_json_ready(),
+        _state_target(args),
+        compact=getattr(args, "compact_json", False),
+    )
     print("Tastiness ratings saved.")
 
 
+def cmd_rate(
+    args,
+) -> int | None:
+    """Execute the ``rate`` subcommand.
+
+    Sets the tastiness of one food, prompting for it when no value is
+    given, and saves the updated state.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``name``, ``value``).
+
+    Returns
+    -------
+    int or None
+        Exit status when the command fails; None on success.
+    """
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
+
+    food = manager.get_food(args.name)
+    if food is None:
+        print(f"Error: {FoodNotFoundError(args.name)}")
+        return EXIT_FOOD_NOT_FOUND
+    before = food.tastiness
+    value = args.value
+    if value is None:
+        value = prompt_for_tastiness(food.name)
+    try:
+        manager.edit_food(food.name, "tastiness", value)
+    except ValueError as exc:
+        print(f"Error: {exc}")
+        return EXIT_INVALID_INPUT
+
+    print(
+        f"{food.name}: {TASTINESS_NAMES[before]} -> {TASTINESS_NAMES[value]} "
+        f"({value})"
+    )
+    save_food_dict(
+        manager.to_json_ready(),
+        _state_target(args),
+        compact=getattr(args, "compact_json", False),
+    )
+
+
 def cmd_predict(
     args,
-) -> None:
+) -> int | None:
     """Execute the ``predict`` subcommand.
 
     Predicts SP gain from eating a specific food. Used for in-game validation
@@ -218,8 +754,13 @@ def cmd_predict(
     ----------
     args : argparse.Namespace
         Parsed CLI arguments.
+
+    Returns
+    -------
+    int or None
+        Exit status when the command fails; None on success.
     """
-    manager = load_food_state(skip_prompts=True)
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
 
     # Look up the food
     food = manager.get_food(args.food)
@@ -231,7 +772,7 @@ def cmd_predict(
         ]
         for match_name in matches[:10]:
             print(f"  - {manager.foods[match_name].name}")
-        return
+        return EXIT_FOOD_NOT_FOUND
 
     # Parse cravings
     cravings = (
@@ -291,6 +832,10 @@ def cmd_predict(
         server_mult=server_mult,
         dinner_party_mult=dinner_party_mult,
     )
+    # Show the multiplier get_sp applied (variety mode derives its own)
+    dinner_party_mult = dinner_party_multiplier(
+        dinner_party_mult, len(unique_foods_24h)
+    )
 
     # Print breakdown
     print("\n" + "=" * 60)
@@ -323,20 +868,405 @@ def cmd_predict(
     print(f"  Dinner Party:    {dinner_party_mult:.2f}x")
     print()
     print("-" * 60)
-    print(f"PREDICTED SP:      {sp:.2f}")
+    print(f"PREDICTED SP:      {sp:.{getattr(args, 'precision', 2)}f}")
     print("-" * 60)
 
     # Show formula
     bonus_frac = total_bonus_pp / 100 + satisfied_bonus
     nutrition_sp = density_sum * (1 + bonus_frac) * dinner_party_mult
     print(
-        f"\nFormula: ({density_sum:.2f} * (1 + {bonus_frac:.4f}) * {dinner_party_mult:.2f} + 12) * {server_mult:.2f}"
+        f"\nFormula: ({density_sum:.2f} * (1 + {bonus_frac:.4f}) "
+        f"* {dinner_party_mult:.2f} + {BASE_SKILL_POINTS:g}) * {server_mult:.2f}"
     )
-    print(f"       = ({nutrition_sp:.2f} + 12) * {server_mult:.2f}")
+    print(f"       = ({nutrition_sp:.2f} + {BASE_SKILL_POINTS:g}) * {server_mult:.2f}")
     print(f"       = {sp:.2f}")
     print()
 
 
+def cmd_top_foods(
+    args,
+) -> None:
+    """Execute the ``top-foods`` subcommand.
+
+    Prints the N foods with the highest standalone SP per calorie, as if
+    each were eaten alone on an empty stomach.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``n``).
+    """
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
+    ranked = rank_foods_by_efficiency(list(manager.foods.values()))[: args.n]
+    if not ranked:
+        print("No foods to rank.")
+        return
+
+    name_width = max(len(food.name) for food, _ in ranked)
+    for index, (food, sp_per_calorie) in enumerate(ranked, 1):
+        print(
+            f"{index:>2}. {food.name:<{name_width}}  {food.calories:>5} cal  "
+            f"ΔSP {sp_per_calorie * food.calories:+.2f}  "
+            f"({sp_per_calorie * 1000:.2f} SP/1000 cal)"
+        )
+
+
+def cmd_analyze(
+    args,
+) -> None:
+    """Execute the ``analyze`` subcommand.
+
+    Reports which bonus is furthest below its maximum, the nutrient
+    limiting balance, and the available food that would best fix it.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``file``).
+    """
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
+    if not manager.stomach:
+        print("Stomach is empty; nothing to analyze.")
+        return
+
+    diagnosis = diagnose_stomach(manager)
+    print(f"Current SP: {manager.get_current_sp():.2f}")
+    for name, bonus in diagnosis.bonus_pp.items():
+        gap = diagnosis.gap_pp[name]
+        print(f"  {name.capitalize():<8} {bonus:+6.2f} pp  ({gap:.2f} pp below max)")
+    print(f"Weakest bonus: {diagnosis.weakest}")
+    print(
+        f"Limiting nutrient: {diagnosis.limiting_nutrient} "
+        f"({diagnosis.density[diagnosis.limiting_nutrient]:.2f})"
+    )
+    if diagnosis.suggestion is None:
+        print("No available food improves balance.")
+    else:
+        print(f"Try: {diagnosis.suggestion.name}")
+
+
+def cmd_explain(
+    args,
+) -> None:
+    """Execute the ``explain`` subcommand.
+
+    Prints the SP formula with each term replaced by its value for the
+    current stomach.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``satisfied``, ``server_mult``, ``dinner_party``).
+    """
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
+    breakdown = sp_breakdown(
+        manager.stomach,
+        args.satisfied,
+        manager.unique_variety_foods(),
+        server_mult=args.server_mult,
+        dinner_party_mult=args.dinner_party,
+    )
+    if not manager.stomach:
+        print("Stomach is empty; SP is the base value.")
+    display_sp_formula(breakdown)
+
+
+def cmd_edit(
+    args,
+) -> int | None:
+    """Execute the ``edit`` subcommand.
+
+    Changes a single field of one food, prints the record before and after,
+    and saves the updated state.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``name``, ``field``, ``value``).
+
+    Returns
+    -------
+    int or None
+        Exit status when the command fails; None on success.
+    """
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
+
+    food = manager.get_food(args.name)
+    before = f"{food.debug_string()} A:{food.available}" if food else ""
+    try:
+        food = manager.edit_food(args.name, args.field, args.value)
+    except (FoodNotFoundError, ValueError) as exc:
+        print(f"Error: {exc}")
+        return exit_code_for(exc)
+
+    print(f"Before: {before}")
+    print(f"After:  {food.debug_string()} A:{food.available}")
+    save_food_dict(
+        manager.to_json_ready(),
+        _state_target(args),
+        compact=getattr(args, "compact_json", False),
+    )
+
+
+def cmd_restock(
+    args,
+) -> int | None:
+    """Execute the ``restock`` subcommand.
+
+    Sets the available count of one named food (or every food with
+    ``--all``) and saves the updated state.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``amount`` plus ``food`` or ``all``).
+
+    Returns
+    -------
+    int or None
+        Exit status when the command fails; None on success.
+    """
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
+
+    names = list(manager.foods) if args.all else [args.food]
+    try:
+        for name in names:
+            manager.set_availability(name, args.amount)
+    except (FoodNotFoundError, ValueError) as exc:
+        print(f"Error: {exc}")
+        return exit_code_for(exc)
+
+    save_food_dict(
+        manager.to_json_ready(),
+        _state_target(args),
+        compact=getattr(args, "compact_json", False),
+    )
+    if args.all:
+        print(f"Restocked {len(names)} foods to {args.amount}.")
+    else:
+        food = manager.get_food(args.food)
+        assert food is not None
+        print(f"Restocked {food.name} to {args.amount}.")
+
+
+def cmd_merge(
+    args,
+) -> int | None:
+    """Execute the ``merge`` subcommand.
+
+    Loads another food file, merges it into the current state using the
+    chosen strategy, and saves the result. Files ending in ``.csv`` go
+    through the CSV importer, with ``--header-map`` entries applied. With
+    ``--reseed-availability`` the file replaces the stock list instead
+    (see `FoodStateManager.reseed_availability`).
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``other_file``, ``strategy``,
+        ``header_map``, ``reseed_availability``).
+
+    Returns
+    -------
+    int or None
+        Exit status when the command fails; None on success.
+    """
+    try:
+        if args.other_file.lower().endswith(".csv"):
+            header_map = parse_header_map(getattr(args, "header_map", None) or [])
+            other = load_foods_csv(args.other_file, header_map)
+        else:
+            other = read_food_dict(args.other_file)
+    except OSError as exc:
+        print(f"Error: {exc}")
+        return EXIT_IO
+    except ValueError as exc:
+        print(f"Error: {exc}")
+        return EXIT_INVALID_INPUT
+
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
+    if getattr(args, "reseed_availability", False):
+        added, updated = manager.reseed_availability(other)
+    else:
+        added, updated = manager.merge(other, MergeStrategy(args.strategy))
+    save_food_dict(
+        manager.to_json_ready(),
+        _state_target(args),
+        compact=getattr(args, "compact_json", False),
+    )
+    print(
+        f"Merged {len(other)} foods from {args.other_file}: "
+        f"{added} added, {updated} updated."
+    )
+
+
+def cmd_diff(
+    args,
+) -> int | None:
+    """Execute the ``diff`` subcommand.
+
+    Compares the current state with another food file and prints one line
+    per added (``+``), removed (``-``), or changed (``~``) food.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``other_file``).
+
+    Returns
+    -------
+    int or None
+        Exit status when the command fails; None on success.
+    """
+    try:
+        other = read_food_dict(args.other_file)
+    except OSError as exc:
+        print(f"Error: {exc}")
+        return EXIT_IO
+
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
+    diff = diff_catalogs(list(manager.foods.values()), other)
+    if diff.is_empty():
+        print(f"No differences from {args.other_file}.")
+        return
+
+    for name in diff.added:
+        print(f"+ {name}")
+    for name in diff.removed:
+        print(f"- {name}")
+    for name, fields in diff.changed.items():
+        changes = ", ".join(
+            f"{field} {old} -> {new}" for field, (old, new) in fields.items()
+        )
+        print(f"~ {name}: {changes}")
+    print(
+        f"{len(diff.added)} added, {len(diff.removed)} removed, "
+        f"{len(diff.changed)} changed."
+    )
+
+
+def cmd_history(
+    args,
+) -> None:
+    """Execute the ``history`` subcommand.
+
+    Prints the most recent logged bites with a running calorie total.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``limit``).
+    """
+    entries = read_history(HISTORY_PATH, args.limit)
+    if not entries:
+        print("No consumption history. Use 'plan --log' to record bites.")
+        return
+
+    name_width = max(len(entry["Name"]) for entry in entries)
+    running_calories = 0
+    for entry in entries:
+        running_calories += entry["Calories"]
+        print(
+            f"{entry['Timestamp']}  {entry['Name']:<{name_width}}  "
+            f"{entry['Calories']:>5} cal  (total {running_calories})"
+        )
+
+
+def cmd_validate(
+    args,
+) -> None:
+    """Execute the ``validate`` subcommand.
+
+    Checks the state file against `schema.FOOD_FIELDS` and lists every
+    structural, type, and range problem at once. With ``--fix``, clamps
+    negatives to 0, resets bad tastiness to unknown, and saves. Exits with
+    status 4 if anything is still invalid.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``fix``).
+    """
+    state_path = _state_path(args)
+    if state_path == STDIO_PATH:
+        content = sys.stdin.read()
+    else:
+        with open(state_path, "r", encoding="utf-8") as in_file:
+            content = in_file.read()
+
+    errors = validate_json(content)
+    if not errors:
+        print(f"All {len(json.loads(content))} foods are valid.")
+        return
+
+    for error in errors:
+        print(f"  - {error}")
+
+    if args.fix:
+        try:
+            entries = json.loads(content)
+        except ValueError:
+            entries = None
+        if isinstance(entries, list) and all(
+            isinstance(entry, dict) and "Name" in entry for entry in entries
+        ):
+            report = validate_food_entries(entries, fix=True)
+            save_food_dict(
+                entries,
+                _state_target(args),
+                compact=getattr(args, "compact_json", False),
+            )
+            remaining = validate_food_entries(entries)
+            print(f"Fixed {len(report) - len(remaining)} of {len(report)} foods.")
+            errors = validate_json(json.dumps(entries))
+        else:
+            print("Not saved: make every entry an object with a Name first.")
+
+    if errors:
+        sys.exit(EXIT_INVALID_INPUT)
+
+
+def cmd_self_test(
+    args,
+) -> int | None:
+    """Execute the ``self-test`` subcommand.
+
+    Plans ``SELF_TEST_BUDGET`` calories twice from the saved state (no
+    cravings, nothing saved) and reports whether both plans match bite for
+    bite. A mismatch means ranking depends on something other than the
+    inputs, such as iteration order.
+
+    Parameters
+    ----------
+    args : argparse.Namespace
+        Parsed CLI arguments (``file``).
+
+    Returns
+    -------
+    int or None
+        ``EXIT_ERROR`` when the plans differ; None on success.
+    """
+    manager = load_food_state(skip_prompts=True, path=_state_path(args))
+
+    first, _ = plan_from_state(manager, [], 0, SELF_TEST_BUDGET)
+    second, _ = plan_from_state(manager, [], 0, SELF_TEST_BUDGET)
+    if first == second:
+        print(
+            f"Self-test passed: {len(first)} bites at {SELF_TEST_BUDGET} cal "
+            "planned identically twice."
+        )
+        return None
+
+    print(f"Self-test FAILED: two plans at {SELF_TEST_BUDGET} cal differ.")
+    for index, (left, right) in enumerate(zip(first, second), 1):
+        if left != right:
+            print(f"  First difference at bite {index}: {left.name} vs {right.name}")
+            break
+    else:
+        print(f"  Plan lengths differ: {len(first)} vs {len(second)} bites")
+    return EXIT_ERROR
+
+
 def cmd_reset(
     args,
 ) -> None:
@@ -351,7 +1281,7 @@ def cmd_reset(
         Parsed CLI arguments.
     """
 
-    manager = load_food_state()
+    manager = load_food_state(path=_state_path(args))
     any_reset_performed = False
     if args.stomach:
         manager.reset_stomach()
@@ -366,7 +1296,11 @@ def cmd_reset(
         print("Nothing to do.")
         print("Pick at least one of: --stomach --availability --tastiness")
         return
-    save_food_dict(manager.to_json_ready(), DATA_PATH)
+    save_food_dict(
+        manager.to_json_ready(),
+        _state_target(args),
+        compact=getattr(args, "compact_json", False),
+    )
     print("Reset complete.")
 
 
@@ -374,11 +1308,13 @@ def main():
     """CLI entry point.
 
     Parses args, configures logging, and dispatches to the selected subcommand.
+    Exits with the status returned by the command, or the one `exit_code_for`
+    picks when it raises.
     """
 
     parser = build_parser()
     args = parser.parse_args()
-    setup_logging(args.verbose)
+    setup_logging(-1 if args.quiet else args.verbose)
 
     # Note: --config was already processed early for config loading
     # (see top of file). The args.config attribute is available but
@@ -386,17 +1322,52 @@ def main():
 
     # Fallback: default to "plan" when no subcommand is provided (back-compat)
     command = args.cmd or "plan"
-    if command == "plan":
-        cmd_plan(args)
-    elif command == "predict":
-        cmd_predict(args)
-    elif command == "rate-unknowns":
-        cmd_rate_unknowns(args)
-    elif command == "reset":
-        cmd_reset(args)
-    else:
+    handlers = {
+        "plan": cmd_plan,
+        "predict": cmd_predict,
+        "rate-unknowns": cmd_rate_unknowns,
+        "rate": cmd_rate,
+        "top-foods": cmd_top_foods,
+        "analyze": cmd_analyze,
+        "explain": cmd_explain,
+        "edit": cmd_edit,
+        "restock": cmd_restock,
+        "merge": cmd_merge,
+        "diff": cmd_diff,
+        "history": cmd_history,
+        "validate": cmd_validate,
+        "self-test": cmd_self_test,
+        "reset": cmd_reset,
+    }
+    if command not in handlers:
         parser.error(f"Unknown command: {command}")
 
+    # With --file -, a saving command's stdout carries only the saved
+    # state; everything else it prints goes to stderr
+    saves_to_stdout = False
+    if getattr(args, "file", None) == STDIO_PATH:
+        conflict = _stdin_state_conflict(command, args)
+        if conflict:
+            print(f"Error: {conflict}", file=sys.stderr)
+            sys.exit(EXIT_INVALID_INPUT)
+        saves_to_stdout = command in STATE_SAVING_COMMANDS or (
+            command == "validate" and args.fix
+        )
+        if saves_to_stdout:
+            args.catalog_out = sys.stdout
+
+    try:
+        if saves_to_stdout:
+            with contextlib.redirect_stdout(sys.stderr):
+                status = handlers[command](args)
+        else:
+            status = handlers[command](args)
+    except (FoodNotFoundError, OSError, ValueError) as exc:
+        print(f"Error: {exc}", file=sys.stderr)
+        status = exit_code_for(exc)
+    if status:
+        sys.exit(status)
+
 
 if __name__ == "__main__":
     main()
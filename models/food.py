@@ -6,6 +6,8 @@ string representation, and JSON-compatible conversion.
 Exports
 -------
 Food
+FoodBuilder
+NUTRIENT_FIELDS
 
 Notes
 -----
@@ -16,6 +18,9 @@ from constants import (
     TASTINESS_MULTIPLIERS,
 )
 
+# Nutrient dimensions every food carries, in display order
+NUTRIENT_FIELDS: tuple[str, ...] = ("carbs", "protein", "fat", "vitamins")
+
 
 class Food:
     """Construct a Food record.
@@ -84,6 +89,31 @@ class Food:
         """
         return self.carbs + self.protein + self.fat + self.vitamins
 
+    def validation_errors(
+        self,
+    ):
+        """List the validity rules this record violates.
+
+        Returns
+        -------
+        list[str]
+            One message per violated rule (negative calories/nutrients,
+            tastiness outside the scale). Empty when the record is valid.
+        """
+        errors = []
+        for label, value in (
+            ("Calories", self.calories),
+            ("Carbs", self.carbs),
+            ("Protein", self.protein),
+            ("Fat", self.fat),
+            ("Vitamins", self.vitamins),
+        ):
+            if value < 0:
+                errors.append(f"{label} is negative ({value})")
+        if self.tastiness not in TASTINESS_MULTIPLIERS:
+            errors.append(f"Tastiness {self.tastiness} is not a valid rating")
+        return errors
+
     def is_valid(
         self,
     ):
@@ -95,13 +125,44 @@ class Food:
             ``True`` if required fields are present and values
             are within expected ranges.
         """
-        return (
-            self.calories >= 0
-            and self.carbs >= 0
-            and self.protein >= 0
-            and self.fat >= 0
-            and self.vitamins >= 0
-            and self.tastiness in TASTINESS_MULTIPLIERS
+        return not self.validation_errors()
+
+    def scale(
+        self,
+        factor: float,
+    ) -> "Food":
+        """Copy with calories and all four nutrients multiplied by *factor*.
+
+        Name, tastiness, and stomach/available counts are kept. Scaled
+        values are rounded to whole numbers like every other field.
+
+        Parameters
+        ----------
+        factor : float
+            Portion multiplier (e.g., ``2.0`` for a double serving).
+
+        Returns
+        -------
+        Food
+            New record; ``self`` is unchanged.
+
+        Raises
+        ------
+        ValueError
+            If *factor* is not positive.
+        """
+        if factor <= 0:
+            raise ValueError(f"Scale factor must be positive, got {factor}")
+        return Food(
+            name=self.name,
+            calories=round(self.calories * factor),
+            carbs=round(self.carbs * factor),
+            protein=round(self.protein * factor),
+            fat=round(self.fat * factor),
+            vitamins=round(self.vitamins * factor),
+            tastiness=self.tastiness,
+            stomach=self.stomach,
+            available=self.available,
         )
 
     def calories_per_nutrient(
@@ -260,6 +321,37 @@ class Food:
         """
         return self.sum_nutrients() / max(self.calories, 1)
 
+    def _per_calorie(
+        self,
+        amount,
+    ):
+        """*amount* divided by calories; 0.0 for zero-calorie foods."""
+        return amount / self.calories if self.calories > 0 else 0.0
+
+    def carbs_per_calorie(
+        self,
+    ):
+        """Carbs per calorie (0.0 when calories are 0)."""
+        return self._per_calorie(self.carbs)
+
+    def protein_per_calorie(
+        self,
+    ):
+        """Protein per calorie (0.0 when calories are 0)."""
+        return self._per_calorie(self.protein)
+
+    def fat_per_calorie(
+        self,
+    ):
+        """Fat per calorie (0.0 when calories are 0)."""
+        return self._per_calorie(self.fat)
+
+    def vitamins_per_calorie(
+        self,
+    ):
+        """Vitamins per calorie (0.0 when calories are 0)."""
+        return self._per_calorie(self.vitamins)
+
     def debug_string(
         self,
     ):
@@ -276,3 +368,106 @@ class Food:
             f"F:{self.fat} V:{self.vitamins} "
             f"T:{self.tastiness}"
         )
+
+
+class FoodBuilder:
+    """Build a `Food` one field at a time, validating on `build`.
+
+    Every setter returns the builder, so calls chain::
+
+        bannock = FoodBuilder("Bannock").calories(600).carbs(12).build()
+
+    Unset nutrients default to ``0``, counts to ``0``, and tastiness to
+    ``99`` (unknown).
+
+    Parameters
+    ----------
+    name : str
+        Food item name.
+    """
+
+    def __init__(
+        self,
+        name: str,
+    ) -> None:
+        self._fields: dict[str, object] = {
+            "name": name,
+            "calories": 0,
+            "carbs": 0,
+            "protein": 0,
+            "fat": 0,
+            "vitamins": 0,
+            "tastiness": 99,
+            "stomach": 0,
+            "available": 0,
+        }
+
+    def _set(
+        self,
+        field: str,
+        value: int,
+    ) -> "FoodBuilder":
+        self._fields[field] = value
+        return self
+
+    def calories(self, value: int) -> "FoodBuilder":
+        """Set calories per unit."""
+        return self._set("calories", value)
+
+    def carbs(self, value: int) -> "FoodBuilder":
+        """Set carbohydrates per unit."""
+        return self._set("carbs", value)
+
+    def protein(self, value: int) -> "FoodBuilder":
+        """Set protein per unit."""
+        return self._set("protein", value)
+
+    def fat(self, value: int) -> "FoodBuilder":
+        """Set fat per unit."""
+        return self._set("fat", value)
+
+    def vitamins(self, value: int) -> "FoodBuilder":
+        """Set vitamins per unit."""
+        return self._set("vitamins", value)
+
+    def tastiness(self, value: int) -> "FoodBuilder":
+        """Set the tastiness rating (``-3..3``, or ``99`` for unknown)."""
+        return self._set("tastiness", value)
+
+    def stomach(self, value: int) -> "FoodBuilder":
+        """Set the consumed count."""
+        return self._set("stomach", value)
+
+    def available(self, value: int) -> "FoodBuilder":
+        """Set the units available to eat."""
+        return self._set("available", value)
+
+    def build(
+        self,
+    ) -> Food:
+        """Create the `Food`, checking the same rules as `Food.is_valid`.
+
+        Returns
+        -------
+        Food
+            New record.
+
+        Raises
+        ------
+        ValueError
+            If the name is blank or any validity rule fails; the message
+            lists every violated rule.
+        """
+        fields = dict(self._fields)
+        name = str(fields["name"]).strip()
+        if not name:
+            raise ValueError("Food name must not be blank")
+        # Build with a placeholder rating so a bad one is reported with the
+        # other problems instead of raising from the constructor
+        tastiness = fields.pop("tastiness")
+        food = Food(**{**fields, "name": name}, tastiness=99)
+        food.tastiness = int(tastiness)
+        errors = food.validation_errors()
+        if errors:
+            raise ValueError(f"Invalid food '{name}': " + "; ".join(errors))
+        return food
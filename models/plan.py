@@ -1,5 +1,7 @@
 from dataclasses import (
+    asdict,
     dataclass,
+    field,
 )
 
 from models.food import Food
@@ -25,6 +27,11 @@ class MealPlanItem:
         Variety bonus change (pp) due to the bite.
     tastiness_delta_pp : float
         Tastiness bonus change (pp) due to the bite.
+    density : dict[str, float]
+        Calorie-weighted nutrient density of the stomach after the bite
+        (keys ``carbs``, ``protein``, ``fat``, ``vitamins``).
+    balance_bonus_pp : float
+        Balanced-diet bonus (pp) after the bite.
     """
 
     name: str
@@ -34,6 +41,230 @@ class MealPlanItem:
     craving: bool
     variety_delta_pp: float
     tastiness_delta_pp: float = 0.0
+    density: dict[str, float] = field(default_factory=dict)
+    balance_bonus_pp: float = 0.0
+
+
+@dataclass
+class AggregatedItem:
+    """A run of consecutive identical bites shown as one plan row.
+
+    Attributes
+    ----------
+    name : str
+        Food name.
+    count : int
+        Bites in the run.
+    calories : int
+        Calories across the run.
+    sp_gain : float
+        SP gained across the run.
+    new_sp : float
+        SP after the last bite of the run.
+    craving : bool
+        True if any bite in the run satisfied a craving.
+    variety_delta_pp : float
+        Summed variety bonus change (pp).
+    tastiness_delta_pp : float
+        Summed tastiness bonus change (pp).
+    density : dict[str, float]
+        Stomach nutrient density after the last bite.
+    balance_bonus_pp : float
+        Balanced-diet bonus (pp) after the last bite.
+    """
+
+    name: str
+    count: int
+    calories: int
+    sp_gain: float
+    new_sp: float
+    craving: bool
+    variety_delta_pp: float
+    tastiness_delta_pp: float = 0.0
+    density: dict[str, float] = field(default_factory=dict)
+    balance_bonus_pp: float = 0.0
+
+
+@dataclass
+class PlanSummary:
+    """Totals over a whole plan.
+
+    Attributes
+    ----------
+    items : int
+        Number of bites.
+    calories : int
+        Calories across all bites.
+    sp_gain : float
+        SP gained over the plan.
+    final_sp : float or None
+        SP after the last bite; ``None`` for an empty plan.
+    """
+
+    items: int
+    calories: int
+    sp_gain: float
+    final_sp: float | None
+
+
+@dataclass
+class PlanResult:
+    """A plan plus its totals, in a form that survives a JSON round trip.
+
+    Attributes
+    ----------
+    items : list[MealPlanItem]
+        Ordered bites.
+    summary : PlanSummary
+        Totals over `items`.
+    """
+
+    items: list[MealPlanItem]
+    summary: PlanSummary
+
+    @classmethod
+    def from_plan(
+        cls,
+        meal_plan: list[MealPlanItem],
+    ) -> "PlanResult":
+        """Wrap a planner result and compute its summary."""
+        return cls(
+            items=list(meal_plan),
+            summary=PlanSummary(
+                items=len(meal_plan),
+                calories=sum(item.calories for item in meal_plan),
+                sp_gain=sum(item.sp_gain for item in meal_plan),
+                final_sp=meal_plan[-1].new_sp if meal_plan else None,
+            ),
+        )
+
+    def to_dict(
+        self,
+    ) -> dict:
+        """JSON-ready ``{"items": [...], "summary": {...}}`` mapping."""
+        return asdict(self)
+
+    @classmethod
+    def from_dict(
+        cls,
+        data: dict,
+    ) -> "PlanResult":
+        """Rebuild a result written by `to_dict`."""
+        return cls(
+            items=[MealPlanItem(**item) for item in data["items"]],
+            summary=PlanSummary(**data["summary"]),
+        )
+
+
+@dataclass
+class StomachDiagnosis:
+    """Why the current stomach scores the SP it does.
+
+    Attributes
+    ----------
+    bonus_pp : dict[str, float]
+        Current ``balance``, ``variety`` and ``taste`` bonuses (pp).
+    gap_pp : dict[str, float]
+        Distance of each bonus below its maximum (pp).
+    weakest : str
+        Bonus with the largest gap.
+    limiting_nutrient : str
+        Nutrient with the lowest weighted density.
+    density : dict[str, float]
+        Weighted nutrient density of the stomach.
+    suggestion : Food or None
+        Available food that most improves balance, if any does.
+    suggestion_bias : float
+        Balance-improvement bias of `suggestion` (0.0 when none).
+    """
+
+    bonus_pp: dict[str, float]
+    gap_pp: dict[str, float]
+    weakest: str
+    limiting_nutrient: str
+    density: dict[str, float]
+    suggestion: Food | None = None
+    suggestion_bias: float = 0.0
+
+
+@dataclass
+class UnmetCraving:
+    """A requested craving the plan did not satisfy.
+
+    Attributes
+    ----------
+    name : str
+        Display name of the craved food.
+    reason : str
+        Why it was not eaten (e.g., ``"no stock left"``).
+    """
+
+    name: str
+    reason: str
+
+
+@dataclass
+class WhyNotReport:
+    """Where one food stood when the planner picked its next bite.
+
+    Attributes
+    ----------
+    name : str
+        Display name of the food asked about.
+    excluded : str or None
+        Why it was not a candidate at all (e.g., ``"no stock left"``);
+        ``None`` when it was ranked.
+    rank_score : float or None
+        Its first-pass ranking score; ``None`` when excluded.
+    best_name : str or None
+        Candidate with the highest first-pass score, if any fits.
+    best_rank_score : float or None
+        That candidate's score.
+    in_tie_window : bool
+        Whether it was within the tie-break window of the best score and
+        so reached the soft-variety/proximity finalists.
+    picked : bool
+        Whether it would be the next ranked bite.
+    """
+
+    name: str
+    excluded: str | None = None
+    rank_score: float | None = None
+    best_name: str | None = None
+    best_rank_score: float | None = None
+    in_tie_window: bool = False
+    picked: bool = False
+
+    @property
+    def gap(
+        self,
+    ) -> float | None:
+        """How far `rank_score` is below the best score (``None`` if unranked)."""
+        if self.rank_score is None or self.best_rank_score is None:
+            return None
+        return self.best_rank_score - self.rank_score
+
+
+@dataclass
+class BudgetSweepPoint:
+    """Outcome of planning at one calorie budget.
+
+    Attributes
+    ----------
+    budget : int
+        Calorie budget planned against.
+    calories : int
+        Calories the plan actually used.
+    final_sp : float
+        SP after the plan.
+    sp_per_100kcal : float
+        SP gained per 100 kcal of budget.
+    """
+
+    budget: int
+    calories: int
+    final_sp: float
+    sp_per_100kcal: float
 
 
 def append_meal_log(
@@ -44,6 +275,8 @@ def append_meal_log(
     craving: bool,
     variety_delta: float,
     tastiness_delta: float = 0.0,
+    density: dict[str, float] | None = None,
+    balance_bonus: float = 0.0,
 ) -> None:
     """Append a single bite entry to the meal log.
 
@@ -63,6 +296,10 @@ def append_meal_log(
         Change in variety bonus (percentage points).
     tastiness_delta : float, optional
         Change in tastiness bonus (percentage points), by default 0.0.
+    density : dict[str, float], optional
+        Post-bite nutrient density, by default empty.
+    balance_bonus : float, optional
+        Post-bite balanced-diet bonus (pp), by default 0.0.
     """
     # Record a bite; this function only appends to the log
     meal_log.append(
@@ -74,5 +311,66 @@ def append_meal_log(
             craving=craving,
             variety_delta_pp=variety_delta,
             tastiness_delta_pp=tastiness_delta,
+            density=dict(density or {}),
+            balance_bonus_pp=balance_bonus,
         )
     )
+
+
+@dataclass(frozen=True)
+class NutrientTarget:
+    """Total intake of one nutrient against a goal set with ``--targets``.
+
+    Attributes
+    ----------
+    nutrient : str
+        Nutrient key (``carbs``, ``protein``, ``fat`` or ``vitamins``).
+    total : float
+        Absolute intake (nutrient x units summed over the stomach).
+    goal : float
+        Requested minimum.
+    """
+
+    nutrient: str
+    total: float
+    goal: float
+
+    @property
+    def met(
+        self,
+    ) -> bool:
+        """Whether the total reaches the goal."""
+        return self.total >= self.goal
+
+    @property
+    def shortfall(
+        self,
+    ) -> float:
+        """How far the total is below the goal (0 when met)."""
+        return max(0.0, self.goal - self.total)
+
+
+@dataclass(frozen=True)
+class VarietyProgress:
+    """One stomach food's calories against ``VARIETY_CAL_THRESHOLD``.
+
+    Attributes
+    ----------
+    name : str
+        Food name.
+    calories : int
+        Calories eaten so far (calories x units in the stomach).
+    progress : float
+        *calories* as a fraction of the threshold (1.0 or more qualifies).
+    qualifies : bool
+        Whether the food counts toward the variety bonus.
+    units_needed : int or None
+        More units needed to cross the threshold (0 once it qualifies);
+        ``None`` for a zero-calorie food, which never can.
+    """
+
+    name: str
+    calories: int
+    progress: float
+    qualifies: bool
+    units_needed: int | None
@@ -7,23 +7,46 @@ proximity tie-breaks, and optional low-calorie penalties.
 Exports
 -------
 plan_meal
+plan_from_state
+rank_candidates
+history_penalties
+explain_why_not
+tie_finalists
+TieFinalist
+peek_next
+sweep_budgets
+diagnose_stomach
+rank_foods_by_efficiency
+resolve_cravings
+unmet_cravings
 """
 
+import copy
 import difflib
 import logging
+import math
+import random
+from collections.abc import Callable
+from dataclasses import (
+    dataclass,
+)
 
 from calculations import (
+    StomachAccumulator,
+    calculate_balanced_diet_bonus,
     calculate_balanced_diet_ratio,
     get_sp_delta,
+    get_tastiness_bonus,
     get_variety_bonus,
     simulate_stomach_with_added_food,
     soft_variety_count,
     sum_all_weighted_nutrients,
-    tastiness_delta_for_added_unit,
-    variety_count,
+    variety_fraction_for,
 )
 from constants import (
     BALANCED_DIET_IMPROVEMENT_STRENGTH,
+    HISTORY_LOOKBACK,
+    HISTORY_PENALTY_STRENGTH,
     LOW_CALORIE_THRESHOLD,
     LOW_CALORIE_PENALTY_STRENGTH,
     MAX_ITERATIONS,
@@ -31,19 +54,54 @@ from constants import (
     SOFT_VARIETY_BIAS_STRENGTH,
     PROXIMITY_APPROACH_WEIGHT,
     PROXIMITY_OVERSHOOT_PENALTY,
+    TASTINESS_MULTIPLIERS,
+    TASTINESS_WEIGHT,
     TIEBREAK_SCORE_WINDOW_SP,
+    VARIETY_BONUS_CAP_PP,
     VARIETY_CAL_THRESHOLD,
 )
 from food_state_manager import (
+    FoodNotFoundError,
     FoodStateManager,
+    StateSnapshot,
 )
 from models.food import (
     Food,
+    NUTRIENT_FIELDS,
+)
+from models.plan import (
+    BudgetSweepPoint,
+    MealPlanItem,
+    StomachDiagnosis,
+    UnmetCraving,
+    WhyNotReport,
+    append_meal_log,
 )
-from models.plan import MealPlanItem, append_meal_log
 
 logger = logging.getLogger(__name__)
 
+# Tie-break details for each automatic bite; enabled by -vv or --explain-tie
+tie_logger = logging.getLogger(f"{__name__}.ties")
+
+# Candidates offered per step when the user picks bites interactively
+INTERACTIVE_TOP_N = 5
+
+# Deepest greedy lookahead; each extra step re-ranks every candidate's copy
+MAX_LOOKAHEAD = 3
+
+# What automatic bite selection optimizes: SP gain, or foods reaching the
+# variety calorie threshold
+OBJECTIVE_SP = "sp"
+OBJECTIVE_VARIETY = "variety"
+PLAN_OBJECTIVES = (OBJECTIVE_SP, OBJECTIVE_VARIETY)
+
+# Why the planning loop ended (see `plan_meal_with_stop`)
+STOP_BUDGET = "budget"
+STOP_NO_FOOD = "no_food"
+STOP_MAX_BITES = "max_bites"
+STOP_USER = "user"
+STOP_ITERATION_CAP = "iteration_cap"
+
 
 # Ranking-only bias helpers; never change the SP shown to the user
 # Nutrient density after a hypothetical bite:
@@ -65,25 +123,29 @@ def _nutrient_sum(
 
 def fmt_signed(
     value: float,
+    precision: int = 2,
 ) -> str:
-    """Format a float with an explicit sign and two decimals.
+    """Format a float with an explicit sign and *precision* decimals.
 
     Parameters
     ----------
     value : float
         Number to format.
+    precision : int, optional
+        Digits after the decimal point. Defaults to ``2``.
 
     Returns
     -------
     str
         Formatted string like ``+1.23`` or ``-0.45``.
     """
-    return f"+{value:.2f}" if value >= 0 else f"{value:.2f}"
+    return f"+{value:.{precision}f}" if value >= 0 else f"{value:.{precision}f}"
 
 
 def _soft_variety_bias(
     stomach: dict[Food, int],
     food: Food,
+    soft_variety_before: float | None = None,
 ) -> float:
     """Bias based on change in soft-variety bonus if one unit is added.
 
@@ -96,6 +158,9 @@ def _soft_variety_bias(
         Current stomach counts.
     food : Food
         Candidate food to hypothetically add.
+    soft_variety_before : float, optional
+        Precomputed `soft_variety_count` of *stomach*, so callers scoring
+        many candidates scan the stomach once. Default computes it here.
 
     Returns
     -------
@@ -103,10 +168,14 @@ def _soft_variety_bias(
         Bias term to add to the primary rank score.
     """
     # Soft-variety delta (pp) after adding this food; scaled by
-    # post-bite nutrient density
-    soft_variety_before = soft_variety_count(stomach)
-    soft_variety_after = soft_variety_count(
-        simulate_stomach_with_added_food(stomach, food)
+    # post-bite nutrient density. Only the candidate's own fraction moves.
+    if soft_variety_before is None:
+        soft_variety_before = soft_variety_count(stomach)
+    quantity = stomach.get(food, 0)
+    soft_variety_after = (
+        soft_variety_before
+        - variety_fraction_for(food, quantity)
+        + variety_fraction_for(food, quantity + 1)
     )
     variety_delta_pp = get_variety_bonus(soft_variety_after) - get_variety_bonus(
         soft_variety_before
@@ -157,6 +226,82 @@ def _proximity_bias(
     return PROXIMITY_APPROACH_WEIGHT * proximity_score
 
 
+def _calories_to_qualify(
+    stomach: dict[Food, int],
+    food: Food,
+) -> int:
+    """Calories of whole units of *food* still needed to reach
+    ``VARIETY_CAL_THRESHOLD`` (0 once it counts toward variety).
+    """
+    eaten = food.calories * stomach.get(food, 0)
+    if eaten >= VARIETY_CAL_THRESHOLD:
+        return 0
+    units = math.ceil((VARIETY_CAL_THRESHOLD - eaten) / food.calories)
+    return units * food.calories
+
+
+def _order_for_variety(
+    stomach: dict[Food, int],
+    ranked: list[tuple[Food, float]],
+    remaining_calories: int,
+) -> list[tuple[Food, float]]:
+    """Reorder a ranking so the cheapest food to qualify for variety leads.
+
+    Foods that can still reach the threshold within the remaining budget
+    come first, fewest calories to go first, which finishes partly eaten
+    foods before starting new ones. The rest keep their SP order behind.
+    """
+
+    def key(pair: tuple[Food, float]) -> tuple[int, int]:
+        needed = _calories_to_qualify(stomach, pair[0])
+        if 0 < needed <= remaining_calories:
+            return 0, needed
+        return 1, 0
+
+    return sorted(ranked, key=key)
+
+
+def _missing_nutrients(
+    stomach: dict[Food, int],
+) -> list[str]:
+    """Nutrients no food in *stomach* provides (zero balance ratio)."""
+    eaten = [food for food, quantity in stomach.items() if quantity > 0]
+    return [
+        name
+        for name in NUTRIENT_FIELDS
+        if not any(getattr(food, name) > 0 for food in eaten)
+    ]
+
+
+def _cover_missing_nutrients(
+    stomach: dict[Food, int],
+    ranked: list[tuple[Food, float]],
+    tolerance: float,
+) -> list[tuple[Food, float]]:
+    """Move the food leaving the fewest nutrients at zero to the front.
+
+    Only foods whose SP delta is within *tolerance* of the best delta are
+    considered, so the guard never costs more than that per bite. Among
+    equal coverage the existing order wins; with nothing missing the
+    ranking is returned as is.
+    """
+    missing = _missing_nutrients(stomach)
+    if not missing or not ranked:
+        return ranked
+
+    def still_missing(pair: tuple[Food, float]) -> int:
+        return sum(1 for name in missing if getattr(pair[0], name) <= 0)
+
+    best_delta = max(delta for _, delta in ranked)
+    eligible = [pair for pair in ranked if pair[1] >= best_delta - tolerance]
+    if not eligible:
+        return ranked
+    pick = min(eligible, key=still_missing)
+    if still_missing(pick) >= still_missing(ranked[0]):
+        return ranked
+    return [pick, *(pair for pair in ranked if pair is not pick)]
+
+
 def _balance_improvement_bias(
     stomach: dict[Food, int],
     food: Food,
@@ -228,6 +373,57 @@ def _low_calorie_penalty(
     )
 
 
+def diagnose_stomach(
+    manager,
+) -> StomachDiagnosis:
+    """Explain which bonus holds SP back and which food would help most.
+
+    Compares balance, variety and taste bonuses with their maxima, finds the
+    nutrient limiting balance, and suggests the available food with the
+    highest `_balance_improvement_bias`.
+
+    Parameters
+    ----------
+    manager : FoodStateManager
+        Current stomach and availability.
+
+    Returns
+    -------
+    StomachDiagnosis
+        Bonuses, gaps, limiting nutrient, and suggested food.
+    """
+    stomach = manager.stomach
+    density, _ = sum_all_weighted_nutrients(stomach)
+    bonus_pp = {
+        "balance": calculate_balanced_diet_bonus(list(density.values())),
+        "variety": get_variety_bonus(len(manager.unique_variety_foods())),
+        "taste": get_tastiness_bonus(stomach),
+    }
+    max_pp = {
+        "balance": 50.0,
+        "variety": VARIETY_BONUS_CAP_PP,
+        "taste": max(TASTINESS_MULTIPLIERS.values()) * 100.0 * TASTINESS_WEIGHT,
+    }
+    gap_pp = {name: max_pp[name] - bonus_pp[name] for name in bonus_pp}
+
+    suggestion = None
+    suggestion_bias = 0.0
+    for food in sorted(manager.all_available(), key=lambda f: f.name):
+        bias = _balance_improvement_bias(stomach, food)
+        if bias > suggestion_bias:
+            suggestion, suggestion_bias = food, bias
+
+    return StomachDiagnosis(
+        bonus_pp=bonus_pp,
+        gap_pp=gap_pp,
+        weakest=max(gap_pp, key=gap_pp.__getitem__),
+        limiting_nutrient=min(density, key=density.__getitem__),
+        density=density,
+        suggestion=suggestion,
+        suggestion_bias=suggestion_bias,
+    )
+
+
 def update_cravings(
     cravings: list[str],
     food: Food,
@@ -254,20 +450,177 @@ def update_cravings(
     return False
 
 
-def _choose_next_bite(
+def history_penalties(
+    entries: list[dict],
+    lookback: int = HISTORY_LOOKBACK,
+    strength: float = HISTORY_PENALTY_STRENGTH,
+) -> dict[str, float]:
+    """Rank penalties for foods in the last *lookback* history entries.
+
+    The newest bite costs its food *strength* SP; older bites fade linearly
+    toward 0. A food eaten several times keeps its most recent penalty.
+
+    Parameters
+    ----------
+    entries : list of dict
+        History entries with a ``Name`` key, oldest first (`read_history`).
+    lookback : int, optional
+        Most recent entries considered.
+    strength : float, optional
+        Penalty (SP) for the newest bite.
+
+    Returns
+    -------
+    dict[str, float]
+        Penalty by lowercased food name; empty without history.
+    """
+    penalties: dict[str, float] = {}
+    recent = entries[-lookback:] if lookback > 0 else []
+    for age, entry in enumerate(reversed(recent)):
+        name = str(entry.get("Name", "")).lower()
+        if name:
+            penalties.setdefault(name, strength * (1.0 - age / lookback))
+    return penalties
+
+
+def _at_repeat_cap(
+    manager: FoodStateManager,
+    food: Food,
+    max_repeats: int | None,
+) -> bool:
+    """True if *food* already has *max_repeats* units in the stomach."""
+    return max_repeats is not None and manager.stomach.get(food, 0) >= max_repeats
+
+
+def _distinct_restriction(
+    meal_plan: list[MealPlanItem],
+    max_distinct: int | None,
+) -> set[str] | None:
+    """Names the next bite is limited to once the plan uses *max_distinct* foods.
+
+    ``None`` means no restriction (no cap, or the cap is not reached yet).
+    """
+    if max_distinct is None:
+        return None
+    planned = {normalize_name(item.name) for item in meal_plan}
+    return planned if len(planned) >= max_distinct else None
+
+
+@dataclass(frozen=True)
+class TieFinalist:
+    """A candidate inside the near-equal window, with its ranking terms.
+
+    Attributes
+    ----------
+    food : Food
+        Candidate food.
+    raw_delta : float
+        SP change from eating one unit now.
+    rank_score : float
+        First-pass score (raw delta plus low-calorie and balance terms).
+    soft_variety_bias : float
+        Soft-variety term added for the primary rank.
+    proximity_bias : float
+        Tie-break term toward the variety calorie threshold.
+    primary_rank : float
+        Score the finalists are ordered by, before the proximity tie-break.
+    """
+
+    food: Food
+    raw_delta: float
+    rank_score: float
+    soft_variety_bias: float
+    proximity_bias: float
+    primary_rank: float
+
+
+def tie_finalists(
+    manager: FoodStateManager,
+    near_candidates: list[tuple[Food, float, float]],
+    recent_penalties: dict[str, float] | None = None,
+) -> list[TieFinalist]:
+    """Score near-equal candidates and order them as selection does.
+
+    Ordered by ``(primary_rank, proximity_bias)`` descending, exact ties by
+    name. The first entry is the pick unless a stock-weighted draw is used.
+
+    Parameters
+    ----------
+    manager : FoodStateManager
+        Current food state.
+    near_candidates : list[tuple[Food, float, float]]
+        ``(food, raw_delta, rank_score)`` within the tie-break window.
+    recent_penalties : dict[str, float], optional
+        Per-name penalties from `history_penalties`, taken off the
+        primary rank.
+
+    Returns
+    -------
+    list[TieFinalist]
+        Finalists, best first.
+    """
+    finalists = []
+    soft_variety_now = soft_variety_count(manager.stomach)
+    for food, raw_delta, rank_score in near_candidates:
+        soft_variety_bias = _soft_variety_bias(
+            manager.stomach,
+            food,
+            soft_variety_now,
+        )
+        proximity_bias = _proximity_bias(
+            manager.stomach,
+            food,
+        )
+        balance_bias = _balance_improvement_bias(manager.stomach, food)
+        primary_rank = (
+            raw_delta
+            + _low_calorie_penalty(food)
+            + soft_variety_bias
+            + balance_bias
+            - (recent_penalties or {}).get(food.name.lower(), 0.0)
+        )
+        finalists.append(
+            TieFinalist(
+                food=food,
+                raw_delta=raw_delta,
+                rank_score=rank_score,
+                soft_variety_bias=soft_variety_bias,
+                proximity_bias=proximity_bias,
+                primary_rank=primary_rank,
+            )
+        )
+
+    # Exact ties go to the alphabetically first name so plans don't depend
+    # on catalog order (stable sorts keep the name order within ties).
+    finalists.sort(key=lambda finalist: finalist.food.name)
+    finalists.sort(
+        key=lambda finalist: (finalist.primary_rank, finalist.proximity_bias),
+        reverse=True,
+    )
+    return finalists
+
+
+def rank_candidates(
     manager: FoodStateManager,
     remaining_calories: int,
     cravings: list[str],
     cravings_satisfied: int,
-) -> tuple[
-    Food | None,
-    float,
-]:
-    """Select the next bite purely by ranking.
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    rng: random.Random | None = None,
+    objective: str = OBJECTIVE_SP,
+    recent_penalties: dict[str, float] | None = None,
+    zero_nutrient_tolerance: float | None = None,
+    restrict_to: set[str] | None = None,
+) -> list[tuple[Food, float]]:
+    """Rank every feasible bite, best first.
 
     First filters by feasibility (calories), then scores candidates by
     SP delta plus penalties/biases; finally applies a soft-variety primary rank
-    and a proximity tie-break among near-equal options.
+    and a proximity tie-break among near-equal options. Exact ties are broken
+    by food name, or by a stock-weighted draw when *rng* is given.
+    Candidates outside the near-equal window follow, ordered by their
+    first-pass score.
 
     Parameters
     ----------
@@ -279,25 +632,147 @@ def _choose_next_bite(
         Current cravings (names, case-insensitive).
     cravings_satisfied : int
         Count of cravings already satisfied.
+    max_repeats : int, optional
+        Hard cap on units of any one food in the stomach; foods at the cap
+        are skipped. ``None`` means no cap.
+    min_bite_calories : int, optional
+        Drop foods below this many calories unless nothing else fits.
+        ``None`` leaves small bites to the soft low-calorie penalty.
+    rng : random.Random, optional
+        When given, the candidates tied for first place are drawn in
+        proportion to their remaining stock and the winner moves to the
+        front, so abundant foods are favored among equals.
+    objective : str, optional
+        ``OBJECTIVE_VARIETY`` moves foods that can still reach the variety
+        threshold to the front, cheapest to qualify first. Default
+        ``OBJECTIVE_SP`` ranks by SP alone.
+    recent_penalties : dict[str, float], optional
+        Per-name rank penalties from `history_penalties`, so recently
+        eaten foods lose to fresh ones of similar value. Reported SP
+        deltas are unchanged.
+    zero_nutrient_tolerance : float, optional
+        While some nutrient is still zero in the stomach, lead with the
+        food covering the most missing nutrients among those within this
+        many SP of the best delta. ``None`` disables the guard.
+    restrict_to : set[str], optional
+        Normalized names the bite must come from (see `plan_meal`'s
+        *max_distinct*). ``None`` allows every food.
 
     Returns
     -------
-    tuple[Food | None, float]
-        Best food and its raw SP delta; (None, 0.0) if nothing fits.
+    list[tuple[Food, float]]
+        ``(food, raw SP delta)`` pairs; empty if nothing fits.
     """
-    candidates: list[tuple[Food, float, float]] = []
-    # (food, raw_delta, rank_score)
-    best_food = None
-    best_rank_score = float("-inf")
-    best_raw_delta = 0.0
+    return _rank_with_finalists(
+        manager,
+        remaining_calories,
+        cravings,
+        cravings_satisfied,
+        max_repeats,
+        min_bite_calories,
+        rng,
+        objective,
+        recent_penalties,
+        zero_nutrient_tolerance,
+        restrict_to,
+    )[0]
 
+
+def _rank_with_finalists(
+    manager: FoodStateManager,
+    remaining_calories: int,
+    cravings: list[str],
+    cravings_satisfied: int,
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    rng: random.Random | None = None,
+    objective: str = OBJECTIVE_SP,
+    recent_penalties: dict[str, float] | None = None,
+    zero_nutrient_tolerance: float | None = None,
+    restrict_to: set[str] | None = None,
+) -> tuple[list[tuple[Food, float]], list[TieFinalist]]:
+    """`rank_candidates` plus the finalists behind its first entries."""
     # 1) Compute raw ΔSP + low-calorie penalty (first pass, no soft/proximity)
+    candidates = _score_candidates(
+        manager,
+        remaining_calories,
+        cravings,
+        cravings_satisfied,
+        max_repeats,
+        min_bite_calories,
+        recent_penalties,
+        restrict_to,
+    )
+    if not candidates:
+        return [], []
+
+    # 2) Keep near-equals within TIEBREAK_SCORE_WINDOW_SP of the best rank_score
+    best_rank_score = max(rank_score for _, _, rank_score in candidates)
+    near_candidates = []
+    far_candidates = []
+    for candidate in candidates:
+        if (best_rank_score - candidate[2]) <= TIEBREAK_SCORE_WINDOW_SP:
+            near_candidates.append(candidate)
+        else:
+            far_candidates.append(candidate)
+
+    # 3) Add soft-variety as primary rank; use proximity as
+    #    deterministic tie-break
+    finalists = tie_finalists(manager, near_candidates, recent_penalties)
+    if rng is not None and finalists:
+        _draw_tied_leader(manager, finalists, rng)
+    far_candidates.sort(key=lambda candidate: candidate[0].name)
+    far_candidates.sort(key=lambda candidate: candidate[2], reverse=True)
+
+    ranked = [(finalist.food, finalist.raw_delta) for finalist in finalists] + [
+        (food, raw_delta) for food, raw_delta, _ in far_candidates
+    ]
+    if objective == OBJECTIVE_VARIETY:
+        ranked = _order_for_variety(manager.stomach, ranked, remaining_calories)
+    if zero_nutrient_tolerance is not None:
+        ranked = _cover_missing_nutrients(
+            manager.stomach, ranked, zero_nutrient_tolerance
+        )
+    return ranked, finalists
+
+
+def _candidate_exclusion(
+    manager: FoodStateManager,
+    food: Food,
+    remaining_calories: int,
+    max_repeats: int | None,
+    restrict_to: set[str] | None = None,
+) -> str | None:
+    """Why an in-stock *food* cannot be ranked right now, or ``None``."""
+    if food.calories <= MIN_CALORIE_FLOOR:
+        return f"at or below the {MIN_CALORIE_FLOOR}-cal floor"
+    # There is no over-budget first bite: a fitting food always wins, and
+    # when nothing fits the plan simply ends.
+    if food.calories > remaining_calories:
+        return "too large for the remaining budget"
+    if _at_repeat_cap(manager, food, max_repeats):
+        return "at the repeat cap"
+    if restrict_to is not None and normalize_name(food.name) not in restrict_to:
+        return "past the distinct-food cap"
+    return None
+
+
+def _score_candidates(
+    manager: FoodStateManager,
+    remaining_calories: int,
+    cravings: list[str],
+    cravings_satisfied: int,
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    recent_penalties: dict[str, float] | None = None,
+    restrict_to: set[str] | None = None,
+) -> list[tuple[Food, float, float]]:
+    """First-pass ``(food, raw_delta, rank_score)`` for every feasible bite."""
+    candidates: list[tuple[Food, float, float]] = []
     for food in manager.all_available():
-        # Skip foods at or below the calorie floor
-        if food.calories <= MIN_CALORIE_FLOOR:
-            continue
-        # Skip foods that exceed the remaining calorie budget for this plan
-        if food.calories > remaining_calories:
+        if _candidate_exclusion(
+            manager, food, remaining_calories, max_repeats, restrict_to
+        ):
             continue
 
         # Raw ΔSP from adding one unit now. Includes daily multiplier
@@ -312,7 +787,12 @@ def _choose_next_bite(
             raw_delta
             + _low_calorie_penalty(food)
             + _balance_improvement_bias(manager.stomach, food)
+            - (recent_penalties or {}).get(food.name.lower(), 0.0)
         )
+        # NaN compares False against everything and would scramble the ranking
+        if not math.isfinite(rank_score):
+            logger.debug("Dropping %s: non-finite score %r", food.name, rank_score)
+            continue
         candidates.append(
             (
                 food,
@@ -320,114 +800,382 @@ def _choose_next_bite(
                 rank_score,
             )
         )
-        if rank_score > best_rank_score:
-            best_rank_score = rank_score
-            best_food = food
-            best_raw_delta = raw_delta
 
-    if not candidates:
-        return None, 0.0
+    # Hard floor on bite size; fall back to small bites only if nothing is larger
+    if min_bite_calories is not None:
+        large_candidates = [c for c in candidates if c[0].calories >= min_bite_calories]
+        if large_candidates:
+            candidates = large_candidates
+    return candidates
 
-    # 2) Keep near-equals within TIEBREAK_SCORE_WINDOW_SP of the best rank_score
-    near_candidates = [
-        (food, raw_delta, rank_score)
-        for (food, raw_delta, rank_score) in candidates
-        if (best_rank_score - rank_score) <= TIEBREAK_SCORE_WINDOW_SP
-    ]
 
-    # Sort by (primary_rank, proximity_bias); pick the last (highest)
-    scored_candidates: list[tuple[Food, float, float, float]] = []
+def explain_why_not(
+    manager: FoodStateManager,
+    food_name: str,
+    remaining_calories: int,
+    cravings: list[str],
+    cravings_satisfied: int,
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    recent_penalties: dict[str, float] | None = None,
+) -> WhyNotReport:
+    """Report how one food fared in the ranking for the next bite.
 
-    # 3) Add soft-variety as primary rank; use proximity as
-    #    deterministic tie-break
-    for food, raw_delta, _rank_score in near_candidates:
-        soft_variety_bias = _soft_variety_bias(
-            manager.stomach,
-            food,
-        )
-        proximity_bias = _proximity_bias(
-            manager.stomach,
-            food,
+    Uses the same first-pass scoring and tie-break window as
+    `rank_candidates`, on the current state. Cravings eaten ahead of the
+    ranking are not considered.
+
+    Parameters
+    ----------
+    manager : FoodStateManager
+        Current food state; not modified.
+    food_name : str
+        Food to explain (case-insensitive).
+    remaining_calories : int
+        Calories left to spend.
+    cravings : list[str]
+        Current cravings (names, case-insensitive).
+    cravings_satisfied : int
+        Count of cravings already satisfied.
+    max_repeats : int, optional
+        Hard cap on units of any one food in the stomach.
+    min_bite_calories : int, optional
+        Bite-size floor, as for `rank_candidates`.
+    recent_penalties : dict[str, float], optional
+        History penalties, as for `rank_candidates`.
+
+    Returns
+    -------
+    WhyNotReport
+        Exclusion reason, or its score against the best candidate.
+
+    Raises
+    ------
+    FoodNotFoundError
+        If no food matches *food_name*.
+    """
+    food = manager.get_food(food_name)
+    if food is None:
+        raise FoodNotFoundError(food_name)
+    report = WhyNotReport(food.name)
+
+    candidates = _score_candidates(
+        manager,
+        remaining_calories,
+        cravings,
+        cravings_satisfied,
+        max_repeats,
+        min_bite_calories,
+        recent_penalties,
+    )
+    if candidates:
+        best, _, best_score = max(
+            sorted(candidates, key=lambda candidate: candidate[0].name),
+            key=lambda candidate: candidate[2],
         )
-        balance_bias = _balance_improvement_bias(manager.stomach, food)
-        primary_rank = (
-            raw_delta
-            + _low_calorie_penalty(food)
-            + soft_variety_bias
-            + balance_bias
+        report.best_name, report.best_rank_score = best.name, best_score
+
+    scores = {candidate.name: score for candidate, _, score in candidates}
+    if food.name in scores:
+        report.rank_score = scores[food.name]
+        gap = report.gap
+        if gap is not None:
+            report.in_tie_window = gap <= TIEBREAK_SCORE_WINDOW_SP
+        ranked = rank_candidates(
+            manager,
+            remaining_calories,
+            cravings,
+            cravings_satisfied,
+            max_repeats,
+            min_bite_calories,
+            recent_penalties=recent_penalties,
         )
-        scored_candidates.append(
-            (
-                food,
-                raw_delta,
-                primary_rank,
-                proximity_bias,
-            )
+        report.picked = ranked[0][0].name == food.name
+    elif not manager.can_consume(food):
+        report.excluded = "no stock left"
+    else:
+        report.excluded = _candidate_exclusion(
+            manager, food, remaining_calories, max_repeats
         )
+        if report.excluded is None:
+            if min_bite_calories is not None and food.calories < min_bite_calories:
+                report.excluded = "below the minimum bite size"
+            else:
+                report.excluded = "score is not a finite number"
+    return report
 
-    if not scored_candidates:
-        return best_food, best_raw_delta
 
-    scored_candidates.sort(
-        key=lambda candidate: (candidate[2], candidate[3]),
-    )  # (primary_rank, proximity_bias)
-    best_food, best_raw_delta, _, _ = scored_candidates[-1]
-    return best_food, best_raw_delta
+def _draw_tied_leader(
+    manager: FoodStateManager,
+    finalists: list[TieFinalist],
+    rng: random.Random,
+) -> None:
+    """Move a stock-weighted pick among the first-place ties to the front."""
+
+    def key(finalist: TieFinalist) -> tuple[float, float]:
+        return finalist.primary_rank, finalist.proximity_bias
+
+    leader_key = key(finalists[0])
+    tied_count = 1
+    while tied_count < len(finalists) and key(finalists[tied_count]) == leader_key:
+        tied_count += 1
+    if tied_count == 1:
+        return
+    tied = finalists[:tied_count]
+    weights = [manager.available.get(finalist.food, 0) for finalist in tied]
+    winner = rng.choices(range(tied_count), weights=weights)[0]
+    finalists.insert(0, finalists.pop(winner))
+
+
+def rank_foods_by_efficiency(
+    foods: list[Food],
+) -> list[tuple[Food, float]]:
+    """Rank foods by standalone SP per calorie on an empty stomach.
+
+    Independent of planning state: each food is scored as the ΔSP of one
+    unit eaten alone (no cravings) divided by its calories. Zero-calorie
+    foods are skipped.
 
+    Parameters
+    ----------
+    foods : list[Food]
+        Foods to rank (e.g., the whole catalog).
 
-def _apply_bite(
-    manager,
-    food,
-    *,
-    current_sp,
-    remaining_calories,
-    cravings,
-    cravings_satisfied,
-    variety_count_now,
-    meal_plan,
-    server_mult: float = 1.0,
-    dinner_party_mult: float = 1.0,
-) -> tuple[float, int, int, int]:
-    """Consume `food`, recompute SP/bonuses, append to log,
-    and return updated state.
+    Returns
+    -------
+    list[tuple[Food, float]]
+        ``(food, sp_per_calorie)`` pairs, highest first; ties keep input order.
     """
-    before_sp = current_sp
-    pre_stomach = dict(manager.stomach)
-    tastiness_delta = tastiness_delta_for_added_unit(pre_stomach, food)
-
-    manager.consume(food)
-    remaining_calories -= food.calories
+    ranked = [
+        (food, get_sp_delta(food, {}, [], 0) / food.calories)
+        for food in foods
+        if food.calories > 0
+    ]
+    ranked.sort(key=lambda pair: pair[1], reverse=True)
+    return ranked
 
-    # Consider it satisfied whenever this bite matches a
-    # remaining craving (case-insensitive)
-    food_name = normalize_name(food.name)
-    satisfied = False
-    if food_name in cravings:  # remove one instance if duplicated
-        cravings.remove(food_name)
-        cravings_satisfied += 1
-        satisfied = True
 
-    current_sp = manager.get_current_sp(
-        cravings=cravings,
-        cravings_satisfied=cravings_satisfied,
-        server_mult=server_mult,
-        dinner_party_mult=dinner_party_mult,
-    )
+def _choose_next_bite(
+    manager: FoodStateManager,
+    remaining_calories: int,
+    cravings: list[str],
+    cravings_satisfied: int,
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    rng: random.Random | None = None,
+    objective: str = OBJECTIVE_SP,
+    recent_penalties: dict[str, float] | None = None,
+    zero_nutrient_tolerance: float | None = None,
+    restrict_to: set[str] | None = None,
+    lookahead: int = 1,
+) -> tuple[
+    Food | None,
+    float,
+]:
+    """Select the next bite purely by ranking.
 
-    new_variety_count = variety_count(manager.stomach)
-    new_var = get_variety_bonus(new_variety_count)
-    old_var = get_variety_bonus(variety_count_now)
-    variety_delta = new_var - old_var
+    Returns the top entry of `rank_candidates`; *rng*, *objective*,
+    *recent_penalties*, *zero_nutrient_tolerance*, and *restrict_to* are
+    passed through.
+    With *lookahead* above 1 (SP objective only), every ranked candidate
+    is instead scored by `_lookahead_sp` and the best wins, earlier ranks
+    first on ties; 1 keeps the plain greedy pick.
+    With ``tie_logger`` at DEBUG, each finalist's ranking terms are logged
+    along with the pick (SP objective only).
+
+    Returns
+    -------
+    tuple[Food | None, float]
+        Best food and its raw SP delta; (None, 0.0) if nothing fits.
+    """
+    ranked, finalists = _rank_with_finalists(
+        manager,
+        remaining_calories,
+        cravings,
+        cravings_satisfied,
+        max_repeats,
+        min_bite_calories,
+        rng,
+        objective,
+        recent_penalties,
+        zero_nutrient_tolerance,
+        restrict_to,
+    )
+    if not ranked:
+        return None, 0.0
+    if objective == OBJECTIVE_SP and tie_logger.isEnabledFor(logging.DEBUG):
+        _log_tie_finalists(finalists)
+    if lookahead > 1 and objective == OBJECTIVE_SP and len(ranked) > 1:
+        return max(
+            ranked,
+            key=lambda pair: _lookahead_sp(
+                manager,
+                pair[0],
+                remaining_calories,
+                cravings,
+                cravings_satisfied,
+                lookahead,
+                max_repeats,
+                min_bite_calories,
+                recent_penalties,
+                zero_nutrient_tolerance,
+                restrict_to,
+            ),
+        )
+    return ranked[0]
+
+
+def _lookahead_sp(
+    manager: FoodStateManager,
+    food: Food,
+    remaining_calories: int,
+    cravings: list[str],
+    cravings_satisfied: int,
+    depth: int,
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    recent_penalties: dict[str, float] | None = None,
+    zero_nutrient_tolerance: float | None = None,
+    restrict_to: set[str] | None = None,
+) -> float:
+    """SP after eating *food* then up to *depth* - 1 greedy bites, on a copy.
+
+    Multipliers are left at 1.0: they scale every outcome alike, so the
+    comparison between candidates is unchanged.
+    """
+    clone = _clone_manager(manager)
+    cravings = list(cravings)
+    current_sp = clone.get_current_sp(cravings, cravings_satisfied)
+    variety_count_now = len(clone.unique_variety_foods())
+    for step in range(depth):
+        if step:
+            food, _ = _choose_next_bite(
+                clone,
+                remaining_calories,
+                cravings,
+                cravings_satisfied,
+                max_repeats,
+                min_bite_calories,
+                None,
+                OBJECTIVE_SP,
+                recent_penalties,
+                zero_nutrient_tolerance,
+                restrict_to,
+            )
+            if food is None:
+                break
+        (
+            current_sp,
+            remaining_calories,
+            cravings_satisfied,
+            variety_count_now,
+        ) = _apply_bite(
+            clone,
+            food,
+            current_sp=current_sp,
+            remaining_calories=remaining_calories,
+            cravings=cravings,
+            cravings_satisfied=cravings_satisfied,
+            variety_count_now=variety_count_now,
+            meal_plan=[],
+        )
+    return current_sp
 
-    append_meal_log(
-        meal_log=meal_plan,
-        food=food,
-        sp_gain=current_sp - before_sp,
-        new_sp=current_sp,
-        craving=satisfied,
-        variety_delta=variety_delta,
-        tastiness_delta=tastiness_delta,
-    )
+
+def _log_tie_finalists(
+    finalists: list[TieFinalist],
+) -> None:
+    """Log each finalist's ranking terms, marking the selected one."""
+    tie_logger.debug(
+        "%d finalist(s) within %.3f SP:",
+        len(finalists),
+        TIEBREAK_SCORE_WINDOW_SP,
+    )
+    for position, finalist in enumerate(finalists):
+        tie_logger.debug(
+            "%s %s rank=%.3f soft_variety=%.3f proximity=%.3f primary=%.3f",
+            "->" if position == 0 else "  ",
+            finalist.food.name,
+            finalist.rank_score,
+            finalist.soft_variety_bias,
+            finalist.proximity_bias,
+            finalist.primary_rank,
+        )
+
+
+def _clone_manager(
+    manager: FoodStateManager,
+) -> FoodStateManager:
+    """Deep copy of *manager* with no consume hook (exploration is silent).
+
+    *manager* itself is never touched, so copies may be taken from several
+    threads at once.
+    """
+    return copy.deepcopy(manager, {id(manager.on_consume): None})
+
+
+def _apply_bite(
+    manager,
+    food,
+    *,
+    current_sp,
+    remaining_calories,
+    cravings,
+    cravings_satisfied,
+    variety_count_now,
+    meal_plan,
+    server_mult: float = 1.0,
+    dinner_party_mult: float = 1.0,
+    accumulator: StomachAccumulator | None = None,
+) -> tuple[float, int, int, int]:
+    """Consume `food`, recompute SP/bonuses, append to log,
+    and return updated state.
+
+    A caller planning many bites passes its own `accumulator` (seeded from
+    the manager's stomach) so snapshots don't re-walk the stomach.
+    """
+    if accumulator is None:
+        accumulator = StomachAccumulator(manager.stomach)
+    before_sp = current_sp
+    before_taste = accumulator.tastiness_bonus()
+
+    if manager.consume(food):
+        accumulator.add(food)
+    remaining_calories -= food.calories
+    tastiness_delta = accumulator.tastiness_bonus() - before_taste
+
+    # Consider it satisfied whenever this bite matches a
+    # remaining craving (case-insensitive)
+    food_name = normalize_name(food.name)
+    satisfied = False
+    if food_name in cravings:  # remove one instance if duplicated
+        cravings.remove(food_name)
+        cravings_satisfied += 1
+        satisfied = True
+
+    current_sp = accumulator.sp(
+        cravings_satisfied,
+        server_mult=server_mult,
+        dinner_party_mult=dinner_party_mult,
+    )
+
+    new_variety_count = accumulator.variety_count
+    new_var = get_variety_bonus(new_variety_count)
+    old_var = get_variety_bonus(variety_count_now)
+    variety_delta = new_var - old_var
+    density = accumulator.density()
+
+    append_meal_log(
+        meal_log=meal_plan,
+        food=food,
+        sp_gain=current_sp - before_sp,
+        new_sp=current_sp,
+        craving=satisfied,
+        variety_delta=variety_delta,
+        tastiness_delta=tastiness_delta,
+        density=density,
+        balance_bonus=calculate_balanced_diet_bonus(list(density.values())),
+    )
 
     return (
         current_sp,
@@ -442,9 +1190,13 @@ def _pick_feasible_craving(
     cravings,
     remaining_calories,
     cravings_satisfied,
+    max_repeats=None,
+    restrict_to=None,
 ):
     """Return a craving food that can be eaten now.
-    Picks the highest ΔSP among feasible options, or None.
+    Picks the highest ΔSP among feasible options (ties by name), or None.
+    Cravings already at the *max_repeats* cap, or outside *restrict_to*
+    (the distinct-food cap), are skipped.
     """
     cravings_set = {normalize_name(name) for name in cravings}
     candidates = []
@@ -453,9 +1205,16 @@ def _pick_feasible_craving(
             not quantity_available
             or food.calories <= MIN_CALORIE_FLOOR
             or food.calories > remaining_calories
+            or _at_repeat_cap(manager, food, max_repeats)
         ):
             continue
         if normalize_name(food.name) in cravings_set:
+            if restrict_to is not None and normalize_name(food.name) not in restrict_to:
+                logger.info(
+                    "Craving '%s' skipped: plan is at the distinct-food cap",
+                    food.name,
+                )
+                continue
             sp_delta = get_sp_delta(
                 food,
                 manager.stomach,
@@ -463,7 +1222,68 @@ def _pick_feasible_craving(
                 cravings_satisfied,
             )
             candidates.append((sp_delta, food))
-    return max(candidates)[1] if candidates else None
+    if not candidates:
+        return None
+    # Equal ΔSP goes to the alphabetically first name
+    candidates.sort(key=lambda candidate: candidate[1].name)
+    return max(candidates, key=lambda candidate: candidate[0])[1]
+
+
+def unmet_cravings(
+    manager,
+    cravings,
+    meal_plan,
+    remaining_calories,
+    max_repeats=None,
+    max_distinct=None,
+) -> list[UnmetCraving]:
+    """Cravings the finished plan did not satisfy, with the reason.
+
+    Each satisfied bite in *meal_plan* covers one craving entry, so a
+    craving listed twice needs two craved bites.
+
+    Parameters
+    ----------
+    manager : FoodStateManager
+        State after planning.
+    cravings : list of str
+        Cravings requested before planning (case-insensitive names).
+    meal_plan : list[MealPlanItem]
+        The finished plan.
+    remaining_calories : int
+        Budget left after the plan.
+    max_repeats : int, optional
+        Repeat cap used while planning.
+    max_distinct : int, optional
+        Distinct-food cap used while planning.
+
+    Returns
+    -------
+    list[UnmetCraving]
+        One entry per unsatisfied craving, in request order.
+    """
+    pending = validate_cravings(manager, [normalize_name(c) for c in cravings])[0]
+    for item in meal_plan:
+        name = normalize_name(item.name)
+        if item.craving and name in pending:
+            pending.remove(name)
+
+    restrict_to = _distinct_restriction(meal_plan, max_distinct)
+    unmet = []
+    for name in pending:
+        food = manager.get_food(name)
+        if not manager.can_consume(food):
+            reason = "no stock left"
+        elif _at_repeat_cap(manager, food, max_repeats):
+            reason = "at the repeat cap"
+        elif restrict_to is not None and name not in restrict_to:
+            reason = "past the distinct-food cap"
+        elif food.calories > remaining_calories:
+            reason = "too large for the remaining budget"
+        else:
+            reason = "never chosen"
+        unmet.append(UnmetCraving(food.name, reason))
+    return unmet
 
 
 def normalize_name(
@@ -511,7 +1331,35 @@ def validate_cravings(
     return valid, invalid, suggestions
 
 
-def plan_meal(
+def resolve_cravings(
+    manager,
+    names: list[str],
+) -> tuple[list[str], list[str]]:
+    """Resolve free-text craving names to known foods.
+
+    Exact (case-insensitive) matches are kept; otherwise the closest fuzzy
+    match from `validate_cravings` is used.
+
+    Returns
+    -------
+    tuple[list[str], list[str]]
+        ``(resolved, unresolved)``: normalized food names, and the input
+        names with no match.
+    """
+    resolved = []
+    unresolved = []
+    for name in names:
+        valid, _, suggestions = validate_cravings(manager, [normalize_name(name)])
+        if valid:
+            resolved.append(valid[0])
+        elif suggestions:
+            resolved.append(next(iter(suggestions.values()))[0])
+        else:
+            unresolved.append(name)
+    return resolved, unresolved
+
+
+def _plan_in_place(
     manager,
     cravings,
     cravings_satisfied,
@@ -519,29 +1367,32 @@ def plan_meal(
     *,
     server_mult: float = 1.0,
     dinner_party_mult: float = 1.0,
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    pick_bite: Callable[[list[tuple[Food, float]]], Food | None] | None = None,
+    rng: random.Random | None = None,
+    max_bites: int | None = None,
+    overshoot_tolerance: int = 0,
+    require: list[str] | None = None,
+    objective: str = OBJECTIVE_SP,
+    recent_penalties: dict[str, float] | None = None,
+    zero_nutrient_tolerance: float | None = None,
+    max_distinct: int | None = None,
+    lookahead: int = 1,
 ):
-    """Plan a sequence of bites under the current constraints.
+    """Greedy planning loop; eats each chosen bite from *manager* directly.
 
-    Parameters
-    ----------
-    manager : FoodStateManager
-        Current food state/availability.
-    cravings : list of str
-        Requested cravings (case-insensitive names).
-    cravings_satisfied : int
-        Number of cravings already satisfied today.
-    remaining_calories : int
-        Calorie budget for this plan.
-    server_mult : float, optional
-        Server skill gain multiplier. Default is 1.0.
-    dinner_party_mult : float, optional
-        Dinner party multiplier (1.0-3.0). Default is 1.0.
-
-    Returns
-    -------
-    list[MealPlanItem]
-        Ordered plan with per-bite deltas and tags.
+    Callers pass a private copy (see `plan_from_state`); arguments are as
+    for `plan_meal`. Returns the plan and its ``STOP_*`` reason.
     """
+    if objective not in PLAN_OBJECTIVES:
+        raise ValueError(
+            f"objective must be one of {', '.join(PLAN_OBJECTIVES)}, got {objective!r}"
+        )
+    if not 1 <= lookahead <= MAX_LOOKAHEAD:
+        raise ValueError(
+            f"lookahead must be between 1 and {MAX_LOOKAHEAD}, got {lookahead}"
+        )
     current_sp = manager.get_current_sp(
         cravings,
         cravings_satisfied,
@@ -552,31 +1403,132 @@ def plan_meal(
     meal_plan: list[MealPlanItem] = []
     cravings = [normalize_name(name) for name in cravings]
     cravings = validate_cravings(manager, cravings)[0]
+    accumulator = StomachAccumulator(manager.stomach)
+    # Set when planning ends because no bite fits the budget
+    budget_exhausted = False
+    stop_reason = STOP_ITERATION_CAP
+
+    # They never use up the budget, so the calorie floor keeps them out
+    zero_calorie = sorted(f.name for f in manager.all_available() if f.calories <= 0)
+    if zero_calorie:
+        logger.warning(
+            "Skipping zero-calorie foods (they never use up the budget): %s",
+            ", ".join(zero_calorie),
+        )
+
+    # Forced staples first; their SP changes are logged like any bite
+    for name in require or []:
+        if max_bites is not None and len(meal_plan) >= max_bites:
+            logger.warning("Required food '%s' skipped: plan is at max bites", name)
+            continue
+        food = manager.get_food(name)
+        if food is None or not manager.can_consume(food):
+            logger.warning("Required food '%s' is not in stock; skipped", name)
+            continue
+        if food.calories <= 0:
+            logger.warning("Required food '%s' has no calories; skipped", name)
+            continue
+        restrict_to = _distinct_restriction(meal_plan, max_distinct)
+        if restrict_to is not None and normalize_name(food.name) not in restrict_to:
+            logger.warning(
+                "Required food '%s' skipped: plan is at the distinct-food cap", name
+            )
+            continue
+        if food.calories > remaining_calories:
+            logger.warning(
+                "Required food '%s' (%d cal) exceeds the remaining %d cal; skipped",
+                food.name,
+                food.calories,
+                remaining_calories,
+            )
+            continue
+        (
+            current_sp,
+            remaining_calories,
+            cravings_satisfied,
+            variety_count_now,
+        ) = _apply_bite(
+            manager,
+            food,
+            current_sp=current_sp,
+            remaining_calories=remaining_calories,
+            cravings=cravings,
+            cravings_satisfied=cravings_satisfied,
+            variety_count_now=variety_count_now,
+            meal_plan=meal_plan,
+            server_mult=server_mult,
+            dinner_party_mult=dinner_party_mult,
+            accumulator=accumulator,
+        )
 
     for _ in range(MAX_ITERATIONS):
         if remaining_calories <= 0:
+            budget_exhausted = True
+            stop_reason = STOP_BUDGET
             break
+        if max_bites is not None and len(meal_plan) >= max_bites:
+            stop_reason = STOP_MAX_BITES
+            break
+        restrict_to = _distinct_restriction(meal_plan, max_distinct)
 
-        # craving-first if feasible, else ranked best
-        food = _pick_feasible_craving(
-            manager,
-            cravings,
-            remaining_calories,
-            cravings_satisfied,
-        )
-        if not food:
-            food, _ = _choose_next_bite(
+        if pick_bite is not None:
+            ranked = rank_candidates(
                 manager,
                 remaining_calories,
                 cravings,
                 cravings_satisfied,
+                max_repeats,
+                min_bite_calories,
+                objective=objective,
+                recent_penalties=recent_penalties,
+                zero_nutrient_tolerance=zero_nutrient_tolerance,
+                restrict_to=restrict_to,
             )
-            if not food:
+            if not ranked:
                 logger.info(
                     "No suitable food with %d remaining cal",
                     remaining_calories,
                 )
+                stop_reason = STOP_NO_FOOD
+                break
+            food = pick_bite(ranked[:INTERACTIVE_TOP_N])
+            if food is None:
+                logger.info("Planning stopped by user")
+                stop_reason = STOP_USER
                 break
+        else:
+            # craving-first if feasible, else ranked best
+            food = _pick_feasible_craving(
+                manager,
+                cravings,
+                remaining_calories,
+                cravings_satisfied,
+                max_repeats,
+                restrict_to,
+            )
+            if not food:
+                food, _ = _choose_next_bite(
+                    manager,
+                    remaining_calories,
+                    cravings,
+                    cravings_satisfied,
+                    max_repeats,
+                    min_bite_calories,
+                    rng,
+                    objective,
+                    recent_penalties,
+                    zero_nutrient_tolerance,
+                    restrict_to,
+                    lookahead,
+                )
+                if not food:
+                    logger.info(
+                        "No suitable food with %d remaining cal",
+                        remaining_calories,
+                    )
+                    budget_exhausted = True
+                    stop_reason = STOP_NO_FOOD
+                    break
 
         logger.info(
             "Consume %s | %d cal",
@@ -599,12 +1551,409 @@ def plan_meal(
             meal_plan=meal_plan,
             server_mult=server_mult,
             dinner_party_mult=dinner_party_mult,
+            accumulator=accumulator,
         )
 
     else:
-        logger.warning(
-            "Loop exited after max iterations (%d).",
-            MAX_ITERATIONS,
+        # The last allowed bite may itself have finished the plan
+        if remaining_calories <= 0:
+            budget_exhausted = True
+            stop_reason = STOP_BUDGET
+        elif max_bites is not None and len(meal_plan) >= max_bites:
+            stop_reason = STOP_MAX_BITES
+        elif not rank_candidates(
+            manager,
+            remaining_calories,
+            cravings,
+            cravings_satisfied,
+            max_repeats,
+            min_bite_calories,
+            restrict_to=_distinct_restriction(meal_plan, max_distinct),
+        ):
+            budget_exhausted = True
+            stop_reason = STOP_NO_FOOD
+        else:
+            logger.warning(
+                "Loop exited after max iterations (%d).",
+                MAX_ITERATIONS,
+            )
+
+    at_capacity = max_bites is not None and len(meal_plan) >= max_bites
+    if (
+        budget_exhausted
+        and overshoot_tolerance > 0
+        and pick_bite is None
+        and not at_capacity
+    ):
+        food = _pick_overshoot_bite(
+            manager,
+            remaining_calories,
+            overshoot_tolerance,
+            cravings,
+            cravings_satisfied,
+            max_repeats,
+            min_bite_calories,
+            rng,
+            _distinct_restriction(meal_plan, max_distinct),
         )
+        if food is not None:
+            logger.info(
+                "Overshoot with %s | %d cal over budget",
+                food.name,
+                food.calories - remaining_calories,
+            )
+            _apply_bite(
+                manager,
+                food,
+                current_sp=current_sp,
+                remaining_calories=remaining_calories,
+                cravings=cravings,
+                cravings_satisfied=cravings_satisfied,
+                variety_count_now=variety_count_now,
+                meal_plan=meal_plan,
+                server_mult=server_mult,
+                dinner_party_mult=dinner_party_mult,
+                accumulator=accumulator,
+            )
+
+    return meal_plan, stop_reason
+
+
+def plan_meal(
+    manager,
+    cravings,
+    cravings_satisfied,
+    remaining_calories,
+    *,
+    server_mult: float = 1.0,
+    dinner_party_mult: float = 1.0,
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    pick_bite: Callable[[list[tuple[Food, float]]], Food | None] | None = None,
+    rng: random.Random | None = None,
+    max_bites: int | None = None,
+    overshoot_tolerance: int = 0,
+    require: list[str] | None = None,
+    objective: str = OBJECTIVE_SP,
+    recent_penalties: dict[str, float] | None = None,
+    zero_nutrient_tolerance: float | None = None,
+    max_distinct: int | None = None,
+    lookahead: int = 1,
+):
+    """Plan a sequence of bites under the current constraints.
+
+    Parameters
+    ----------
+    manager : FoodStateManager
+        Current food state/availability.
+    cravings : list of str
+        Requested cravings (case-insensitive names).
+    cravings_satisfied : int
+        Number of cravings already satisfied today.
+    remaining_calories : int
+        Calorie budget for this plan.
+    server_mult : float, optional
+        Server skill gain multiplier. Default is 1.0.
+    dinner_party_mult : float, optional
+        Dinner party multiplier (1.0-3.0). Default is 1.0.
+    max_repeats : int, optional
+        Hard cap on units of any one food in the stomach. Default is no cap.
+    min_bite_calories : int, optional
+        Skip ranked foods below this many calories unless nothing larger
+        fits. Cravings are still eaten. Default is no floor.
+    pick_bite : callable, optional
+        Interactive chooser. Receives the top ``INTERACTIVE_TOP_N`` ranked
+        ``(food, sp_delta)`` pairs and returns the food to eat, or ``None``
+        to stop. Replaces the automatic craving-first/ranked choice.
+    rng : random.Random, optional
+        Break exact ranking ties by a draw weighted by remaining stock
+        instead of by name. Pass a seeded instance for reproducible plans.
+    max_bites : int, optional
+        Stomach capacity: the plan never has more than this many bites,
+        counting required foods and any overshoot bite, even if calories
+        remain. ``MAX_ITERATIONS`` still applies; the smaller limit wins.
+    overshoot_tolerance : int, optional
+        Once nothing else fits, allow one final bite that exceeds the
+        budget by at most this many calories, if it raises SP. Ignored by
+        the interactive chooser. Default 0 never overshoots.
+    require : list of str, optional
+        Foods eaten first, one unit each, before normal planning. Names
+        that are unknown, out of stock, zero-calorie, or too large for the
+        remaining budget are skipped with a warning.
+    objective : str, optional
+        ``OBJECTIVE_VARIETY`` picks ranked bites that get the most foods
+        over the variety calorie threshold instead of the largest SP gain.
+        Cravings still come first. Default is ``OBJECTIVE_SP``.
+    recent_penalties : dict[str, float], optional
+        Rank penalties by lowercased name (see `history_penalties`) that
+        steer ranked bites away from recently eaten foods. Default ``None``
+        ranks without history.
+    zero_nutrient_tolerance : float, optional
+        Avoid the balance cliff: while the stomach lacks a nutrient
+        entirely, prefer ranked bites that supply it, giving up at most
+        this many SP per bite. Default ``None`` ranks by SP alone.
+    max_distinct : int, optional
+        Most different foods the plan may use. Once it uses that many,
+        every further bite (cravings, required foods, and the overshoot
+        bite included) must repeat one of them; cravings for other foods
+        are skipped with a log note. Foods already in the stomach before
+        planning do not count. Default is no cap.
+    lookahead : int, optional
+        Score each ranked bite by the SP reached after it and up to
+        ``lookahead - 1`` further greedy bites, so the plan can see past
+        thresholds such as variety. At most ``MAX_LOOKAHEAD``; ignored by
+        the interactive chooser and the variety objective. Default 1 is
+        plain greedy.
+
+    Returns
+    -------
+    list[MealPlanItem]
+        Ordered plan with per-bite deltas and tags.
+
+    Notes
+    -----
+    Zero-calorie foods are never planned: they would not use up the
+    budget, so the loop could only stop at ``MAX_ITERATIONS``. They fall
+    under ``MIN_CALORIE_FLOOR`` (validated ``>= 0``) and a warning lists
+    any that are in stock.
+
+    Planning runs on a private copy; the final counts are then restored
+    onto *manager* and its consume hook, if any, is called once per
+    planned bite in eating order. `plan_meal_with_stop` also reports why
+    planning ended.
+    """
+    return plan_meal_with_stop(
+        manager,
+        cravings,
+        cravings_satisfied,
+        remaining_calories,
+        server_mult=server_mult,
+        dinner_party_mult=dinner_party_mult,
+        max_repeats=max_repeats,
+        min_bite_calories=min_bite_calories,
+        pick_bite=pick_bite,
+        rng=rng,
+        max_bites=max_bites,
+        overshoot_tolerance=overshoot_tolerance,
+        require=require,
+        objective=objective,
+        recent_penalties=recent_penalties,
+        zero_nutrient_tolerance=zero_nutrient_tolerance,
+        max_distinct=max_distinct,
+        lookahead=lookahead,
+    )[0]
+
+
+def plan_meal_with_stop(
+    manager,
+    cravings,
+    cravings_satisfied,
+    remaining_calories,
+    **plan_kwargs,
+) -> tuple[list[MealPlanItem], str]:
+    """`plan_meal` plus the reason planning ended.
+
+    Parameters are as for `plan_meal`.
+
+    Returns
+    -------
+    tuple[list[MealPlanItem], str]
+        The plan, and ``STOP_BUDGET`` (budget used up), ``STOP_NO_FOOD``
+        (nothing left fits), ``STOP_MAX_BITES``, ``STOP_USER`` (the
+        interactive chooser quit), or ``STOP_ITERATION_CAP`` (cut short
+        after ``MAX_ITERATIONS`` bites).
+    """
+    work = _clone_manager(manager)
+    meal_plan, stop_reason = _plan_in_place(
+        work,
+        list(cravings),
+        cravings_satisfied,
+        remaining_calories,
+        **plan_kwargs,
+    )
+    manager.restore(work.snapshot())
+    if manager.on_consume is not None:
+        for item in meal_plan:
+            manager.on_consume(manager.get_food(item.name))
+    return meal_plan, stop_reason
+
+
+def plan_from_state(
+    manager,
+    cravings,
+    cravings_satisfied,
+    remaining_calories,
+    **plan_kwargs,
+) -> tuple[list[MealPlanItem], StateSnapshot]:
+    """Plan on a private copy of *manager*, leaving it untouched.
+
+    Nothing is shared with *manager* while planning, so several plans
+    (different budgets, cravings, ...) can start from one state at once,
+    e.g. from a thread pool.
+
+    Parameters
+    ----------
+    manager : FoodStateManager
+        Starting state; not modified.
+    cravings : list of str
+        Requested cravings (case-insensitive names).
+    cravings_satisfied : int
+        Number of cravings already satisfied today.
+    remaining_calories : int
+        Calorie budget for this plan.
+    **plan_kwargs
+        Extra `plan_meal` keywords (multipliers, repeat cap, ...).
+
+    Returns
+    -------
+    tuple[list[MealPlanItem], StateSnapshot]
+        The plan, and the stomach/availability counts after eating it
+        (pass to `FoodStateManager.restore` to commit).
+    """
+    work = _clone_manager(manager)
+    meal_plan, _ = _plan_in_place(
+        work,
+        list(cravings),
+        cravings_satisfied,
+        remaining_calories,
+        **plan_kwargs,
+    )
+    return meal_plan, work.snapshot()
+
+
+def _pick_overshoot_bite(
+    manager: FoodStateManager,
+    remaining_calories: int,
+    overshoot_tolerance: int,
+    cravings: list[str],
+    cravings_satisfied: int,
+    max_repeats: int | None = None,
+    min_bite_calories: int | None = None,
+    rng: random.Random | None = None,
+    restrict_to: set[str] | None = None,
+) -> Food | None:
+    """Best bite that overshoots the budget by at most the tolerance.
+
+    Ranks against the stretched budget and keeps only foods that do not
+    fit the real one and would raise SP; None if there are none.
+    """
+    ranked = rank_candidates(
+        manager,
+        remaining_calories + overshoot_tolerance,
+        cravings,
+        cravings_satisfied,
+        max_repeats,
+        min_bite_calories,
+        rng,
+        restrict_to=restrict_to,
+    )
+    for food, sp_delta in ranked:
+        if food.calories > remaining_calories and sp_delta > 0:
+            return food
+    return None
+
+
+def peek_next(
+    manager,
+    cravings,
+    cravings_satisfied,
+    remaining_calories,
+    n,
+    **plan_kwargs,
+) -> list[tuple[Food, float]]:
+    """Preview the next *n* bites the greedy planner would take.
 
-    return meal_plan
+    Plans on a private copy of *manager*, so stomach, availability, and the
+    consume hook of the real state are untouched.
+
+    Parameters
+    ----------
+    manager : FoodStateManager
+        Current state; not modified.
+    cravings : list of str
+        Requested cravings (case-insensitive names).
+    cravings_satisfied : int
+        Number of cravings already satisfied today.
+    remaining_calories : int
+        Calorie budget left.
+    n : int
+        Bites to preview; fewer are returned if the budget or stock runs out.
+    **plan_kwargs
+        Extra `plan_meal` keywords (multipliers, repeat cap, ...).
+
+    Returns
+    -------
+    list[tuple[Food, float]]
+        ``(food, projected SP gain)`` pairs in eating order, with foods
+        taken from *manager*.
+    """
+    if n <= 0:
+        return []
+    preview, _ = plan_from_state(
+        manager,
+        cravings,
+        cravings_satisfied,
+        remaining_calories,
+        max_bites=n,
+        **plan_kwargs,
+    )
+    return [(manager.get_food(item.name), item.sp_gain) for item in preview]
+
+
+def sweep_budgets(
+    manager,
+    cravings,
+    cravings_satisfied,
+    budgets,
+    **plan_kwargs,
+) -> list[BudgetSweepPoint]:
+    """Plan once per budget to show how SP grows with the calorie limit.
+
+    Each budget is planned on its own copy of *manager*, so every run
+    starts from the same state and *manager* is not modified. SP is a
+    calorie-weighted average, so a larger budget can end lower when the
+    extra bites dilute a denser stomach.
+
+    Parameters
+    ----------
+    manager : FoodStateManager
+        Current state; not modified.
+    cravings : list of str
+        Requested cravings (case-insensitive names).
+    cravings_satisfied : int
+        Number of cravings already satisfied today.
+    budgets : Iterable[int]
+        Calorie budgets to plan for, in output order.
+    **plan_kwargs
+        Extra `plan_meal` keywords (multipliers, repeat cap, ...).
+
+    Returns
+    -------
+    list[BudgetSweepPoint]
+        One point per budget.
+    """
+    start_sp = manager.get_current_sp(
+        cravings,
+        cravings_satisfied,
+        server_mult=plan_kwargs.get("server_mult", 1.0),
+        dinner_party_mult=plan_kwargs.get("dinner_party_mult", 1.0),
+    )
+    points = []
+    for budget in budgets:
+        meal_plan, _ = plan_from_state(
+            manager,
+            cravings,
+            cravings_satisfied,
+            budget,
+            **plan_kwargs,
+        )
+        final_sp = meal_plan[-1].new_sp if meal_plan else start_sp
+        points.append(
+            BudgetSweepPoint(
+                budget=budget,
+                calories=sum(item.calories for item in meal_plan),
+                final_sp=final_sp,
+                sp_per_100kcal=(final_sp - start_sp) / budget * 100.0,
+            )
+        )
+    return points
@@ -0,0 +1,199 @@
+"""Expected shape of ``food_state.json`` and a validator that reports it all.
+
+`read_food_dict` stops at the first bad entry; `validate_json` instead
+walks the whole file and returns every structural, type, and range error
+so a hand-edited file can be fixed in one pass.
+
+Exports
+-------
+FieldSpec
+FOOD_FIELDS
+food_json_schema
+validate_json
+"""
+
+import json
+from dataclasses import (
+    dataclass,
+)
+
+from constants import (
+    TASTINESS_MULTIPLIERS,
+)
+
+
+@dataclass(frozen=True)
+class FieldSpec:
+    """One key of a food entry.
+
+    Attributes
+    ----------
+    name : str
+        JSON key.
+    kind : str
+        ``"string"``, ``"number"`` or ``"integer"`` (JSON Schema names).
+    required : bool
+        Whether every entry must carry the key.
+    minimum : float or None
+        Smallest allowed value for numeric kinds.
+    choices : tuple[int, ...] or None
+        Allowed values, when the field is an enumeration.
+    alias : str or None
+        Older key accepted in place of *name* (e.g. ``"Fats"``).
+    """
+
+    name: str
+    kind: str
+    required: bool = True
+    minimum: float | None = None
+    choices: tuple[int, ...] | None = None
+    alias: str | None = None
+
+
+#: Keys of one food entry, in the order errors are reported
+FOOD_FIELDS: tuple[FieldSpec, ...] = (
+    FieldSpec("Name", "string"),
+    FieldSpec("Calories", "number", minimum=0),
+    FieldSpec("Carbs", "number", minimum=0),
+    FieldSpec("Protein", "number", minimum=0),
+    FieldSpec("Fat", "number", minimum=0, alias="Fats"),
+    FieldSpec("Vitamins", "number", minimum=0),
+    FieldSpec("Tastiness", "integer", choices=tuple(sorted(TASTINESS_MULTIPLIERS))),
+    FieldSpec("Stomach", "integer", required=False, minimum=0),
+    FieldSpec("Available", "integer", required=False, minimum=0),
+)
+
+
+def food_json_schema() -> dict:
+    """JSON Schema (draft 2020-12) for the food-state file.
+
+    Built from `FOOD_FIELDS`, for editors and other tools that validate
+    JSON against a schema.
+
+    Returns
+    -------
+    dict
+        Schema for a list of food objects.
+    """
+    properties: dict[str, dict] = {}
+    required = []
+    alternatives = []
+    for spec in FOOD_FIELDS:
+        rule: dict = {"type": spec.kind}
+        if spec.kind == "string":
+            rule["minLength"] = 1
+        if spec.minimum is not None:
+            rule["minimum"] = spec.minimum
+        if spec.choices is not None:
+            rule["enum"] = list(spec.choices)
+        properties[spec.name] = rule
+        if spec.alias is not None:
+            properties[spec.alias] = rule
+            if spec.required:
+                alternatives.append(
+                    {"anyOf": [{"required": [spec.name]}, {"required": [spec.alias]}]}
+                )
+        elif spec.required:
+            required.append(spec.name)
+
+    item: dict = {"type": "object", "required": required, "properties": properties}
+    if alternatives:
+        item["allOf"] = alternatives
+    return {
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "EcoDietMaker food state",
+        "type": "array",
+        "items": item,
+    }
+
+
+def _type_name(
+    value,
+) -> str:
+    """JSON type name of a decoded value, for error messages."""
+    if value is None:
+        return "null"
+    if isinstance(value, bool):
+        return "boolean"
+    if isinstance(value, (int, float)):
+        return "number"
+    if isinstance(value, str):
+        return "string"
+    if isinstance(value, list):
+        return "array"
+    return "object"
+
+
+def _field_errors(
+    spec: FieldSpec,
+    value,
+) -> list[str]:
+    """Problems with one present field; wording matches `Food.validation_errors`."""
+    if spec.kind == "string":
+        if not isinstance(value, str):
+            return [f"{spec.name} must be a string (got {_type_name(value)})"]
+        if not value.strip():
+            return [f"{spec.name} is empty"]
+        return []
+
+    # bool is an int subclass in Python but not a number in JSON
+    if isinstance(value, bool) or not isinstance(value, (int, float)):
+        return [f"{spec.name} must be a {spec.kind} (got {_type_name(value)})"]
+    if spec.kind == "integer" and not float(value).is_integer():
+        return [f"{spec.name} must be a whole number (got {value})"]
+    if spec.minimum is not None and value < spec.minimum:
+        return [f"{spec.name} is negative ({value})"]
+    if spec.choices is not None and value not in spec.choices:
+        return [f"{spec.name} {value} is not a valid rating"]
+    return []
+
+
+def validate_json(
+    content: str,
+) -> list[str]:
+    """Check food-state JSON text against `FOOD_FIELDS`.
+
+    Every entry is checked, and every field of each entry, so one call
+    lists all problems in the file.
+
+    Parameters
+    ----------
+    content : str
+        Raw file contents.
+
+    Returns
+    -------
+    list[str]
+        One message per problem, prefixed with the entry's name (or
+        ``<entry N>`` when it has none). Empty when the file is valid.
+    """
+    if not content.strip():
+        return ["file is empty"]
+    try:
+        data = json.loads(content)
+    except json.JSONDecodeError as exc:
+        return [f"not valid JSON: {exc.msg} (line {exc.lineno}, column {exc.colno})"]
+    if not isinstance(data, list):
+        return [f"expected a list of foods but found {_type_name(data)}"]
+
+    errors = []
+    for index, entry in enumerate(data):
+        if not isinstance(entry, dict):
+            errors.append(
+                f"<entry {index}>: expected an object but found {_type_name(entry)}"
+            )
+            continue
+        name = entry.get("Name")
+        label = name if isinstance(name, str) and name.strip() else f"<entry {index}>"
+        for spec in FOOD_FIELDS:
+            key = spec.name
+            if key not in entry and spec.alias is not None and spec.alias in entry:
+                key = spec.alias
+            if key not in entry:
+                if spec.required:
+                    errors.append(f"{label}: missing field {spec.name!r}")
+                continue
+            errors.extend(
+                f"{label}: {problem}" for problem in _field_errors(spec, entry[key])
+            )
+    return errors
@@ -0,0 +1,59 @@
+"""Tests for the beam-search planner."""
+
+import pytest  # pyright: ignore[reportMissingImports]
+
+from beam_planner import plan_beam
+from planner import plan_meal
+
+
+def _final_sp(meal_plan, manager) -> float:
+    """SP after the plan, falling back to the untouched stomach."""
+    if meal_plan:
+        return meal_plan[-1].new_sp
+    return manager.get_current_sp(cravings=[], cravings_satisfied=0)
+
+
+# ---------------------------------------------------------------------------
+# TestPlanBeam
+# ---------------------------------------------------------------------------
+
+
+class TestPlanBeam:
+    """Beam search against the greedy baseline."""
+
+    @pytest.mark.parametrize("beam_width", [1, 2, 3])
+    @pytest.mark.parametrize("budget", [1000, 2000, 3000])
+    def test_never_below_greedy(
+        self, simple_manager_factory, beam_width: int, budget: int
+    ) -> None:
+        greedy_manager = simple_manager_factory()
+        beam_manager = simple_manager_factory()
+
+        greedy = plan_meal(greedy_manager, [], 0, budget)
+        beam = plan_beam(beam_manager, [], 0, budget, beam_width)
+
+        assert _final_sp(beam, beam_manager) >= _final_sp(greedy, greedy_manager)
+
+    def test_respects_budget_and_updates_manager(
+        self, simple_manager_factory
+    ) -> None:
+        manager = simple_manager_factory()
+
+        meal_plan = plan_beam(manager, [], 0, 3000, 3)
+
+        assert sum(item.calories for item in meal_plan) <= 3000
+        assert sum(manager.stomach.values()) == len(meal_plan)
+        assert manager.get_current_sp(
+            cravings=[], cravings_satisfied=0
+        ) == pytest.approx(meal_plan[-1].new_sp)
+
+    def test_expansion_cap_still_returns_plan(self, simple_manager_factory) -> None:
+        manager = simple_manager_factory()
+
+        meal_plan = plan_beam(manager, [], 0, 3000, 3, max_expansions=2)
+
+        assert meal_plan
+
+    def test_rejects_zero_width(self, simple_manager_factory) -> None:
+        with pytest.raises(ValueError):
+            plan_beam(simple_manager_factory(), [], 0, 2000, 0)
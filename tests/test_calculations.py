@@ -4,16 +4,28 @@ from collections import (
 )
 
 from calculations import (
+    StomachAccumulator,
+    _unique_variety_names,
     calculate_nutrition_multiplier,
+    check_nutrient_targets,
+    dinner_party_multiplier,
     evaluate_bonus_with_addition,
     get_sp,
     get_sp_delta,
     get_tastiness_bonus,
     get_variety_bonus,
     simulate_stomach_with_added_food,
+    sp_breakdown,
     sum_all_weighted_nutrients,
+    total_nutrients,
+    variety_count,
+    variety_progress,
+)
+from config import (
+    TasteScaleConfig,
 )
 from constants import (
+    BASE_SKILL_POINTS,
     CRAVING_SATISFIED_FRAC,
     TASTINESS_WEIGHT,
     TASTINESS_MULTIPLIERS,
@@ -104,6 +116,51 @@ def test_variety_bonus_is_capped_and_monotonic():
     assert 0.0 <= x1 <= x2 <= x3 <= VARIETY_BONUS_CAP_PP
 
 
+def test_variety_half_life_sets_midpoint(monkeypatch):
+    """Half of the cap is reached at exactly VARIETY_HALF_LIFE foods."""
+    assert math.isclose(get_variety_bonus(20), VARIETY_BONUS_CAP_PP / 2)
+
+    monkeypatch.setattr("calculations.VARIETY_HALF_LIFE", 10.0)
+
+    assert math.isclose(get_variety_bonus(10), VARIETY_BONUS_CAP_PP / 2)
+    assert math.isclose(get_variety_bonus(20), VARIETY_BONUS_CAP_PP * 0.75)
+
+
+def test_widened_taste_scale_raises_favorite_sp(monkeypatch):
+    """A wider taste scale lifts SP for a favorite food; unknown stays neutral."""
+    favorite = food("pie", 300, c=10, p=10, f=10, v=10, t=3)
+    unknown = food("mystery", 300, c=10, p=10, f=10, v=10, t=99)
+    fav_st = stomach_dict((favorite, 2))
+    unk_st = stomach_dict((unknown, 2))
+
+    before = get_sp(fav_st, [], 0, {"pie"})
+    before_unknown = get_sp(unk_st, [], 0, {"mystery"})
+    before_acc = StomachAccumulator(fav_st).sp(0)
+    widened = TasteScaleConfig(min_mult=-0.6, max_mult=0.6).as_multipliers()
+    monkeypatch.setattr("calculations.TASTINESS_MULTIPLIERS", widened)
+    after = get_sp(fav_st, [], 0, {"pie"})
+
+    assert after > before
+    assert StomachAccumulator(fav_st).sp(0) > before_acc
+    assert math.isclose(get_sp(unk_st, [], 0, {"mystery"}), before_unknown)
+
+
+def test_base_skill_points_shift_sp_by_server_mult(monkeypatch):
+    """Raising the base by Δ raises SP by exactly Δ × server_mult."""
+    st = stomach_dict((food("fish", 300, p=30), 2))
+    server_mult = 1.5
+
+    before = get_sp(st, [], 0, {"fish"}, server_mult=server_mult)
+    before_acc = StomachAccumulator(st).sp(0, server_mult=server_mult)
+    monkeypatch.setattr("calculations.BASE_SKILL_POINTS", 20.5)
+    after = get_sp(st, [], 0, {"fish"}, server_mult=server_mult)
+    after_acc = StomachAccumulator(st).sp(0, server_mult=server_mult)
+
+    expected = (20.5 - BASE_SKILL_POINTS) * server_mult
+    assert math.isclose(after - before, expected)
+    assert math.isclose(after_acc - before_acc, expected)
+
+
 def test_get_sp_satisfied_cravings_fraction_scales_total():
     a = food("fish", 300, p=30)
     st = stomach_dict((a, 2))  # calories/qty shouldn't change the delta
@@ -313,6 +370,17 @@ def test_balanced_diet_ratio_all_zero():
     assert calculate_balanced_diet_ratio([0.0, 0.0, 0.0, 0.0]) == 0.0
 
 
+def test_cv_balance_metric_softens_single_zero_nutrient():
+    """Three good nutrients and one zero: minmax gives 0, cv stays positive."""
+    from calculations import calculate_balanced_diet_ratio
+
+    nutrients = [10.0, 10.0, 10.0, 0.0]
+    assert calculate_balanced_diet_ratio(nutrients, metric="minmax") == 0.0
+    cv_ratio = calculate_balanced_diet_ratio(nutrients, metric="cv")
+    assert math.isclose(cv_ratio, 1.0 - (18.75**0.5) / 7.5)
+    assert calculate_balanced_diet_ratio([3.0, 3.0, 3.0, 3.0], metric="cv") == 1.0
+
+
 def test_sp_matches_game_pumpkin_scenario():
     """Reproduce the in-game scenario: 6 pumpkins, SP should be 16."""
     pumpkin = food("Pumpkin", 340, c=5, p=1, f=0, v=2, t=0)
@@ -325,3 +393,167 @@ def test_sp_matches_game_pumpkin_scenario():
     # density=8, balanced_diet ratio=0 (fat=0) → -50pp → mult=0.5
     # nutrition_sp = 8 * 0.5 = 4, SP = 4 + 12 = 16
     assert abs(sp - 16.0) < 0.1, f"Expected ~16.0, got {sp}"
+
+
+def test_vitamin_weight_raises_sp_only_for_vitamin_rich_food(monkeypatch):
+    """Doubling the vitamin weight helps vitamin-heavy food, not vitamin-free."""
+    rich = {food("Salad", 1000, c=5, p=5, f=5, v=20): 1}
+    poor = {food("Bread", 1000, c=10, p=10, f=10, v=0): 1}
+    before = {
+        name: get_sp(stomach, [], 0, set())
+        for name, stomach in (("rich", rich), ("poor", poor))
+    }
+
+    monkeypatch.setattr(
+        "calculations.NUTRIENT_WEIGHTS",
+        {"carbs": 1.0, "protein": 1.0, "fat": 1.0, "vitamins": 2.0},
+    )
+
+    assert get_sp(rich, [], 0, set()) > before["rich"]
+    assert math.isclose(get_sp(poor, [], 0, set()), before["poor"])
+
+
+def test_stomach_accumulator_matches_get_sp_after_each_bite():
+    """Running totals agree with a full re-walk after every bite."""
+    bread = food("Bread", 600, c=12, p=3, f=8, v=0, t=2)
+    salad = food("Salad", 1100, c=12, p=6, f=8, v=22, t=-1)
+    steak = food("Steak", 1400, c=10, p=18, f=12, v=8, t=3)
+    stomach = {bread: 2}
+    acc = StomachAccumulator(stomach)
+
+    for bite in (salad, bread, steak, salad, steak, bread, bread):
+        acc.add(bite)
+        stomach[bite] = stomach.get(bite, 0) + 1
+        truth = get_sp(
+            stomach,
+            [],
+            1,
+            _unique_variety_names(stomach),
+            server_mult=1.5,
+            dinner_party_mult=2.0,
+        )
+        assert abs(acc.sp(1, server_mult=1.5, dinner_party_mult=2.0) - truth) < 1e-9
+        density, _ = sum_all_weighted_nutrients(stomach)
+        assert all(math.isclose(acc.density()[k], v) for k, v in density.items())
+        assert acc.variety_count == len(_unique_variety_names(stomach))
+
+
+def test_stomach_accumulator_empty_matches_base_sp():
+    assert StomachAccumulator().sp() == get_sp({}, [], 0, set())
+
+
+def test_stomach_accumulator_variety_count_tracks_threshold_crossings():
+    """Incremental variety count matches a full recount bite by bite."""
+    bread = food("Bread", 700, c=12, p=3, f=8, v=0)
+    salad = food("Salad", 1100, c=12, p=6, f=8, v=22)
+    steak = food("Steak", VARIETY_CAL_THRESHOLD, c=10, p=18, f=12, v=8)
+    stomach = {bread: 1}
+    acc = StomachAccumulator(stomach)
+    counts = []
+
+    for bite in (bread, salad, steak, bread, salad, bread, steak):
+        acc.add(bite)
+        stomach[bite] = stomach.get(bite, 0) + 1
+        assert acc.variety_count == variety_count(stomach)
+        counts.append(acc.variety_count)
+
+    # Steak qualifies on one bite; bread on its third; salad on its second
+    assert counts == [0, 0, 1, 2, 3, 3, 3]
+
+
+def test_variety_dinner_party_grows_with_distinct_foods(monkeypatch):
+    """In variety mode more qualifying foods raise the dinner-party share."""
+    dishes = [
+        food(name, 1000, c=10, p=10, f=10, v=10)
+        for name in ("Bread", "Salad", "Steak", "Stew")
+    ]
+
+    contributions = []
+    for count in range(1, len(dishes) + 1):
+        stomach = {dish: 2 for dish in dishes[:count]}  # 2000 cal each
+        names = _unique_variety_names(stomach)
+        monkeypatch.setattr("calculations.DINNER_PARTY_MODE", "fixed")
+        fixed = get_sp(stomach, [], 0, names)
+        monkeypatch.setattr("calculations.DINNER_PARTY_MODE", "variety")
+        derived = get_sp(stomach, [], 0, names)
+        assert math.isclose(StomachAccumulator(stomach).sp(), derived)
+        contributions.append(derived - fixed)
+
+    assert all(a < b for a, b in zip(contributions, contributions[1:]))
+
+
+def test_dinner_party_modes(monkeypatch):
+    """Fixed mode passes the multiplier through; variety mode caps it."""
+    assert dinner_party_multiplier(2.5, 40) == 2.5
+
+    monkeypatch.setattr("calculations.DINNER_PARTY_MODE", "variety")
+    monkeypatch.setattr("calculations.DINNER_PARTY_PER_FOOD", 0.25)
+    monkeypatch.setattr("calculations.DINNER_PARTY_MAX", 2.0)
+
+    assert dinner_party_multiplier(2.5, 0) == 1.0
+    assert dinner_party_multiplier(2.5, 2) == 1.5
+    assert dinner_party_multiplier(2.5, 40) == 2.0
+
+
+def test_sp_breakdown_total_matches_get_sp():
+    bread = food("Bread", 600, c=12, p=3, f=8, v=0, t=2)
+    salad = food("Salad", 1100, c=12, p=6, f=8, v=22, t=-1)
+    stomach = {bread: 4, salad: 2}
+    variety = _unique_variety_names(stomach)
+
+    breakdown = sp_breakdown(
+        stomach, 2, variety, server_mult=1.2, dinner_party_mult=1.5
+    )
+
+    truth = get_sp(stomach, [], 2, variety, server_mult=1.2, dinner_party_mult=1.5)
+    assert math.isclose(breakdown.total, truth, rel_tol=1e-12)
+
+
+def test_total_nutrients_sums_every_bite():
+    bread = food("Bread", 600, c=12, p=3, f=8, v=0)
+    salad = food("Salad", 1100, c=12, p=6, f=8, v=22)
+    bites = [bread, salad, bread, bread]
+    stomach = {bread: 3, salad: 1}
+
+    totals = total_nutrients(stomach)
+
+    for nutrient in ("carbs", "protein", "fat", "vitamins"):
+        assert totals[nutrient] == sum(getattr(b, nutrient) for b in bites)
+
+
+def test_check_nutrient_targets_flags_met_and_under():
+    bread = food("Bread", 600, c=12, p=3, f=8, v=0)
+    stomach = {bread: 5}  # protein 15, carbs 60, vitamins 0
+
+    targets = check_nutrient_targets(
+        stomach, {"protein": 15, "carbs": 61, "vitamins": 0}
+    )
+
+    assert [(t.nutrient, t.met) for t in targets] == [
+        ("protein", True),
+        ("carbs", False),
+        ("vitamins", True),
+    ]
+    assert targets[1].shortfall == 1
+    assert targets[0].shortfall == 0
+
+
+def test_variety_progress_per_food():
+    bread = food("Bread", 600)
+    salad = food("Salad", 1100)
+    germ = food("Germ", 0)
+    threshold = VARIETY_CAL_THRESHOLD
+    units = math.ceil(threshold / 600)
+    stomach = {bread: units, salad: 1, germ: 3, food("Pie", 900): 0}
+
+    progress = {entry.name: entry for entry in variety_progress(stomach)}
+
+    assert set(progress) == {"Bread", "Salad", "Germ"}
+    assert progress["Bread"].qualifies
+    assert progress["Bread"].units_needed == 0
+    assert progress["Bread"].progress == 600 * units / threshold
+    assert not progress["Salad"].qualifies
+    assert progress["Salad"].calories == 1100
+    assert progress["Salad"].units_needed == math.ceil((threshold - 1100) / 1100)
+    assert progress["Germ"].units_needed is None
+    assert [e.name for e in variety_progress(stomach)] == ["Bread", "Salad", "Germ"]
@@ -3,6 +3,7 @@
 import pytest
 
 from interface.cli import build_parser
+from planner import MAX_LOOKAHEAD
 
 
 class TestBuildParser:
@@ -14,6 +15,122 @@ class TestBuildParser:
         assert args.cmd == "plan"
         assert args.server_mult == 1.0
         assert args.dinner_party == 1.0
+        assert args.max_repeats is None
+        assert args.servings == []
+        assert args.seed == []
+
+    def test_plan_servings_repeatable(self) -> None:
+        """--servings FOOD=FACTOR parses into (name, factor) pairs."""
+        args = build_parser().parse_args(
+            ["plan", "--servings", "Elk Taco=1.5", "--servings", "Bannock=2"]
+        )
+        assert args.servings == [("Elk Taco", 1.5), ("Bannock", 2.0)]
+
+    @pytest.mark.parametrize("bad", ["Bannock", "Bannock=0", "=2", "Bannock=x"])
+    def test_plan_servings_rejects_bad_values(self, bad: str) -> None:
+        with pytest.raises(SystemExit):
+            build_parser().parse_args(["plan", "--servings", bad])
+
+    def test_plan_seed_repeatable(self) -> None:
+        """--seed FOOD:QTY parses into (name, quantity) pairs."""
+        args = build_parser().parse_args(
+            ["plan", "--seed", "Apple:3", "--seed", "Elk Taco:1"]
+        )
+        assert args.seed == [("Apple", 3), ("Elk Taco", 1)]
+
+    @pytest.mark.parametrize("bad", ["Apple", "Apple:0", ":2", "Apple:1.5"])
+    def test_plan_seed_rejects_bad_values(self, bad: str) -> None:
+        with pytest.raises(SystemExit):
+            build_parser().parse_args(["plan", "--seed", bad])
+
+    def test_plan_max_bites(self) -> None:
+        """--max-bites N caps the total bites; absent means no cap."""
+        assert build_parser().parse_args(["plan"]).max_bites is None
+        args = build_parser().parse_args(["plan", "--max-bites", "6"])
+        assert args.max_bites == 6
+
+    def test_plan_max_distinct_foods(self) -> None:
+        """--max-distinct-foods N caps different foods; absent means no cap."""
+        assert build_parser().parse_args(["plan"]).max_distinct_foods is None
+        args = build_parser().parse_args(["plan", "--max-distinct-foods", "4"])
+        assert args.max_distinct_foods == 4
+
+    def test_plan_max_repeats(self) -> None:
+        """--max-repeats N caps units per food; absent means no cap."""
+        assert build_parser().parse_args(["plan"]).max_repeats is None
+        args = build_parser().parse_args(["plan", "--max-repeats", "2"])
+        assert args.max_repeats == 2
+
+    @pytest.mark.parametrize(
+        "flag", ["--max-repeats", "--max-bites", "--max-distinct-foods"]
+    )
+    @pytest.mark.parametrize("bad", ["0", "-3", "many"])
+    def test_plan_caps_reject_bad_values(self, flag: str, bad: str) -> None:
+        with pytest.raises(SystemExit):
+            build_parser().parse_args(["plan", flag, bad])
+
+    def test_plan_greedy_lookahead(self) -> None:
+        """--greedy-lookahead K defaults to 1 and is bounded by MAX_LOOKAHEAD."""
+        assert build_parser().parse_args(["plan"]).greedy_lookahead == 1
+        deepest = str(MAX_LOOKAHEAD)
+        args = build_parser().parse_args(["plan", "--greedy-lookahead", deepest])
+        assert args.greedy_lookahead == MAX_LOOKAHEAD
+        with pytest.raises(SystemExit):
+            too_deep = str(MAX_LOOKAHEAD + 1)
+            build_parser().parse_args(["plan", "--greedy-lookahead", too_deep])
+
+    def test_plan_targets(self) -> None:
+        """--targets NUTRIENT=GOAL,... parses into a goal per nutrient."""
+        args = build_parser().parse_args(
+            ["plan", "--targets", "Protein=150,vitamins=80"]
+        )
+        assert args.targets == {"protein": 150.0, "vitamins": 80.0}
+
+    @pytest.mark.parametrize(
+        "bad", ["protein", "sugar=5", "protein=-1", "fat=x", "fat=nan", "fat=inf"]
+    )
+    def test_plan_targets_rejects_bad_values(self, bad: str) -> None:
+        with pytest.raises(SystemExit):
+            build_parser().parse_args(["plan", "--targets", bad])
+
+    def test_plan_beam(self) -> None:
+        """--beam N takes a width of at least one partial plan."""
+        assert build_parser().parse_args(["plan"]).beam is None
+        assert build_parser().parse_args(["plan", "--beam", "3"]).beam == 3
+
+    @pytest.mark.parametrize("bad", ["0", "-2", "wide"])
+    def test_plan_beam_rejects_bad_values(self, bad: str) -> None:
+        with pytest.raises(SystemExit):
+            build_parser().parse_args(["plan", "--beam", bad])
+
+    def test_precision(self) -> None:
+        """--precision N is global and defaults to two decimals."""
+        assert build_parser().parse_args(["plan"]).precision == 2
+        args = build_parser().parse_args(["--precision", "4", "plan"])
+        assert args.precision == 4
+
+    @pytest.mark.parametrize("bad", ["-1", "7", "two"])
+    def test_precision_rejects_bad_values(self, bad: str) -> None:
+        with pytest.raises(SystemExit):
+            build_parser().parse_args(["--precision", bad, "plan"])
+
+    def test_plan_tiebreak_seed(self) -> None:
+        """--tiebreak-seed N parses to an int; absent means name tie-breaks."""
+        assert build_parser().parse_args(["plan"]).tiebreak_seed is None
+        args = build_parser().parse_args(["plan", "--tiebreak-seed", "7"])
+        assert args.tiebreak_seed == 7
+
+    def test_plan_budget_sweep_includes_both_ends(self) -> None:
+        """--budget-sweep FROM:TO:STEP expands to the budget list."""
+        args = build_parser().parse_args(["plan", "--budget-sweep", "1000:2000:500"])
+        assert args.budget_sweep == [1000, 1500, 2000]
+
+    @pytest.mark.parametrize(
+        "bad", ["1000:2000", "0:1000:100", "2000:1000:100", "a:b:c"]
+    )
+    def test_plan_budget_sweep_rejects_bad_values(self, bad: str) -> None:
+        with pytest.raises(SystemExit):
+            build_parser().parse_args(["plan", "--budget-sweep", bad])
 
     def test_predict_subcommand_requires_food(self) -> None:
         """predict without --food fails."""
@@ -45,6 +162,11 @@ class TestBuildParser:
         args_vv = build_parser().parse_args(["-vv", "plan"])
         assert args_vv.verbose == 2
 
+    def test_quiet_flag(self) -> None:
+        """-q is global and off by default."""
+        assert build_parser().parse_args(["plan"]).quiet is False
+        assert build_parser().parse_args(["-q", "plan"]).quiet is True
+
     def test_config_flag(self) -> None:
         """--config path.yml captured."""
         args = build_parser().parse_args(["--config", "my_config.yml", "plan"])
@@ -54,3 +176,11 @@ class TestBuildParser:
         """No subcommand → cmd=None."""
         args = build_parser().parse_args([])
         assert args.cmd is None
+
+    def test_edit_positional_args(self) -> None:
+        """edit NAME FIELD VALUE parses value as int."""
+        args = build_parser().parse_args(["edit", "Bannock", "calories", "650"])
+        assert args.cmd == "edit"
+        assert args.name == "Bannock"
+        assert args.field == "calories"
+        assert args.value == 650
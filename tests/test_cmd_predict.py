@@ -110,3 +110,16 @@ class TestCmdPredict:
         output = capsys.readouterr().out
         assert "2.00x" in output  # server mult
         assert "1.50x" in output  # dinner party mult
+
+    def test_formula_uses_base_skill_points(self, capsys, monkeypatch) -> None:
+        """The formula shows the configured base SP, not a fixed 12."""
+        monkeypatch.setattr(
+            "main.load_food_state",
+            lambda **kwargs: _make_manager(),
+        )
+        monkeypatch.setattr("main.BASE_SKILL_POINTS", 15.5)
+        from main import cmd_predict
+
+        cmd_predict(_make_args(food="Bannock"))
+        output = capsys.readouterr().out
+        assert output.count("+ 15.5)") == 2
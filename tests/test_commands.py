@@ -0,0 +1,704 @@
+"""Tests for state-editing subcommands in ``main``."""
+
+import io
+import json
+from argparse import Namespace
+
+import pytest
+
+from conftest import make_food
+from constants import ZERO_NUTRIENT_TOLERANCE_SP
+from food_state_manager import FoodNotFoundError, FoodStateManager
+
+
+def _make_manager() -> FoodStateManager:
+    """Create a test manager with a couple of foods."""
+    return FoodStateManager(
+        [
+            make_food("Bannock", calories=600, carbs=12, protein=3, fat=8),
+            make_food("Crimson Salad", calories=1100, vitamins=22),
+        ]
+    )
+
+
+def _patch_state(monkeypatch, manager: FoodStateManager) -> list:
+    """Route load/save through *manager*; return the list of saved payloads."""
+    saved: list = []
+    monkeypatch.setattr("main.load_food_state", lambda **kwargs: manager)
+    monkeypatch.setattr(
+        "main.save_food_dict",
+        lambda food_list, path, **kwargs: saved.append(food_list),
+    )
+    return saved
+
+
+class TestCmdTopFoods:
+    """Tests for the top-foods subcommand."""
+
+    def test_prints_top_n(self, capsys, monkeypatch) -> None:
+        _patch_state(monkeypatch, _make_manager())
+        from main import cmd_top_foods
+
+        cmd_top_foods(Namespace(n=1))
+
+        lines = capsys.readouterr().out.splitlines()
+        assert len(lines) == 1
+        assert lines[0].startswith(" 1. ")
+        assert "SP/1000 cal" in lines[0]
+
+
+class TestCmdAnalyze:
+    """Tests for the analyze subcommand."""
+
+    def test_empty_stomach(self, capsys, monkeypatch) -> None:
+        _patch_state(monkeypatch, _make_manager())
+        from main import cmd_analyze
+
+        cmd_analyze(Namespace())
+
+        assert "nothing to analyze" in capsys.readouterr().out
+
+    def test_names_limiting_nutrient(self, capsys, monkeypatch) -> None:
+        manager = _make_manager()
+        manager.consume(manager.get_food("Bannock"))
+        _patch_state(monkeypatch, manager)
+        from main import cmd_analyze
+
+        cmd_analyze(Namespace())
+
+        out = capsys.readouterr().out
+        assert "Limiting nutrient: protein" in out
+        assert "Try: Crimson Salad" in out
+
+
+class TestCmdExplain:
+    """Tests for the explain subcommand."""
+
+    def test_prints_formula_and_current_sp(self, capsys, monkeypatch) -> None:
+        manager = _make_manager()
+        manager.consume(manager.get_food("Bannock"))
+        _patch_state(monkeypatch, manager)
+        from main import cmd_explain
+
+        cmd_explain(Namespace(satisfied=0, server_mult=1.0, dinner_party=1.0))
+
+        lines = capsys.readouterr().out.splitlines()
+        assert lines[0].startswith("SP = (nutrient_total")
+        assert lines[-1] == f"   = {manager.get_current_sp():.2f}"
+
+
+class TestCmdEdit:
+    """Tests for the edit subcommand."""
+
+    def test_edit_prints_before_after_and_saves(self, capsys, monkeypatch) -> None:
+        manager = _make_manager()
+        saved = _patch_state(monkeypatch, manager)
+        from main import cmd_edit
+
+        cmd_edit(Namespace(name="bannock", field="calories", value=650))
+
+        output = capsys.readouterr().out
+        assert "Before: Bannock | Cal: 600" in output
+        assert "After:  Bannock | Cal: 650" in output
+        assert saved and saved[0][0]["Calories"] == 650
+
+    def test_edit_rejects_invalid_without_saving(self, capsys, monkeypatch) -> None:
+        manager = _make_manager()
+        saved = _patch_state(monkeypatch, manager)
+        from main import cmd_edit
+
+        cmd_edit(Namespace(name="Bannock", field="tastiness", value=7))
+
+        assert "Error: Invalid tastiness" in capsys.readouterr().out
+        assert saved == []
+
+
+class TestCmdRate:
+    """Tests for the rate subcommand."""
+
+    def test_sets_rating_and_saves(self, capsys, monkeypatch) -> None:
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_rate
+
+        cmd_rate(Namespace(name="bannock", value=2))
+
+        assert "Bannock: ok -> delicious (2)" in capsys.readouterr().out
+        assert saved and saved[0][0]["Tastiness"] == 2
+
+    def test_prompts_when_value_omitted(self, capsys, monkeypatch) -> None:
+        saved = _patch_state(monkeypatch, _make_manager())
+        monkeypatch.setattr("main.prompt_for_tastiness", lambda name: -1)
+        from main import cmd_rate
+
+        cmd_rate(Namespace(name="Crimson Salad", value=None))
+
+        assert "Crimson Salad: ok -> bad (-1)" in capsys.readouterr().out
+        assert saved and saved[0][1]["Tastiness"] == -1
+
+    def test_rejects_invalid_rating(self, capsys, monkeypatch) -> None:
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_rate
+
+        cmd_rate(Namespace(name="Bannock", value=5))
+
+        assert "Error: Invalid tastiness" in capsys.readouterr().out
+        assert saved == []
+
+    def test_unknown_food(self, capsys, monkeypatch) -> None:
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_rate
+
+        cmd_rate(Namespace(name="Pizza", value=1))
+
+        assert "Error: Food 'Pizza' not found" in capsys.readouterr().out
+        assert saved == []
+
+
+class TestCmdHistory:
+    """Tests for the history subcommand."""
+
+    def test_prints_running_totals(self, tmp_path, capsys, monkeypatch) -> None:
+        from interface.persistence import append_history_entry
+
+        path = tmp_path / "history.jsonl"
+        for name, calories in (("Bannock", 600), ("Crimson Salad", 1100)):
+            append_history_entry(
+                path, make_food(name, calories), timestamp="2026-01-01T12:00:00"
+            )
+        monkeypatch.setattr("main.HISTORY_PATH", path)
+        from main import cmd_history
+
+        cmd_history(Namespace(limit=10))
+
+        output = capsys.readouterr().out
+        assert "(total 600)" in output
+        assert "(total 1700)" in output
+
+
+class TestCmdPlan:
+    """Tests for the plan subcommand's save behavior."""
+
+    @staticmethod
+    def _manager() -> FoodStateManager:
+        return FoodStateManager(
+            [
+                make_food("Bannock", 600, carbs=12, protein=3, fat=8, stomach=2),
+                make_food("Crimson Salad", 1100, vitamins=22),
+            ]
+        )
+
+    def _run(self, monkeypatch, **flags) -> list:
+        manager = self._manager()
+        saved = _patch_state(monkeypatch, manager)
+        monkeypatch.setattr("main.collect_user_constraints", lambda: ([], 0, 2500))
+        from main import cmd_plan
+
+        cmd_plan(Namespace(quiet=True, **flags))
+        return saved
+
+    def _side_plan_kwargs(self, monkeypatch, **flags) -> list[dict]:
+        """Plan keywords given to the budget sweep, then the knob comparison."""
+        calls: list[dict] = []
+        monkeypatch.setattr(
+            "main.sweep_budgets", lambda *args, **kwargs: calls.append(kwargs) or []
+        )
+        monkeypatch.setattr(
+            "main.compare_plans", lambda *args, **kwargs: calls.append(kwargs) or {}
+        )
+        monkeypatch.setattr("main.load_best_knobs", lambda path: {})
+        monkeypatch.setattr("main.display_budget_sweep", lambda *args, **kwargs: None)
+        monkeypatch.setattr(
+            "main.display_plan_comparison", lambda *args, **kwargs: None
+        )
+
+        self._run(monkeypatch, budget_sweep=[1000, 2000], **flags)
+        self._run(monkeypatch, compare="best.json", **flags)
+        return calls
+
+    def test_sweep_and_compare_keep_max_bites(self, monkeypatch) -> None:
+        calls = self._side_plan_kwargs(monkeypatch, max_bites=3)
+
+        assert [kwargs["max_bites"] for kwargs in calls] == [3, 3]
+
+    def test_sweep_and_compare_keep_avoid_zero_nutrient(self, monkeypatch) -> None:
+        calls = self._side_plan_kwargs(monkeypatch, avoid_zero_nutrient=True)
+
+        tolerances = [kwargs["zero_nutrient_tolerance"] for kwargs in calls]
+        assert tolerances == [ZERO_NUTRIENT_TOLERANCE_SP] * 2
+
+    def test_sweep_and_compare_keep_max_distinct_foods(self, monkeypatch) -> None:
+        calls = self._side_plan_kwargs(monkeypatch, max_distinct_foods=2)
+
+        assert [kwargs["max_distinct"] for kwargs in calls] == [2, 2]
+
+    def test_sweep_and_compare_keep_greedy_lookahead(self, monkeypatch) -> None:
+        calls = self._side_plan_kwargs(monkeypatch, greedy_lookahead=2)
+
+        assert [kwargs["lookahead"] for kwargs in calls] == [2, 2]
+
+    def test_sweep_and_compare_keep_history_penalties(self, monkeypatch) -> None:
+        monkeypatch.setattr(
+            "main.read_history", lambda path: [{"Name": "Bannock", "Calories": 600}]
+        )
+
+        calls = self._side_plan_kwargs(monkeypatch, history_aware=True)
+
+        penalties = [kwargs["recent_penalties"] for kwargs in calls]
+        assert penalties[0] == penalties[1]
+        assert set(penalties[0]) == {"bannock"}
+
+    def test_stdin_pantry_needs_import(self, capsys, monkeypatch) -> None:
+        from main import EXIT_INVALID_INPUT, cmd_plan
+
+        def no_prompts():
+            raise AssertionError("prompted for constraints")
+
+        monkeypatch.setattr("main.collect_user_constraints", no_prompts)
+
+        status = cmd_plan(Namespace(quiet=True, pantry_from="-"))
+
+        assert status == EXIT_INVALID_INPUT
+        assert "needs --import" in capsys.readouterr().out
+
+    def test_save_plan_dash_keeps_stdout_json(self, capsys, monkeypatch) -> None:
+        self._run(monkeypatch, save_plan="-", explain_variety=True)
+
+        captured = capsys.readouterr()
+        payload = json.loads(captured.out)
+        assert payload["items"]
+        assert "Variety progress" in captured.err
+
+    def test_preserve_stomach_keeps_baseline_counts(self, monkeypatch) -> None:
+        saved = self._run(monkeypatch, preserve_stomach=True)
+
+        stomach = {entry["Name"]: entry["Stomach"] for entry in saved[0]}
+        available = {entry["Name"]: entry["Available"] for entry in saved[0]}
+        assert stomach == {"Bannock": 2, "Crimson Salad": 0}
+        assert sum(available.values()) < 20
+
+    def test_default_saves_planned_bites(self, monkeypatch) -> None:
+        saved = self._run(monkeypatch)
+
+        stomach = {entry["Name"]: entry["Stomach"] for entry in saved[0]}
+        assert sum(stomach.values()) > 2
+
+    def test_targets_report_after_plan(self, capsys, monkeypatch) -> None:
+        self._run(monkeypatch, targets={"protein": 1, "vitamins": 100000})
+
+        out = capsys.readouterr().out
+        assert "Nutrient targets:" in out
+        assert "met" in out.split("Protein")[1].splitlines()[0]
+        assert "under by" in out.split("Vitamins")[1].splitlines()[0]
+
+    def test_explain_variety_after_plan(self, capsys, monkeypatch) -> None:
+        self._run(monkeypatch, explain_variety=True)
+
+        out = capsys.readouterr().out
+        assert "Variety progress" in out
+        assert "qualifies" in out
+        assert "kcal" not in out
+
+    def test_fail_on_iteration_cap(self, tmp_path, capsys, monkeypatch) -> None:
+        from main import EXIT_ERROR, cmd_plan
+
+        history = tmp_path / "history.jsonl"
+        monkeypatch.setattr("main.HISTORY_PATH", history)
+        for fail, expected in ((True, EXIT_ERROR), (False, None)):
+            manager = FoodStateManager([make_food("Crumb", 150, available=10_000)])
+            saved = _patch_state(monkeypatch, manager)
+            monkeypatch.setattr(
+                "main.collect_user_constraints", lambda: ([], 0, 1_500_000)
+            )
+
+            status = cmd_plan(
+                Namespace(quiet=True, log=True, fail_on_iteration_cap=fail)
+            )
+
+            out = capsys.readouterr().out
+            assert "iteration cap" in out
+            assert status == expected
+            assert bool(saved) is not fail
+            # A rejected plan leaves no history behind
+            assert history.exists() is not fail
+
+    def test_warns_when_budget_exceeds_stock(self, capsys, monkeypatch) -> None:
+        # Stock is 10 Bannock + 10 Crimson Salad = 17000 cal
+        from main import cmd_plan
+
+        for budget, warned in ((17000, False), (17001, True)):
+            _patch_state(monkeypatch, self._manager())
+            monkeypatch.setattr(
+                "main.collect_user_constraints", lambda b=budget: ([], 0, b)
+            )
+            cmd_plan(Namespace(quiet=True))
+
+            out = capsys.readouterr().out
+            assert ("stock-limited" in out) is warned
+        assert "Only 17000 cal in stock for a 17001 cal budget" in out
+
+    def test_pantry_from_overlays_availability_only(
+        self, tmp_path, capsys, monkeypatch
+    ) -> None:
+        pantry = tmp_path / "pantry.json"
+        pantry.write_text(
+            json.dumps(
+                [
+                    make_food("bannock", 50, carbs=1, available=3).to_dict(),
+                    make_food("Crimson Salad", 50, available=1).to_dict(),
+                    make_food("Elk Wellington", 1400, available=2).to_dict(),
+                ]
+            ),
+            encoding="utf-8",
+        )
+
+        saved = self._run(monkeypatch, pantry_from=str(pantry))
+
+        assert "Ignoring pantry entry 'Elk Wellington'" in capsys.readouterr().out
+        by_name = {entry["Name"]: entry for entry in saved[0]}
+        assert set(by_name) == {"Bannock", "Crimson Salad"}
+        # Nutrients from the main catalog; stock (eaten + left) from the pantry
+        assert by_name["Bannock"]["Calories"] == 600
+        assert by_name["Bannock"]["Carbs"] == 12
+        assert by_name["Crimson Salad"]["Vitamins"] == 22
+        stock = {
+            name: entry["Available"] + entry["Stomach"]
+            for name, entry in by_name.items()
+        }
+        assert stock == {"Bannock": 3 + 2, "Crimson Salad": 1}
+
+
+class TestCmdRestock:
+    """Tests for the restock subcommand."""
+
+    def test_restock_one_food(self, capsys, monkeypatch) -> None:
+        manager = _make_manager()
+        saved = _patch_state(monkeypatch, manager)
+        from main import cmd_restock
+
+        cmd_restock(Namespace(amount=3, food="bannock", all=False))
+
+        assert "Restocked Bannock to 3." in capsys.readouterr().out
+        by_name = {entry["Name"]: entry["Available"] for entry in saved[0]}
+        assert by_name == {"Bannock": 3, "Crimson Salad": 10}
+
+    def test_restock_unknown_food(self, capsys, monkeypatch) -> None:
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_restock
+
+        cmd_restock(Namespace(amount=3, food="Pizza", all=False))
+
+        assert "Error: Food 'Pizza' not found" in capsys.readouterr().out
+        assert saved == []
+
+
+class TestCmdMerge:
+    """Tests for the merge subcommand."""
+
+    def test_merge_adds_and_sums(self, tmp_path, capsys, monkeypatch) -> None:
+        other = tmp_path / "shared.json"
+        other.write_text(
+            json.dumps(
+                [
+                    make_food("bannock", 600, available=4).to_dict(),
+                    make_food("Elk Wellington", 1400, available=2).to_dict(),
+                ]
+            ),
+            encoding="utf-8",
+        )
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_merge
+
+        cmd_merge(Namespace(other_file=str(other), strategy="sum"))
+
+        assert "1 added, 1 updated" in capsys.readouterr().out
+        by_name = {entry["Name"]: entry["Available"] for entry in saved[0]}
+        assert by_name == {"Bannock": 14, "Crimson Salad": 10, "Elk Wellington": 2}
+
+    def test_merge_csv_with_header_map(self, tmp_path, capsys, monkeypatch) -> None:
+        other = tmp_path / "shared.csv"
+        other.write_text(
+            "Food,Energy,Carbs,Prot,Fat,Vit,Stock\nElk Wellington,1400,10,20,15,5,2\n",
+            encoding="utf-8",
+        )
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_merge
+
+        cmd_merge(
+            Namespace(
+                other_file=str(other),
+                strategy="keep",
+                header_map=["Energy=Calories"],
+            )
+        )
+
+        assert "1 added, 0 updated" in capsys.readouterr().out
+        by_name = {entry["Name"]: entry for entry in saved[0]}
+        assert by_name["Elk Wellington"]["Calories"] == 1400
+        assert by_name["Elk Wellington"]["Available"] == 2
+
+    def test_reseed_availability_keeps_stomach(
+        self, tmp_path, capsys, monkeypatch
+    ) -> None:
+        weekly = tmp_path / "weekly.json"
+        weekly.write_text(
+            json.dumps([make_food("Bannock", 600, stomach=9, available=6).to_dict()]),
+            encoding="utf-8",
+        )
+        manager = _make_manager()
+        manager.consume(manager.get_food("Bannock"))
+        saved = _patch_state(monkeypatch, manager)
+        from main import cmd_merge
+
+        cmd_merge(
+            Namespace(
+                other_file=str(weekly), strategy="keep", reseed_availability=True
+            )
+        )
+
+        assert "0 added, 1 updated" in capsys.readouterr().out
+        by_name = {entry["Name"]: entry for entry in saved[0]}
+        assert by_name["Bannock"]["Stomach"] == 1
+        assert by_name["Bannock"]["Available"] == 6
+        assert by_name["Crimson Salad"]["Available"] == 0
+
+    def test_merge_from_stdin(self, capsys, monkeypatch) -> None:
+        incoming = [make_food("Elk Wellington", 1400, available=2).to_dict()]
+        monkeypatch.setattr("sys.stdin", io.StringIO(json.dumps(incoming)))
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_merge
+
+        cmd_merge(Namespace(other_file="-", strategy="keep"))
+
+        assert "1 added, 0 updated" in capsys.readouterr().out
+        assert "Elk Wellington" in {entry["Name"] for entry in saved[0]}
+
+    def test_missing_file_is_an_error(self, tmp_path, capsys, monkeypatch) -> None:
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_merge
+
+        cmd_merge(Namespace(other_file=str(tmp_path / "nope.json"), strategy="keep"))
+
+        assert capsys.readouterr().out.startswith("Error:")
+        assert saved == []
+
+
+class TestCmdDiff:
+    """Tests for the diff subcommand."""
+
+    def test_reports_each_kind_of_change(self, tmp_path, capsys, monkeypatch) -> None:
+        other = tmp_path / "shared.json"
+        other.write_text(
+            json.dumps(
+                [
+                    make_food("Bannock", 650, carbs=12, protein=3, fat=8).to_dict(),
+                    make_food("Elk Wellington", 1400).to_dict(),
+                ]
+            ),
+            encoding="utf-8",
+        )
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_diff
+
+        cmd_diff(Namespace(other_file=str(other)))
+
+        lines = capsys.readouterr().out.splitlines()
+        assert lines == [
+            "+ Elk Wellington",
+            "- Crimson Salad",
+            "~ Bannock: calories 600 -> 650",
+            "1 added, 1 removed, 1 changed.",
+        ]
+        assert saved == []
+
+
+class TestCmdValidate:
+    """Tests for the validate subcommand."""
+
+    def _write_state(self, tmp_path, monkeypatch, entries: list) -> object:
+        path = tmp_path / "food_state.json"
+        path.write_text(json.dumps(entries), encoding="utf-8")
+        monkeypatch.setattr("main.DATA_PATH", path)
+        return path
+
+    def test_invalid_food_reported_with_nonzero_exit(
+        self, tmp_path, capsys, monkeypatch
+    ) -> None:
+        bad = make_food("Bannock", calories=600).to_dict()
+        bad["Protein"] = -2
+        self._write_state(
+            tmp_path, monkeypatch, [make_food("Salad", 400).to_dict(), bad]
+        )
+        from main import cmd_validate
+
+        with pytest.raises(SystemExit):
+            cmd_validate(Namespace(fix=False))
+
+        output = capsys.readouterr().out
+        assert "Bannock:" in output
+        assert "Protein is negative (-2)" in output
+        assert "Salad" not in output
+
+    def test_fix_saves_clamped_values(self, tmp_path, capsys, monkeypatch) -> None:
+        bad = make_food("Bannock", calories=600).to_dict()
+        bad["Protein"] = -2
+        path = self._write_state(tmp_path, monkeypatch, [bad])
+        from main import cmd_validate
+
+        cmd_validate(Namespace(fix=True))
+
+        assert "Fixed 1 of 1 foods." in capsys.readouterr().out
+        saved = json.loads(path.read_text(encoding="utf-8"))
+        assert saved[0]["Protein"] == 0
+
+    def test_malformed_entries_reported_together(
+        self, tmp_path, capsys, monkeypatch
+    ) -> None:
+        bad = make_food("Bannock", calories=600).to_dict()
+        bad["Calories"] = "lots"
+        self._write_state(tmp_path, monkeypatch, [bad, 42])
+        from main import cmd_validate
+
+        with pytest.raises(SystemExit):
+            cmd_validate(Namespace(fix=True))
+
+        output = capsys.readouterr().out
+        assert "Bannock: Calories must be a number (got string)" in output
+        assert "<entry 1>: expected an object but found number" in output
+        assert "Not saved" in output
+
+    def test_all_valid(self, tmp_path, capsys, monkeypatch) -> None:
+        self._write_state(tmp_path, monkeypatch, [make_food("Salad", 400).to_dict()])
+        from main import cmd_validate
+
+        cmd_validate(Namespace(fix=False))
+
+        assert "All 1 foods are valid." in capsys.readouterr().out
+
+
+class TestCmdSelfTest:
+    """Tests for the self-test subcommand."""
+
+    def test_passes_for_deterministic_planner(self, capsys, monkeypatch) -> None:
+        saved = _patch_state(monkeypatch, _make_manager())
+        from main import cmd_self_test
+
+        assert cmd_self_test(Namespace()) is None
+
+        assert capsys.readouterr().out.startswith("Self-test passed:")
+        assert saved == []
+
+    def test_fails_clearly_when_plans_differ(self, capsys, monkeypatch) -> None:
+        _patch_state(monkeypatch, _make_manager())
+        from planner import plan_from_state
+
+        calls: list[int] = []
+
+        def flaky_plan(manager, *args, **kwargs):
+            # Second run eats the same bites in another order
+            calls.append(1)
+            plan, state = plan_from_state(manager, *args, **kwargs)
+            return (plan if len(calls) == 1 else plan[::-1]), state
+
+        monkeypatch.setattr("main.plan_from_state", flaky_plan)
+        from main import EXIT_ERROR, cmd_self_test
+
+        assert cmd_self_test(Namespace()) == EXIT_ERROR
+
+        output = capsys.readouterr().out
+        assert "Self-test FAILED" in output
+        assert "First difference at bite 1" in output
+
+
+class TestExitCodes:
+    """Tests for mapping command failures to process exit statuses."""
+
+    @pytest.mark.parametrize(
+        ("exc", "expected"),
+        [
+            (FoodNotFoundError("Pizza"), 2),
+            (FileNotFoundError("food_state.json"), 3),
+            (ValueError("bad rating"), 4),
+            (RuntimeError("boom"), 1),
+        ],
+    )
+    def test_each_error_kind_has_its_own_code(self, exc, expected) -> None:
+        from main import exit_code_for
+
+        assert exit_code_for(exc) == expected
+
+    def test_unknown_food_returns_not_found_status(self, monkeypatch) -> None:
+        _patch_state(monkeypatch, _make_manager())
+        from main import EXIT_FOOD_NOT_FOUND, cmd_rate
+
+        assert cmd_rate(Namespace(name="Pizza", value=1)) == EXIT_FOOD_NOT_FOUND
+
+    def test_main_exits_with_command_status(self, tmp_path, monkeypatch) -> None:
+        missing = tmp_path / "nope.json"
+        monkeypatch.setattr("sys.argv", ["main.py", "diff", str(missing)])
+        from main import EXIT_IO, main
+
+        with pytest.raises(SystemExit) as excinfo:
+            main()
+
+        assert excinfo.value.code == EXIT_IO
+
+    def test_main_reports_raised_errors_on_stderr(
+        self, tmp_path, capsys, monkeypatch
+    ) -> None:
+        monkeypatch.setattr("main.DATA_PATH", tmp_path / "missing.json")
+        monkeypatch.setattr("sys.argv", ["main.py", "validate"])
+        from main import EXIT_IO, main
+
+        with pytest.raises(SystemExit) as excinfo:
+            main()
+
+        assert excinfo.value.code == EXIT_IO
+        assert capsys.readouterr().err.startswith("Error:")
+
+
+class TestStateFileOption:
+    """Tests for the global --file option."""
+
+    @staticmethod
+    def _state_json() -> str:
+        return json.dumps([make_food("Bannock", 600).to_dict()])
+
+    def test_file_reads_and_saves_named_path(self, tmp_path, monkeypatch) -> None:
+        state = tmp_path / "other_state.json"
+        state.write_text(self._state_json(), encoding="utf-8")
+        monkeypatch.setattr("main.DATA_PATH", tmp_path / "food_state.json")
+        monkeypatch.setattr(
+            "sys.argv", ["main.py", "--file", str(state), "restock", "7", "--all"]
+        )
+        from main import main
+
+        main()
+
+        assert json.loads(state.read_text(encoding="utf-8"))[0]["Available"] == 7
+        assert not (tmp_path / "food_state.json").exists()
+
+    def test_file_dash_keeps_stdout_json(self, capsys, monkeypatch) -> None:
+        monkeypatch.setattr("sys.stdin", io.StringIO(self._state_json()))
+        monkeypatch.setattr(
+            "sys.argv", ["main.py", "--file", "-", "restock", "4", "--food", "Bannock"]
+        )
+        from main import main
+
+        main()
+
+        captured = capsys.readouterr()
+        assert json.loads(captured.out)[0]["Available"] == 4
+        assert "Restocked Bannock to 4." in captured.err
+
+    def test_file_dash_rejects_prompting_commands(self, capsys, monkeypatch) -> None:
+        monkeypatch.setattr("sys.stdin", io.StringIO(self._state_json()))
+        monkeypatch.setattr("sys.argv", ["main.py", "--file", "-", "rate-unknowns"])
+        from main import EXIT_INVALID_INPUT, main
+
+        with pytest.raises(SystemExit) as excinfo:
+            main()
+
+        captured = capsys.readouterr()
+        assert excinfo.value.code == EXIT_INVALID_INPUT
+        assert captured.out == ""
+        assert "rate-unknowns" in captured.err
@@ -63,6 +63,49 @@ class TestLoadConfig:
         with pytest.raises(ValueError, match="craving_satisfied_frac"):
             load_config(config_file)
 
+    def test_non_positive_half_life_raises(self, tmp_path) -> None:
+        """variety_half_life must be positive."""
+        config_file = tmp_path / "bad_half_life.yml"
+        config_file.write_text(yaml.dump({"algorithm": {"variety_half_life": 0}}))
+        with pytest.raises(ValueError, match="variety_half_life"):
+            load_config(config_file)
+
+    def test_zero_history_lookback_raises(self, tmp_path) -> None:
+        """history_lookback must cover at least one bite."""
+        config_file = tmp_path / "bad_lookback.yml"
+        config_file.write_text(yaml.dump({"algorithm": {"history_lookback": 0}}))
+        with pytest.raises(ValueError, match="history_lookback"):
+            load_config(config_file)
+
+    def test_negative_min_calorie_floor_raises(self, tmp_path) -> None:
+        """min_calorie_floor must keep zero-calorie foods out of plans."""
+        config_file = tmp_path / "bad_floor.yml"
+        config_file.write_text(yaml.dump({"algorithm": {"min_calorie_floor": -1}}))
+        with pytest.raises(ValueError, match="min_calorie_floor"):
+            load_config(config_file)
+
+    def test_unknown_dinner_party_mode_raises(self, tmp_path) -> None:
+        """dinner_party_mode must be fixed or variety."""
+        config_file = tmp_path / "bad_mode.yml"
+        config_file.write_text(
+            yaml.dump({"game_rules": {"dinner_party_mode": "guests"}})
+        )
+        with pytest.raises(ValueError, match="dinner_party_mode"):
+            load_config(config_file)
+
+    def test_unknown_balance_metric_raises(self, tmp_path) -> None:
+        """balance_metric must be minmax or cv."""
+        config_file = tmp_path / "bad_metric.yml"
+        config_file.write_text(yaml.dump({"game_rules": {"balance_metric": "mean"}}))
+        with pytest.raises(ValueError, match="balance_metric"):
+            load_config(config_file)
+
+    def test_fractional_base_skill_points(self, tmp_path) -> None:
+        """Servers with a non-integer base SP load as-is."""
+        config_file = tmp_path / "base.yml"
+        config_file.write_text(yaml.dump({"safety": {"base_skill_points": 10.5}}))
+        assert load_config(config_file).safety.base_skill_points == 10.5
+
     def test_partial_config_merges_with_defaults(self, tmp_path) -> None:
         """YAML with only algorithm section; rest uses defaults."""
         partial = {"algorithm": {"soft_variety_bias_strength": 5.0}}
@@ -110,6 +153,49 @@ class TestLoadConfig:
             == default.game_rules.craving_satisfied_frac
         )
 
+    def test_nutrient_weights_partial_and_validated(self, tmp_path) -> None:
+        """Unset nutrient weights default to 1.0; negatives are rejected."""
+        config_file = tmp_path / "weights.yml"
+        config_file.write_text(yaml.dump({"nutrient_weights": {"vitamins": 2.0}}))
+
+        config = load_config(config_file)
+        assert config.nutrient_weights.vitamins == 2.0
+        assert config.nutrient_weights.carbs == 1.0
+
+        config_file.write_text(yaml.dump({"nutrient_weights": {"fat": -1.0}}))
+        with pytest.raises(ValueError, match="nutrient_weights.fat"):
+            load_config(config_file)
+
+    def test_taste_scale_defaults_match_linear_map(self) -> None:
+        """Default taste scale is the -0.30..+0.30 map with 99 neutral."""
+        multipliers = Config().taste_scale.as_multipliers()
+        assert multipliers[-3] == -0.30
+        assert multipliers[-2] == -0.20
+        assert multipliers[0] == 0.0
+        assert multipliers[2] == 0.20
+        assert multipliers[3] == 0.30
+        assert multipliers[99] == 0.0
+
+    def test_taste_scale_explicit_map_and_validation(self, tmp_path) -> None:
+        """An explicit map overrides min/max; partial maps are rejected."""
+        explicit = {-3: -0.5, -2: -0.3, -1: -0.1, 0: 0.0, 1: 0.1, 2: 0.3, 3: 0.5}
+        config_file = tmp_path / "taste.yml"
+        config_file.write_text(yaml.dump({"taste_scale": {"multipliers": explicit}}))
+
+        multipliers = load_config(config_file).taste_scale.as_multipliers()
+        assert multipliers[3] == 0.5
+        assert multipliers[99] == 0.0
+
+        config_file.write_text(
+            yaml.dump({"taste_scale": {"multipliers": {3: 0.5}}})
+        )
+        with pytest.raises(ValueError, match="taste_scale.multipliers"):
+            load_config(config_file)
+
+        config_file.write_text(yaml.dump({"taste_scale": {"max_mult": -0.1}}))
+        with pytest.raises(ValueError, match="taste_scale.max_mult"):
+            load_config(config_file)
+
     def test_set_config_path_overrides_default(self, tmp_path) -> None:
         """set_config_path() affects subsequent load_config() calls."""
         custom = {"algorithm": {"tastiness_weight": 2.5}}
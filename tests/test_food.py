@@ -0,0 +1,92 @@
+"""Tests for the Food record."""
+
+import pytest
+
+from conftest import make_food
+from models.food import FoodBuilder
+
+
+class TestScale:
+    """Tests for Food.scale()."""
+
+    def test_double_serving_doubles_calories_and_nutrients(self) -> None:
+        food = make_food(
+            "Bannock", 600, carbs=12, protein=3, fat=8, vitamins=0, tastiness=2
+        )
+
+        doubled = food.scale(2.0)
+
+        assert doubled.calories == 1200
+        assert (doubled.carbs, doubled.protein, doubled.fat, doubled.vitamins) == (
+            24,
+            6,
+            16,
+            0,
+        )
+        assert doubled.name == "Bannock"
+        assert doubled.tastiness == 2
+        assert food.calories == 600  # original untouched
+
+    @pytest.mark.parametrize("factor", [0.25, 1.0, 3.5])
+    def test_positive_factor_stays_valid(self, factor: float) -> None:
+        assert make_food("Bannock", 600).scale(factor).is_valid()
+
+    @pytest.mark.parametrize("factor", [0.0, -1.0])
+    def test_non_positive_factor_raises(self, factor: float) -> None:
+        with pytest.raises(ValueError):
+            make_food("Bannock", 600).scale(factor)
+
+
+class TestPerCalorie:
+    """Tests for the per-macro efficiency helpers."""
+
+    def test_each_macro_divided_by_calories(self) -> None:
+        apple = make_food("Apple", 80, carbs=4, protein=1, fat=0, vitamins=2)
+
+        assert apple.carbs_per_calorie() == 4 / 80
+        assert apple.protein_per_calorie() == 1 / 80
+        assert apple.fat_per_calorie() == 0.0
+        assert apple.vitamins_per_calorie() == 2 / 80
+
+    def test_zero_calories_gives_zero(self) -> None:
+        water = make_food("Water", 0, carbs=1, protein=1, fat=1, vitamins=1)
+
+        assert water.carbs_per_calorie() == 0.0
+        assert water.protein_per_calorie() == 0.0
+        assert water.fat_per_calorie() == 0.0
+        assert water.vitamins_per_calorie() == 0.0
+
+
+class TestFoodBuilder:
+    """Tests for FoodBuilder."""
+
+    def test_build_sets_fields_and_defaults(self) -> None:
+        food = (
+            FoodBuilder("Bannock")
+            .calories(600)
+            .carbs(12)
+            .protein(3)
+            .fat(8)
+            .available(5)
+            .build()
+        )
+
+        assert food == make_food("Bannock", 600)
+        assert (food.carbs, food.protein, food.fat, food.vitamins) == (12, 3, 8, 0)
+        assert food.tastiness == 99
+        assert (food.stomach, food.available) == (0, 5)
+        assert food.is_valid()
+
+    def test_build_reports_every_violation(self) -> None:
+        builder = FoodBuilder("Bannock").calories(-600).tastiness(7)
+
+        with pytest.raises(ValueError) as excinfo:
+            builder.build()
+
+        message = str(excinfo.value)
+        assert "Calories is negative" in message
+        assert "Tastiness 7" in message
+
+    def test_blank_name_rejected(self) -> None:
+        with pytest.raises(ValueError, match="blank"):
+            FoodBuilder("  ").calories(100).build()
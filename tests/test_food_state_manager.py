@@ -8,10 +8,19 @@ from __future__ import annotations
 
 from unittest.mock import patch
 
+import pytest
 
 from conftest import make_food
-from food_state_manager import FoodStateManager
-from models.food import Food
+from food_state_manager import (
+    FoodNotFoundError,
+    FoodStateManager,
+    MergeStrategy,
+    StateSnapshot,
+    diff_catalogs,
+    normalize_nutrients,
+    nutrient_scale_factors,
+)
+from models.food import Food, NUTRIENT_FIELDS
 
 # ---------------------------------------------------------------------------
 # Helpers
@@ -99,6 +108,113 @@ class TestConsume:
         assert mgr.available[food] == 7
 
 
+# ---------------------------------------------------------------------------
+# TestSnapshotRestore
+# ---------------------------------------------------------------------------
+
+
+class TestSnapshotRestore:
+    """Rolling counts back after a what-if run."""
+
+    def test_consume_then_restore_yields_original_counts(self) -> None:
+        bannock = make_food("Bannock", 600, stomach=2, available=5)
+        salad = make_food("Salad", 1100, available=1)
+        stew = make_food("Stew", 800, available=0)
+        mgr = _make_manager(bannock, salad, stew)
+        stomach_before = dict(mgr.stomach)
+        available_before = dict(mgr.available)
+
+        snap = mgr.snapshot()
+        mgr.consume(bannock)
+        mgr.consume(salad)
+        mgr.consume_n("Bannock", 3)
+        mgr.restore(snap)
+
+        assert mgr.stomach == stomach_before
+        assert mgr.available == available_before
+        assert [(f.stomach, f.available) for f in mgr.foods.values()] == [
+            (2, 5),
+            (0, 1),
+            (0, 0),
+        ]
+
+    def test_snapshot_is_unaffected_by_later_consumption(self) -> None:
+        food = make_food("Bannock", 600, available=3)
+        mgr = _make_manager(food)
+
+        snap = mgr.snapshot()
+        mgr.consume(food)
+
+        assert snap == StateSnapshot(stomach={}, available={"bannock": 3})
+
+    def test_restore_does_not_call_hook(self) -> None:
+        food = make_food("Bannock", 600, available=3)
+        mgr = _make_manager(food)
+        snap = mgr.snapshot()
+        mgr.consume(food)
+        eaten: list[str] = []
+        mgr.on_consume = lambda f: eaten.append(f.name)
+
+        mgr.restore(snap)
+
+        assert eaten == []
+
+    def test_restore_rejects_foreign_snapshot(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600, available=3))
+        other = _make_manager(make_food("Salad", 1100, available=1))
+
+        with pytest.raises(ValueError, match="Salad|salad"):
+            mgr.restore(other.snapshot())
+
+
+# ---------------------------------------------------------------------------
+# TestConsumeN
+# ---------------------------------------------------------------------------
+
+
+class TestConsumeN:
+    """Bulk consumption of one food."""
+
+    def test_consumes_requested_amount(self) -> None:
+        food = make_food("Bannock", 100, stomach=1, available=30)
+        mgr = _make_manager(food)
+
+        assert mgr.consume_n("bannock", 20) == 20
+
+        assert mgr.stomach[food] == 21
+        assert mgr.available[food] == 10
+        assert food.stomach == 21 and food.available == 10
+
+    def test_partial_when_availability_short(self) -> None:
+        food = make_food("Bannock", 100, available=3)
+        mgr = _make_manager(food)
+        eaten: list[Food] = []
+        mgr.on_consume = eaten.append
+
+        assert mgr.consume_n("Bannock", 5) == 3
+
+        assert mgr.stomach[food] == 3
+        assert mgr.available[food] == 0
+        assert eaten == [food] * 3
+
+    def test_out_of_stock_consumes_nothing(self) -> None:
+        food = make_food("Bannock", 100, available=0)
+        mgr = _make_manager(food)
+
+        assert mgr.consume_n("Bannock", 4) == 0
+        assert mgr.stomach.get(food, 0) == 0
+
+    def test_unknown_food_raises(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 100))
+        with pytest.raises(FoodNotFoundError):
+            mgr.consume_n("Pizza", 1)
+
+    def test_negative_amount_raises(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 100, available=5))
+        with pytest.raises(ValueError):
+            mgr.consume_n("Bannock", -1)
+
+
 # ---------------------------------------------------------------------------
 # TestCanConsume
 # ---------------------------------------------------------------------------
@@ -159,6 +275,35 @@ class TestUniqueVarietyFoods:
         assert "bannock" in mgr.unique_variety_foods()
 
 
+# ---------------------------------------------------------------------------
+# TestStomachOrdered
+# ---------------------------------------------------------------------------
+
+
+class TestStomachOrdered:
+    """Stomach contents sorted by name, independent of eating order."""
+
+    def test_alphabetical_with_counts(self) -> None:
+        salad = make_food("crimson Salad", 1100, available=5)
+        bannock = make_food("Bannock", 600, available=5)
+        elk = make_food("Elk Wellington", 1400, available=5)
+        mgr = _make_manager(elk, salad, bannock)
+        mgr.consume(elk)
+        mgr.consume_n("Bannock", 3)
+        mgr.consume(salad)
+
+        assert [(food.name, n) for food, n in mgr.stomach_ordered()] == [
+            ("Bannock", 3),
+            ("crimson Salad", 1),
+            ("Elk Wellington", 1),
+        ]
+
+    def test_empty_stomach(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600, available=5))
+
+        assert mgr.stomach_ordered() == []
+
+
 # ---------------------------------------------------------------------------
 # TestAllAvailable
 # ---------------------------------------------------------------------------
@@ -183,6 +328,30 @@ class TestAllAvailable:
         assert mgr.all_available() == []
 
 
+# ---------------------------------------------------------------------------
+# TestAvailableCalories
+# ---------------------------------------------------------------------------
+
+
+class TestAvailableCalories:
+    """Total calories in stock."""
+
+    def test_sums_calories_times_available(self) -> None:
+        mgr = _make_manager(
+            make_food("Bannock", 600, available=3),
+            make_food("Crimson Salad", 1100, available=2),
+            make_food("Stale Bread", 200, available=0),
+        )
+
+        assert mgr.available_calories() == 4000.0
+
+    def test_tracks_consumption(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600, available=3))
+        mgr.consume(mgr.get_food("Bannock"))
+
+        assert mgr.available_calories() == 1200.0
+
+
 # ---------------------------------------------------------------------------
 # TestResetStomach
 # ---------------------------------------------------------------------------
@@ -355,3 +524,304 @@ class TestGetCurrentSp:
         call_kwargs = mock_sp.call_args
         assert call_kwargs.kwargs["server_mult"] == 1.0
         assert call_kwargs.kwargs["dinner_party_mult"] == 1.0
+
+
+# ---------------------------------------------------------------------------
+# TestEditFood
+# ---------------------------------------------------------------------------
+
+
+class TestEditFood:
+    """Single-field edits with validation."""
+
+    def test_edit_nutrient(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600, protein=3))
+
+        food = mgr.edit_food("bannock", "protein", 7)
+
+        assert food.protein == 7
+        assert mgr.get_food("Bannock").protein == 7  # type: ignore[union-attr]
+
+    def test_edit_available_syncs_map(self) -> None:
+        food = make_food("Bannock", 600, available=0)
+        mgr = _make_manager(food)
+
+        mgr.edit_food("Bannock", "available", 4)
+        assert mgr.available[food] == 4
+
+        mgr.edit_food("Bannock", "available", 0)
+        assert food not in mgr.available
+
+    def test_unknown_food_raises(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600))
+        with pytest.raises(FoodNotFoundError):
+            mgr.edit_food("Pizza", "calories", 100)
+
+    def test_unknown_field_raises(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600))
+        with pytest.raises(ValueError, match="Unknown field"):
+            mgr.edit_food("Bannock", "name", 1)
+
+    def test_out_of_range_tastiness_raises(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600))
+        with pytest.raises(ValueError, match="tastiness"):
+            mgr.edit_food("Bannock", "tastiness", 5)
+
+    def test_negative_value_raises(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600))
+        with pytest.raises(ValueError, match="negative"):
+            mgr.edit_food("Bannock", "calories", -1)
+
+
+# ---------------------------------------------------------------------------
+# TestApplyRatings
+# ---------------------------------------------------------------------------
+
+
+class TestApplyRatings:
+    """Batch tastiness updates from (name, rating) pairs."""
+
+    def test_sets_ratings_and_reports_changes(self) -> None:
+        mgr = _make_manager(
+            make_food("Bannock", 600, tastiness=1),
+            make_food("Crimson Salad", 1100, tastiness=99),
+        )
+
+        changed = mgr.apply_ratings([("bannock", 1), ("crimson salad", -2)])
+
+        assert changed == ["Crimson Salad"]
+        assert mgr.get_food("Bannock").tastiness == 1  # type: ignore[union-attr]
+        assert mgr.get_food("Crimson Salad").tastiness == -2  # type: ignore[union-attr]
+
+    def test_bad_entry_changes_nothing(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600, tastiness=0))
+
+        with pytest.raises(FoodNotFoundError):
+            mgr.apply_ratings([("Bannock", 3), ("Pizza", 1)])
+        with pytest.raises(ValueError, match="tastiness"):
+            mgr.apply_ratings([("Bannock", 3), ("Bannock", 7)])
+
+        assert mgr.get_food("Bannock").tastiness == 0  # type: ignore[union-attr]
+
+
+# ---------------------------------------------------------------------------
+# TestReplaceFood
+# ---------------------------------------------------------------------------
+
+
+class TestReplaceFood:
+    """Swapping a food record while keeping its counts."""
+
+    def test_counts_move_to_new_record(self) -> None:
+        original = make_food("Bannock", 600, stomach=2, available=3)
+        mgr = _make_manager(original)
+
+        returned = mgr.replace_food(original.scale(2.0))
+
+        scaled = mgr.get_food("bannock")
+        assert returned is original
+        assert scaled is not original and scaled.calories == 1200
+        assert mgr.stomach[scaled] == 2 and mgr.available[scaled] == 3
+        assert next(iter(mgr.stomach)) is scaled
+
+    def test_unknown_food_raises(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600))
+        with pytest.raises(FoodNotFoundError):
+            mgr.replace_food(make_food("Pizza", 100))
+
+
+# ---------------------------------------------------------------------------
+# TestSetAvailability
+# ---------------------------------------------------------------------------
+
+
+class TestSetAvailability:
+    """Per-food availability changes."""
+
+    def test_sets_one_food_only(self) -> None:
+        bannock = make_food("Bannock", 600, available=2)
+        salad = make_food("Crimson Salad", 1100, available=3)
+        mgr = _make_manager(bannock, salad)
+
+        mgr.set_availability("BANNOCK", 9)
+
+        assert bannock.available == 9
+        assert mgr.available[bannock] == 9
+        assert salad.available == 3
+        assert mgr.available[salad] == 3
+
+    def test_zero_removes_from_available_map(self) -> None:
+        bannock = make_food("Bannock", 600, available=2)
+        mgr = _make_manager(bannock)
+
+        mgr.set_availability("Bannock", 0)
+
+        assert bannock not in mgr.all_available()
+
+    def test_unknown_food_raises(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600))
+        with pytest.raises(FoodNotFoundError):
+            mgr.set_availability("Pizza", 1)
+
+    def test_negative_raises(self) -> None:
+        mgr = _make_manager(make_food("Bannock", 600))
+        with pytest.raises(ValueError):
+            mgr.set_availability("Bannock", -1)
+
+
+# ---------------------------------------------------------------------------
+# TestMerge
+# ---------------------------------------------------------------------------
+
+
+class TestMerge:
+    """Tests for merge() with each strategy over overlapping catalogs."""
+
+    def _catalogs(self) -> tuple[FoodStateManager, list[Food]]:
+        mine = _make_manager(
+            make_food("Bannock", 600, stomach=2, available=3),
+            make_food("Crimson Salad", 1100, available=1),
+        )
+        theirs = [
+            make_food("BANNOCK", 650, stomach=7, available=4),
+            make_food("Elk Wellington", 1400, stomach=5, available=2),
+        ]
+        return mine, theirs
+
+    def test_keep_existing(self) -> None:
+        mgr, theirs = self._catalogs()
+
+        assert mgr.merge(theirs, MergeStrategy.KEEP_EXISTING) == (1, 0)
+
+        bannock = mgr.get_food("bannock")
+        assert (bannock.calories, bannock.available) == (600, 3)
+        elk = mgr.get_food("elk wellington")
+        assert elk.stomach == 0
+        assert mgr.available[elk] == 2
+
+    def test_overwrite(self) -> None:
+        mgr, theirs = self._catalogs()
+
+        assert mgr.merge(theirs, MergeStrategy.OVERWRITE) == (1, 1)
+
+        bannock = mgr.get_food("bannock")
+        assert (bannock.calories, bannock.available) == (650, 4)
+        assert bannock.stomach == 2  # stomach is never taken from the other file
+        assert mgr.available[bannock] == 4
+        assert len(mgr.foods) == 3
+
+    def test_sum_available(self) -> None:
+        mgr, theirs = self._catalogs()
+
+        assert mgr.merge(theirs, MergeStrategy.SUM_AVAILABLE) == (1, 1)
+
+        bannock = mgr.get_food("bannock")
+        assert (bannock.calories, bannock.available) == (600, 7)
+        assert mgr.available[bannock] == 7
+        assert mgr.get_food("crimson salad").available == 1
+
+    def test_overlay_availability(self) -> None:
+        mgr, theirs = self._catalogs()
+
+        assert mgr.overlay_availability(theirs) == ["Elk Wellington"]
+
+        bannock = mgr.get_food("bannock")
+        assert (bannock.calories, bannock.stomach, bannock.available) == (600, 2, 4)
+        assert mgr.available[bannock] == 4
+        assert mgr.get_food("crimson salad").available == 1
+        assert len(mgr.foods) == 2
+
+    def test_reseed_availability_keeps_stomach(self) -> None:
+        mgr, theirs = self._catalogs()
+        mgr.get_food("bannock").tastiness = 2
+
+        assert mgr.reseed_availability(theirs) == (1, 1)
+
+        bannock = mgr.get_food("bannock")
+        assert (bannock.calories, bannock.available) == (650, 4)
+        assert (bannock.stomach, bannock.tastiness) == (2, 2)
+        assert mgr.stomach[bannock] == 2
+        assert mgr.available[bannock] == 4
+        salad = mgr.get_food("crimson salad")
+        assert salad.available == 0
+        assert salad not in mgr.available
+        assert mgr.get_food("elk wellington").stomach == 0
+
+
+# ---------------------------------------------------------------------------
+# TestDiffCatalogs
+# ---------------------------------------------------------------------------
+
+
+class TestDiffCatalogs:
+    """Tests for diff_catalogs() on added, removed, and modified foods."""
+
+    def test_added_and_removed(self) -> None:
+        old = [make_food("Bannock", 600), make_food("Crimson Salad", 1100)]
+        new = [make_food("bannock", 600), make_food("Elk Wellington", 1400)]
+
+        diff = diff_catalogs(old, new)
+
+        assert diff.added == ["Elk Wellington"]
+        assert diff.removed == ["Crimson Salad"]
+        assert diff.changed == {}
+
+    def test_modified_fields(self) -> None:
+        old = [make_food("Bannock", 600, tastiness=1, stomach=2, available=3)]
+        new = [make_food("Bannock", 650, tastiness=1, stomach=0, available=3)]
+
+        diff = diff_catalogs(old, new)
+
+        assert diff.changed == {"Bannock": {"calories": (600, 650), "stomach": (2, 0)}}
+        assert not diff.added and not diff.removed
+
+    def test_identical_catalogs_are_empty(self) -> None:
+        foods = [make_food("Bannock", 600)]
+
+        assert diff_catalogs(foods, [make_food("Bannock", 600)]).is_empty()
+
+
+# ---------------------------------------------------------------------------
+# TestNormalizeNutrients
+# ---------------------------------------------------------------------------
+
+
+class TestNormalizeNutrients:
+    """Tests for rescaling nutrient dimensions to a common range."""
+
+    @staticmethod
+    def _catalog() -> list[Food]:
+        # Vitamins on a 0-1 scale, everything else up to 100
+        return [
+            make_food("A", 500, carbs=80, protein=10, fat=0, vitamins=1),
+            make_food("B", 500, carbs=20, protein=40, fat=0, vitamins=0),
+            make_food("C", 500, carbs=100, protein=25, fat=0, vitamins=1),
+            make_food("D", 500, carbs=5, protein=50, fat=0, vitamins=0),
+        ]
+
+    def test_factors_map_each_peak_to_the_largest(self) -> None:
+        factors = nutrient_scale_factors(self._catalog())
+
+        assert factors == {"carbs": 1.0, "protein": 2.0, "fat": 1.0, "vitamins": 100.0}
+
+    def test_preserves_order_within_each_nutrient(self) -> None:
+        foods = self._catalog()
+
+        normalized = normalize_nutrients(foods, nutrient_scale_factors(foods))
+
+        for name in NUTRIENT_FIELDS:
+            before = sorted(foods, key=lambda food: getattr(food, name))
+            after = sorted(normalized, key=lambda food: getattr(food, name))
+            assert [f.name for f in before] == [f.name for f in after]
+            for low, high in zip(after, after[1:]):
+                assert getattr(low, name) <= getattr(high, name)
+        assert max(food.vitamins for food in normalized) == 100
+
+    def test_originals_are_unchanged(self) -> None:
+        foods = self._catalog()
+
+        normalized = normalize_nutrients(foods, nutrient_scale_factors(foods))
+
+        assert [food.vitamins for food in foods] == [1, 0, 1, 0]
+        assert [food.calories for food in normalized] == [500] * 4
+        assert normalized[0] is not foods[0]
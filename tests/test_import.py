@@ -12,7 +12,10 @@ if str(ROOT) not in sys.path:
 
 from interface.persistence import (
     _parse_cravings_satisfied,
+    load_foods_csv,
+    load_foods_eco,
     load_game_state_export,
+    parse_header_map,
 )
 
 # ---------------------------------------------------------------------------
@@ -246,3 +249,153 @@ class TestTastinessMapping:
             tastiness=tastiness,
         )
         assert food.tastiness == tastiness
+
+
+# ---------------------------------------------------------------------------
+# In-game food export (load_foods_eco)
+# ---------------------------------------------------------------------------
+
+ECO_FOOD_EXPORT = {
+    "GameVersion": "0.11.1.4",
+    "ExportedAt": "2026-09-30T18:02:11Z",
+    "Foods": [
+        {
+            "TypeName": "BannockItem",
+            "DisplayName": "Bannock",
+            "Calories": 600.0,
+            "Nutrition": {"Carbs": 12.0, "Fat": 8.0, "Protein": 3.0, "Vitamins": 0.0},
+            "Weight": 500,
+            "Tags": ["Food", "Baked"],
+            "Shelflife": {"Hours": 24.0, "Category": "Baked"},
+        },
+        {
+            "Name": "Charred Fish",
+            "Calories": 500,
+            "Carbs": 0,
+            "Protein": 12,
+            "Fats": 8,
+            "Vitamins": 0,
+            "Tastiness": 2,
+            "CraftingTable": {"Name": "Campfire", "Skill": None},
+        },
+        {
+            "DisplayName": "Mystery Recipe",
+            "Calories": 300,
+            "Nutrition": {"Carbs": 5, "Protein": 5},
+        },
+        {"TypeName": "DebugItem", "Tags": []},
+        "not an entry",
+    ],
+}
+
+
+class TestLoadFoodsEco:
+    """Adapter for the game's own food export shape."""
+
+    def test_imports_usable_entries_and_skips_the_rest(
+        self, tmp_path: pathlib.Path, capsys
+    ) -> None:
+        path = tmp_path / "eco_foods.json"
+        path.write_text(json.dumps(ECO_FOOD_EXPORT), encoding="utf-8")
+
+        foods = load_foods_eco(path)
+
+        assert [f.name for f in foods] == ["Bannock", "Charred Fish"]
+        bannock, fish = foods
+        assert (bannock.calories, bannock.carbs, bannock.fat) == (600, 12, 8)
+        assert bannock.tastiness == 99
+        assert bannock.available == 0
+        assert fish.fat == 8
+        assert fish.tastiness == 2
+        out = capsys.readouterr().out
+        assert "Imported 2 foods" in out
+        assert "(skipped 3)" in out
+
+    def test_fractional_calories_are_rounded(self, tmp_path: pathlib.Path) -> None:
+        entry = {**ECO_FOOD_EXPORT["Foods"][0], "Calories": 599.6}
+        path = tmp_path / "eco_foods.json"
+        path.write_text(json.dumps([entry]), encoding="utf-8")
+
+        assert load_foods_eco(path)[0].calories == 600
+
+    def test_accepts_top_level_list(self, tmp_path: pathlib.Path) -> None:
+        path = tmp_path / "eco_foods.json"
+        path.write_text(json.dumps(ECO_FOOD_EXPORT["Foods"][:1]), encoding="utf-8")
+
+        assert [f.name for f in load_foods_eco(path)] == ["Bannock"]
+
+    def test_rejects_file_without_food_list(self, tmp_path: pathlib.Path) -> None:
+        path = tmp_path / "eco_foods.json"
+        path.write_text(json.dumps({"Items": []}), encoding="utf-8")
+
+        with pytest.raises(ValueError):
+            load_foods_eco(path)
+
+
+# ---------------------------------------------------------------------------
+# Spreadsheet CSV import (load_foods_csv)
+# ---------------------------------------------------------------------------
+
+
+class TestLoadFoodsCsv:
+    """Header aliases, --header-map entries, and positional fallback."""
+
+    def test_aliased_headers(self, tmp_path: pathlib.Path) -> None:
+        path = tmp_path / "foods.csv"
+        path.write_text(
+            "Food,kcal,Carb,Prot,Fats,Vit,Taste\n"
+            "Bannock,600,12,3,8,0,1\n"
+            "Charred Fish,500,0,12,8,0,\n",
+            encoding="utf-8",
+        )
+
+        bannock, fish = load_foods_csv(path)
+
+        assert (bannock.name, bannock.calories, bannock.protein) == ("Bannock", 600, 3)
+        assert (bannock.fat, bannock.tastiness, bannock.available) == (8, 1, 0)
+        assert fish.tastiness == 99
+
+    def test_header_map_overrides_aliases(self, tmp_path: pathlib.Path) -> None:
+        path = tmp_path / "foods.csv"
+        path.write_text(
+            "Name,Energy,Carbs,Protein,Fat,Vitamins,Cal\nBannock,600,12,3,8,0,1\n",
+            encoding="utf-8",
+        )
+        header_map = parse_header_map(["Energy=calories", "Cal=Tastiness"])
+
+        (bannock,) = load_foods_csv(path, header_map)
+
+        assert (bannock.calories, bannock.tastiness) == (600, 1)
+
+    def test_positional_without_header(self, tmp_path: pathlib.Path) -> None:
+        path = tmp_path / "foods.csv"
+        path.write_text("Bannock,600,12,3,8,0,2,5\n", encoding="utf-8")
+
+        (bannock,) = load_foods_csv(path)
+
+        assert (bannock.vitamins, bannock.tastiness, bannock.available) == (0, 2, 5)
+
+    def test_unmapped_required_columns(self, tmp_path: pathlib.Path) -> None:
+        path = tmp_path / "foods.csv"
+        path.write_text(
+            "Name,Calories,Carbs,Energy\nBannock,600,12,3\n", encoding="utf-8"
+        )
+
+        with pytest.raises(ValueError, match="Protein, Fat, Vitamins"):
+            load_foods_csv(path)
+
+    def test_bad_cell_names_the_row(self, tmp_path: pathlib.Path) -> None:
+        path = tmp_path / "foods.csv"
+        path.write_text(
+            "Name,Cal,Carbs,Protein,Fat,Vitamins\nBannock,lots,12,3,8,0\n",
+            encoding="utf-8",
+        )
+
+        with pytest.raises(ValueError, match="Row 2: Calories 'lots'"):
+            load_foods_csv(path)
+
+    def test_parse_header_map_rejects_unknown_field(self) -> None:
+        with pytest.raises(ValueError):
+            parse_header_map(["Energy=Joules"])
+        with pytest.raises(ValueError):
+            parse_header_map(["Energy"])
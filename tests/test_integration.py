@@ -92,6 +92,25 @@ class TestFullPlanGeneration:
         assert final_variety > 0
         assert len(meal_plan) > 0
 
+    def test_last_bite_density_matches_final_stomach(
+        self, simple_manager_factory
+    ) -> None:
+        """Captured post-bite density equals the final stomach's density."""
+        manager = simple_manager_factory()
+
+        meal_plan = plan_meal(
+            manager=manager,
+            cravings=[],
+            cravings_satisfied=0,
+            remaining_calories=4000,
+        )
+
+        final_density, _ = sum_all_weighted_nutrients(manager.stomach)
+        assert meal_plan[-1].density == pytest.approx(final_density)
+        assert meal_plan[-1].balance_bonus_pp == pytest.approx(
+            calculate_balanced_diet_ratio(list(final_density.values())) * 100 - 50
+        )
+
 
 class TestPlanRespectsAvailability:
     """Tests that planning respects food availability limits."""
@@ -157,6 +176,96 @@ class TestPlanRespectsAvailability:
         assert final_available == initial_available - consumed_count
 
 
+class TestMaxRepeats:
+    """Tests for the hard per-food repeat cap."""
+
+    def test_no_food_exceeds_cap(self, simple_manager_factory) -> None:
+        """With max_repeats=2, no food appears more than twice."""
+        manager = simple_manager_factory()
+
+        meal_plan = plan_meal(
+            manager=manager,
+            cravings=[],
+            cravings_satisfied=0,
+            remaining_calories=10000,
+            max_repeats=2,
+        )
+
+        names = [item.name for item in meal_plan]
+        assert names
+        assert max(names.count(name) for name in names) <= 2
+
+    def test_capped_craving_is_skipped(self) -> None:
+        """A craving already eaten up to the cap is not planned again."""
+        craved = make_food("Craved", calories=500, stomach=1, available=10)
+        other = make_food("Other", calories=500, available=10)
+        manager = FoodStateManager([craved, other])
+
+        meal_plan = plan_meal(
+            manager=manager,
+            cravings=["Craved"],
+            cravings_satisfied=0,
+            remaining_calories=1000,
+            max_repeats=1,
+        )
+
+        assert "Craved" not in [item.name for item in meal_plan]
+
+
+class TestMinBiteCalories:
+    """Tests for the hard minimum bite size."""
+
+    @staticmethod
+    def _foods() -> list[Food]:
+        return [
+            make_food(
+                "Low Cal Snack", calories=250, carbs=20, protein=20, fat=20, vitamins=20
+            ),
+            make_food("Hearty Stew", calories=500),
+            make_food("Roast", calories=1000, carbs=12, protein=14, fat=9, vitamins=8),
+        ]
+
+    def test_snack_excluded_when_larger_foods_fit(self) -> None:
+        unfiltered = plan_meal(FoodStateManager(self._foods()), [], 0, 3000)
+        filtered = plan_meal(
+            FoodStateManager(self._foods()), [], 0, 3000, min_bite_calories=400
+        )
+
+        assert "Low Cal Snack" in [item.name for item in unfiltered]
+        assert filtered
+        assert "Low Cal Snack" not in [item.name for item in filtered]
+
+    def test_falls_back_to_small_bite_when_nothing_else_fits(self) -> None:
+        meal_plan = plan_meal(
+            FoodStateManager(self._foods()), [], 0, 300, min_bite_calories=400
+        )
+
+        assert [item.name for item in meal_plan] == ["Low Cal Snack"]
+
+
+class TestSeededStomach:
+    """Tests for planning after pre-loading the stomach with consume_n."""
+
+    @staticmethod
+    def _manager() -> FoodStateManager:
+        return FoodStateManager(
+            [
+                make_food("Apple", 500, carbs=12, protein=2, fat=0, vitamins=10),
+                make_food("Bacon", 500, carbs=2, protein=6, fat=10, vitamins=2),
+            ]
+        )
+
+    def test_seed_shifts_first_bite(self) -> None:
+        empty_plan = plan_meal(self._manager(), [], 0, 500)
+
+        seeded = self._manager()
+        assert seeded.consume_n("Bacon", 3) == 3
+        seeded_plan = plan_meal(seeded, [], 0, 500)
+
+        assert [item.name for item in empty_plan] == ["Bacon"]
+        assert [item.name for item in seeded_plan] == ["Apple"]
+
+
 class TestEmptyStomachToFull:
     """Tests for planning from an empty stomach."""
 
@@ -320,6 +429,25 @@ class TestEdgeCases:
 
         assert len(meal_plan) == 0
 
+    def test_first_bite_prefers_fitting_food(self) -> None:
+        """A budget between the cheapest and priciest food picks one that fits."""
+        foods = [
+            make_food(
+                "Feast", calories=1500, carbs=30, protein=30, fat=30, vitamins=30
+            ),
+            make_food("Snack", calories=600, carbs=5, protein=5, fat=5, vitamins=5),
+        ]
+        manager = FoodStateManager(foods)
+
+        meal_plan = plan_meal(
+            manager=manager,
+            cravings=["Feast"],
+            cravings_satisfied=0,
+            remaining_calories=1000,
+        )
+
+        assert [item.name for item in meal_plan] == ["Snack"]
+
     def test_invalid_craving_ignored(self, simple_manager_factory) -> None:
         """Invalid craving names should be gracefully ignored."""
         manager = simple_manager_factory()
@@ -1,16 +1,29 @@
 """Tests for persistence I/O and data integrity logging."""
 
+import io
 import json
 
 import pytest
 
 from conftest import make_food
+from food_state_manager import FoodStateManager
 from interface.persistence import (
+    append_history_entry,
+    backup_file,
+    history_logger,
     log_data_issues,
+    read_cravings_file,
     read_food_dict,
+    read_food_dict_reporting,
+    read_history,
+    read_plan,
     save_food_dict,
+    save_plan,
+    validate_food_entries,
 )
 from models.food import Food
+from models.plan import PlanResult
+from planner import plan_meal
 
 
 class TestReadFoodDict:
@@ -48,12 +61,75 @@ class TestReadFoodDict:
         result = read_food_dict(path)
         assert result == []
 
+    def test_read_empty_or_whitespace_reports_empty(self, tmp_path, capsys) -> None:
+        """Empty and whitespace-only files return [] with a clear message."""
+        for content in ("", "  \n\t "):
+            path = tmp_path / "empty.json"
+            path.write_text(content, encoding="utf-8")
+
+            assert read_food_dict(path) == []
+            assert "food state file is empty" in capsys.readouterr().out
+
+    def test_read_object_instead_of_list_hints(self, tmp_path, capsys) -> None:
+        """A top-level object returns [] with a hint to wrap it in a list."""
+        path = tmp_path / "object.json"
+        path.write_text(json.dumps(make_food("Bannock", 600).to_dict()))
+
+        assert read_food_dict(path) == []
+        assert "wrap the contents in [...]" in capsys.readouterr().out
+
+    def test_out_of_range_tastiness_becomes_unknown(self, tmp_path, capsys) -> None:
+        """A rating off the scale loads as 99 and the food is named."""
+        entries = [
+            {**make_food("Bannock", 600).to_dict(), "Tastiness": 7},
+            make_food("Salad", 1100, tastiness=2).to_dict(),
+        ]
+        path = tmp_path / "food.json"
+        path.write_text(json.dumps(entries), encoding="utf-8")
+
+        bannock, salad = read_food_dict(path)
+
+        assert bannock.tastiness == 99
+        assert salad.tastiness == 2
+        out = capsys.readouterr().out
+        assert "reset to unknown (99) for: Bannock" in out
+        assert "Salad" not in out
+
     def test_read_missing_file_raises(self, tmp_path) -> None:
         """Nonexistent file raises."""
         with pytest.raises(FileNotFoundError):
             read_food_dict(tmp_path / "missing.json")
 
 
+class TestReadFoodDictReporting:
+    """Tests for read_food_dict_reporting()."""
+
+    def test_reports_duplicate_and_keeps_last(self, tmp_path) -> None:
+        """A repeated name is reported once and its last entry is kept."""
+        entries = [
+            make_food("Apple", 100).to_dict(),
+            make_food("Bannock", 600).to_dict(),
+            make_food("apple", 150).to_dict(),
+            make_food("APPLE", 175).to_dict(),
+        ]
+        path = tmp_path / "dupes.json"
+        path.write_text(json.dumps(entries), encoding="utf-8")
+
+        foods, duplicates = read_food_dict_reporting(path)
+
+        assert duplicates == ["apple"]
+        assert [(food.name, food.calories) for food in foods] == [
+            ("APPLE", 175),
+            ("Bannock", 600),
+        ]
+
+    def test_no_duplicates_reports_nothing(self, tmp_path) -> None:
+        path = tmp_path / "clean.json"
+        path.write_text(json.dumps([make_food("Apple", 100).to_dict()]))
+
+        assert read_food_dict_reporting(path)[1] == []
+
+
 class TestSaveFoodDict:
     """Tests for save_food_dict()."""
 
@@ -85,6 +161,76 @@ class TestSaveFoodDict:
         assert result[0].name == "Elk Wellington"
         assert result[0].calories == 1400
 
+    def test_stream_round_trip(self) -> None:
+        """A catalog written to an in-memory writer reads back unchanged."""
+        foods = [
+            make_food("Bannock", calories=600, carbs=12, protein=3, fat=8),
+            make_food("Crimson Salad", calories=1100, vitamins=22, available=3),
+        ]
+        writer = io.StringIO()
+
+        save_food_dict([f.to_dict() for f in foods], writer)
+        result = read_food_dict(io.StringIO(writer.getvalue()))
+
+        assert [f.to_dict() for f in result] == [f.to_dict() for f in foods]
+
+    def test_dash_uses_stdin_and_stdout(self, monkeypatch, capsys) -> None:
+        """'-' reads stdin and writes stdout without backups."""
+        food_list = [make_food("Bannock", calories=600).to_dict()]
+        monkeypatch.setattr("sys.stdin", io.StringIO(json.dumps(food_list)))
+
+        foods = read_food_dict("-")
+        save_food_dict([f.to_dict() for f in foods], "-", compact=True)
+
+        assert json.loads(capsys.readouterr().out) == food_list
+
+    def test_compact_and_pretty_reload_identically(self, tmp_path) -> None:
+        """compact=True writes one line that reads back to the same foods."""
+        food_list = [
+            make_food("Bannock", calories=600, carbs=12, protein=3, fat=8).to_dict(),
+            make_food("Crimson Salad", calories=1100, vitamins=22).to_dict(),
+        ]
+        pretty_path = tmp_path / "pretty.json"
+        compact_path = tmp_path / "compact.json"
+
+        save_food_dict(food_list, pretty_path)
+        save_food_dict(food_list, compact_path, compact=True)
+
+        assert len(compact_path.read_text(encoding="utf-8").splitlines()) == 1
+        pretty = read_food_dict(pretty_path)
+        compact = read_food_dict(compact_path)
+        assert [f.to_dict() for f in compact] == [f.to_dict() for f in pretty]
+
+
+class TestBackupFile:
+    """Tests for backup rotation before saves."""
+
+    def test_saves_keep_exactly_n_backups(self, tmp_path) -> None:
+        """N+1 saves with keep=2 leave two backups of the previous versions."""
+        path = tmp_path / "food_state.json"
+        for calories in (100, 200, 300, 400):
+            save_food_dict(
+                [make_food("Bannock", calories).to_dict()], path, keep_backups=2
+            )
+
+        backups = sorted(p.name for p in tmp_path.glob("food_state.json.bak*"))
+        assert backups == ["food_state.json.bak", "food_state.json.bak.1"]
+
+        def calories_in(name: str) -> int:
+            return json.loads((tmp_path / name).read_text())[0]["Calories"]
+
+        assert calories_in("food_state.json") == 400
+        assert calories_in("food_state.json.bak") == 300
+        assert calories_in("food_state.json.bak.1") == 200
+
+    def test_no_backup_for_new_file_or_zero_keep(self, tmp_path) -> None:
+        path = tmp_path / "food_state.json"
+        backup_file(path, 3)
+        path.write_text("[]")
+        backup_file(path, 0)
+
+        assert list(tmp_path.glob("*.bak*")) == []
+
 
 class TestLogDataIssues:
     """Tests for log_data_issues()."""
@@ -211,3 +357,162 @@ class TestLoadFoodState:
         manager = load_food_state(reset_stomach=True, skip_prompts=True)
         # Stomach should be empty after reset
         assert len(manager.stomach) == 0
+        assert len(food_file.read_text(encoding="utf-8").splitlines()) > 1
+
+        load_food_state(reset_stomach=True, skip_prompts=True, compact=True)
+        assert len(food_file.read_text(encoding="utf-8").splitlines()) == 1
+
+    def test_load_food_state_warns_on_duplicates(
+        self, tmp_path, monkeypatch, capsys
+    ) -> None:
+        """Duplicate names are announced instead of silently dropped."""
+        entries = [make_food(name, 600).to_dict() for name in ("Bannock", "bannock")]
+        food_file = tmp_path / "food_state.json"
+        food_file.write_text(json.dumps(entries), encoding="utf-8")
+        monkeypatch.setattr("interface.persistence.DATA_PATH", food_file)
+        monkeypatch.chdir(tmp_path)
+
+        from interface.persistence import load_food_state
+
+        manager = load_food_state(skip_prompts=True)
+
+        assert "[WARN] Duplicate food names" in capsys.readouterr().out
+        assert len(manager.foods) == 1
+
+    def test_load_food_state_from_stdin(self, monkeypatch) -> None:
+        """path='-' reads stdin and never prompts."""
+        entry = make_food("Bannock", 600).to_dict()
+        entry["Tastiness"] = 99
+        monkeypatch.setattr("sys.stdin", io.StringIO(json.dumps([entry])))
+        monkeypatch.setattr("builtins.input", lambda _: pytest.fail("input() called"))
+
+        from interface.persistence import load_food_state
+
+        manager = load_food_state(path="-")
+        assert manager.get_food("bannock") is not None
+
+
+class TestHistory:
+    """Tests for the append-only consumption history."""
+
+    def test_each_consume_appends_one_line(self, tmp_path) -> None:
+        """N consume calls with the hook installed → N history lines."""
+        path = tmp_path / "history.jsonl"
+        food = make_food("Bannock", calories=600, available=5)
+        manager = FoodStateManager([food])
+        manager.on_consume = history_logger(path)
+
+        for _ in range(3):
+            manager.consume(food)
+
+        lines = path.read_text(encoding="utf-8").splitlines()
+        assert len(lines) == 3
+        assert json.loads(lines[0])["Name"] == "Bannock"
+
+    def test_no_hook_writes_nothing(self, tmp_path) -> None:
+        """Without --log the manager never touches the history file."""
+        path = tmp_path / "history.jsonl"
+        food = make_food("Bannock", calories=600, available=5)
+        FoodStateManager([food]).consume(food)
+        assert not path.exists()
+
+    def test_read_history_limit_keeps_latest(self, tmp_path) -> None:
+        path = tmp_path / "history.jsonl"
+        for calories in (100, 200, 300):
+            append_history_entry(
+                path, make_food("Snack", calories), timestamp="2026-01-01T00:00:00"
+            )
+
+        entries = read_history(path, limit=2)
+        assert [entry["Calories"] for entry in entries] == [200, 300]
+
+    def test_read_missing_history_is_empty(self, tmp_path) -> None:
+        assert read_history(tmp_path / "missing.jsonl") == []
+
+
+class TestReadCravingsFile:
+    """Tests for the plain-text cravings list."""
+
+    def test_skips_blanks_and_comments(self, tmp_path) -> None:
+        path = tmp_path / "cravings.txt"
+        path.write_text(
+            "# favourites\n\n  Bannock  \nElk Taco # weekends\n", encoding="utf-8"
+        )
+
+        assert read_cravings_file(path) == ["Bannock", "Elk Taco"]
+
+
+class TestSavePlan:
+    """Tests for the JSON plan file written by --save-plan."""
+
+    def test_round_trip(self, tmp_path) -> None:
+        manager = FoodStateManager(
+            [make_food("Bannock", 600, carbs=12), make_food("Salad", 1100, vitamins=22)]
+        )
+        result = PlanResult.from_plan(plan_meal(manager, ["Salad"], 0, 3000))
+        path = tmp_path / "plan.json"
+
+        save_plan(result, path)
+        loaded = read_plan(path)
+
+        assert loaded == result
+        assert loaded.summary.items == len(result.items) > 0
+        assert loaded.summary.calories == sum(i.calories for i in result.items)
+        assert loaded.summary.final_sp == result.items[-1].new_sp
+
+    def test_empty_plan(self, tmp_path) -> None:
+        path = tmp_path / "plan.json"
+
+        save_plan(PlanResult.from_plan([]), path)
+
+        assert read_plan(path).summary.final_sp is None
+
+    def test_dash_writes_stdout(self, tmp_path, capsys) -> None:
+        result = PlanResult.from_plan([])
+        path = tmp_path / "plan.json"
+        save_plan(result, path)
+
+        save_plan(result, "-")
+
+        assert capsys.readouterr().out == path.read_text(encoding="utf-8") + "\n"
+
+
+def _entry(name: str, **overrides) -> dict:
+    """Raw food_state.json entry with sane defaults."""
+    entry = make_food(name, calories=500, carbs=5, protein=5, fat=5).to_dict()
+    entry.update(overrides)
+    return entry
+
+
+class TestValidateFoodEntries:
+    """Tests for validate_food_entries()."""
+
+    def test_mixed_sample_reports_only_invalid(self) -> None:
+        entries = [
+            _entry("Good"),
+            _entry("Negative", Carbs=-3),
+            _entry("Bad Taste", Tastiness=7),
+        ]
+
+        report = dict(validate_food_entries(entries))
+
+        assert set(report) == {"Negative", "Bad Taste"}
+        assert report["Negative"] == ["Carbs is negative (-3)"]
+        assert report["Bad Taste"] == ["Tastiness 7 is not a valid rating"]
+
+    def test_missing_field_reported(self) -> None:
+        entry = _entry("Partial")
+        del entry["Vitamins"]
+
+        assert validate_food_entries([entry]) == [
+            ("Partial", ["missing field 'Vitamins'"])
+        ]
+
+    def test_fix_clamps_in_place(self) -> None:
+        entries = [_entry("Negative", Calories=-10, Tastiness=7)]
+
+        validate_food_entries(entries, fix=True)
+
+        assert entries[0]["Calories"] == 0
+        assert entries[0]["Tastiness"] == 99
+        assert validate_food_entries(entries) == []
@@ -6,29 +6,57 @@ _proximity_bias, _balance_improvement_bias) and the full bite selection pipeline
 """
 
 import math
+import random
+from concurrent.futures import ThreadPoolExecutor
+
+import pytest
 
 from conftest import make_food
 from constants import (
     BALANCED_DIET_IMPROVEMENT_STRENGTH,
     LOW_CALORIE_PENALTY_STRENGTH,
     LOW_CALORIE_THRESHOLD,
+    MAX_ITERATIONS,
     PROXIMITY_APPROACH_WEIGHT,
     SOFT_VARIETY_BIAS_STRENGTH,
+    TIEBREAK_SCORE_WINDOW_SP,
     VARIETY_CAL_THRESHOLD,
 )
 from food_state_manager import FoodStateManager
+from interface.persistence import read_cravings_file
 from models.food import Food
 from calculations import (
     calculate_balanced_diet_ratio,
+    get_sp_delta,
     simulate_stomach_with_added_food,
     sum_all_weighted_nutrients,
 )
 from planner import (
+    MAX_LOOKAHEAD,
+    STOP_BUDGET,
+    STOP_ITERATION_CAP,
+    STOP_MAX_BITES,
+    STOP_NO_FOOD,
     _balance_improvement_bias,
     _choose_next_bite,
     _low_calorie_penalty,
+    _missing_nutrients,
+    _pick_feasible_craving,
     _proximity_bias,
     _soft_variety_bias,
+    diagnose_stomach,
+    explain_why_not,
+    history_penalties,
+    peek_next,
+    plan_from_state,
+    plan_meal,
+    plan_meal_with_stop,
+    rank_candidates,
+    rank_foods_by_efficiency,
+    resolve_cravings,
+    sweep_budgets,
+    tie_finalists,
+    unmet_cravings,
 )
 
 # --- Fixtures and helpers ---
@@ -41,6 +69,18 @@ class DummyManager(FoodStateManager):
         super().__init__(foods)
 
 
+def _bread_salad_steak(*extra: Food) -> DummyManager:
+    """Bread, Salad and Steak, followed by any *extra* foods."""
+    return DummyManager(
+        [
+            make_food("Bread", 600, carbs=12, protein=3, fat=8, vitamins=0),
+            make_food("Salad", 1100, carbs=12, protein=6, fat=8, vitamins=22),
+            make_food("Steak", 1400, carbs=10, protein=18, fat=12, vitamins=8),
+            *extra,
+        ]
+    )
+
+
 # --- _low_calorie_penalty tests ---
 
 
@@ -255,6 +295,24 @@ class TestChooseNextBite:
         assert food is not None
         assert food.name == "Cheap"
 
+    def test_fitting_food_beats_richer_over_budget_food(self) -> None:
+        """An over-budget food is never chosen while a cheaper one fits."""
+        rich = make_food(
+            "Rich", calories=1500, carbs=40, protein=40, fat=40, vitamins=40
+        )
+        plain = make_food("Plain", calories=600, carbs=5, protein=5, fat=5)
+        manager = DummyManager([rich, plain])
+
+        food, _delta = _choose_next_bite(
+            manager,
+            remaining_calories=1000,
+            cravings=[],
+            cravings_satisfied=0,
+        )
+
+        assert food is not None
+        assert food.name == "Plain"
+
     def test_higher_sp_food_preferred(self) -> None:
         """Food with higher SP delta should be preferred."""
         # Same calories, different nutrients
@@ -337,6 +395,850 @@ class TestChooseNextBite:
         assert food.name == "Available"
 
 
+# --- rank_candidates tests ---
+
+
+class TestRankCandidates:
+    """Tests for the ranked candidate list behind interactive planning."""
+
+    def _foods(self) -> list[Food]:
+        return [
+            make_food("Low", calories=500, carbs=1, protein=1, fat=1, vitamins=1),
+            make_food("High", calories=500, carbs=20, protein=20, fat=20, vitamins=20),
+            make_food("Huge", calories=5000),
+        ]
+
+    def test_head_matches_choose_next_bite(self) -> None:
+        manager = DummyManager(self._foods())
+
+        ranked = rank_candidates(manager, 1000, [], 0)
+        chosen = _choose_next_bite(manager, 1000, [], 0)
+
+        assert ranked[0] == chosen
+        assert [food.name for food, _ in ranked] == ["High", "Low"]
+
+    def test_deltas_are_projected_sp_gain(self) -> None:
+        manager = DummyManager(self._foods())
+
+        for food, sp_delta in rank_candidates(manager, 1000, [], 0):
+            assert sp_delta == get_sp_delta(food, manager.stomach, [], 0)
+
+    def test_pick_bite_drives_plan(self) -> None:
+        offered: list[list[str]] = []
+
+        def pick_worst(candidates: list[tuple[Food, float]]) -> Food | None:
+            offered.append([food.name for food, _ in candidates])
+            return candidates[-1][0] if len(offered) == 1 else None
+
+        plan = plan_meal(DummyManager(self._foods()), [], 0, 1000, pick_bite=pick_worst)
+
+        assert [item.name for item in plan] == ["Low"]
+        assert offered[0] == ["High", "Low"]
+
+
+# --- Non-finite score guard tests ---
+
+
+class TestNonFiniteGuards:
+    """Zero-calorie foods and NaN scores never reach the ranking."""
+
+    def test_zero_calorie_food_keeps_plan_finite(self) -> None:
+        water = make_food(
+            "Water", calories=0, carbs=0, protein=0, fat=0, vitamins=0, stomach=2
+        )
+        manager = DummyManager([water, make_food("Bannock", calories=500)])
+
+        plan = plan_meal(manager, [], 0, 1500)
+
+        assert [item.name for item in plan] == ["Bannock"] * 3
+        assert all(math.isfinite(item.new_sp) for item in plan)
+        assert all(math.isfinite(item.sp_gain) for item in plan)
+
+    def test_nan_score_is_dropped(self, monkeypatch) -> None:
+        foods = [make_food("Bad", calories=500), make_food("Good", calories=500)]
+
+        def fake_delta(food, *args, **kwargs) -> float:
+            return math.nan if food.name == "Bad" else 1.0
+
+        monkeypatch.setattr("planner.get_sp_delta", fake_delta)
+
+        ranked = rank_candidates(DummyManager(foods), 1000, [], 0)
+
+        assert [food.name for food, _ in ranked] == ["Good"]
+
+
+# --- rank_foods_by_efficiency tests ---
+
+
+class TestRankFoodsByEfficiency:
+    """Tests for the standalone SP-per-calorie report."""
+
+    def test_sorted_descending(self) -> None:
+        foods = [
+            make_food("Plain", calories=800, carbs=5, protein=5, fat=5, vitamins=5),
+            make_food("Rich", calories=400, carbs=20, protein=20, fat=20, vitamins=20),
+            make_food("Middle", calories=600),
+        ]
+
+        ranked = rank_foods_by_efficiency(foods)
+
+        efficiencies = [sp_per_calorie for _, sp_per_calorie in ranked]
+        assert efficiencies == sorted(efficiencies, reverse=True)
+        assert ranked[0][0].name == "Rich"
+
+    def test_ties_keep_input_order_and_skip_zero_calories(self) -> None:
+        foods = [
+            make_food("Zeta", calories=500),
+            make_food("Water", calories=0),
+            make_food("Alpha", calories=500),
+        ]
+
+        ranked = rank_foods_by_efficiency(foods)
+
+        assert [food.name for food, _ in ranked] == ["Zeta", "Alpha"]
+
+
+# --- diagnose_stomach tests ---
+
+
+class TestDiagnoseStomach:
+    """Tests for the analyze diagnosis."""
+
+    def test_reports_limiting_nutrient_and_fixing_food(self) -> None:
+        pumpkin = make_food(
+            "Pumpkin", calories=400, carbs=10, protein=5, fat=0, vitamins=8, stomach=6
+        )
+        huckleberry = make_food(
+            "Huckleberry", calories=400, carbs=2, protein=1, fat=10, vitamins=2
+        )
+        manager = FoodStateManager([pumpkin, huckleberry])
+
+        diagnosis = diagnose_stomach(manager)
+
+        assert diagnosis.limiting_nutrient == "fat"
+        assert diagnosis.weakest == "balance"
+        assert diagnosis.suggestion is not None
+        assert diagnosis.suggestion.name == "Huckleberry"
+
+    def test_no_suggestion_when_nothing_improves_balance(self) -> None:
+        only = make_food("Only", calories=400, fat=0, stomach=3, available=0)
+        manager = FoodStateManager([only])
+
+        diagnosis = diagnose_stomach(manager)
+
+        assert diagnosis.suggestion is None
+        assert diagnosis.suggestion_bias == 0.0
+
+
+# --- resolve_cravings tests ---
+
+
+class TestResolveCravings:
+    """Tests for matching cravings read from a file."""
+
+    def test_file_resolves_valid_and_skips_unknown(self, tmp_path) -> None:
+        manager = FoodStateManager(
+            [make_food("Bannock", calories=600), make_food("Elk Taco", calories=500)]
+        )
+        path = tmp_path / "cravings.txt"
+        path.write_text(
+            "# weekly favourites\nBANNOCK\n\nelk tacco  # typo\nMoon Cheese\n",
+            encoding="utf-8",
+        )
+
+        resolved, unresolved = resolve_cravings(manager, read_cravings_file(path))
+
+        assert resolved == ["bannock", "elk taco"]
+        assert unresolved == ["Moon Cheese"]
+
+
+# --- unmet_cravings tests ---
+
+
+class TestUnmetCravings:
+    """Tests for the post-plan report of unsatisfied cravings."""
+
+    def test_one_unit_craving_eaten_then_out_of_stock(self) -> None:
+        pie = make_food("Pie", calories=500, available=1)
+        manager = FoodStateManager([pie, make_food("Bread", calories=500)])
+        cravings = ["Pie", "pie"]
+
+        plan = plan_meal(manager, list(cravings), 0, 2000)
+        unmet = unmet_cravings(manager, cravings, plan, 0)
+
+        assert [item.name for item in plan].count("Pie") == 1
+        assert [(c.name, c.reason) for c in unmet] == [("Pie", "no stock left")]
+
+    def test_reasons_for_stocked_cravings(self) -> None:
+        feast = make_food("Feast", calories=1500, available=3)
+        manager = FoodStateManager([feast, make_food("Bread", calories=500)])
+
+        def reason(budget: int, max_repeats: int | None = None) -> str:
+            return unmet_cravings(manager, ["Feast"], [], budget, max_repeats)[0].reason
+
+        assert reason(1000) == "too large for the remaining budget"
+        assert reason(2000) == "never chosen"
+        manager.consume(feast)
+        assert reason(2000, max_repeats=1) == "at the repeat cap"
+
+    def test_satisfied_craving_not_reported(self) -> None:
+        manager = FoodStateManager([make_food("Pie", calories=500, available=3)])
+
+        plan = plan_meal(manager, ["Pie"], 0, 500)
+
+        assert unmet_cravings(manager, ["Pie"], plan, 0) == []
+
+
+# --- peek_next tests ---
+
+
+class TestPeekNext:
+    """Non-mutating lookahead of the greedy plan."""
+
+    def test_first_peek_matches_choose_next_bite(self) -> None:
+        manager = _bread_salad_steak()
+        available = dict(manager.available)
+
+        peeked = peek_next(manager, [], 0, 3000, 1)
+        food, raw_delta = _choose_next_bite(manager, 3000, [], 0)
+
+        assert len(peeked) == 1
+        assert peeked[0][0] is food
+        assert math.isclose(peeked[0][1], raw_delta)
+        assert manager.available == available
+        assert not manager.stomach
+
+    def test_matches_start_of_full_plan(self) -> None:
+        peeked = peek_next(_bread_salad_steak(), [], 0, 5000, 3)
+        plan = plan_meal(_bread_salad_steak(), [], 0, 5000)
+
+        assert [food.name for food, _ in peeked] == [item.name for item in plan[:3]]
+
+    def test_consume_hook_not_called(self) -> None:
+        manager = _bread_salad_steak()
+        eaten: list[str] = []
+        manager.on_consume = lambda food: eaten.append(food.name)
+
+        peek_next(manager, [], 0, 3000, 2)
+
+        assert eaten == []
+        assert manager.on_consume is not None
+
+
+# --- Overshoot tolerance tests ---
+
+
+class TestOvershootTolerance:
+    """One final bite past the budget when it raises SP."""
+
+    def test_zero_tolerance_keeps_plan_under_budget(self) -> None:
+        default = plan_meal(_bread_salad_steak(), [], 0, 1500)
+        explicit = plan_meal(_bread_salad_steak(), [], 0, 1500, overshoot_tolerance=0)
+
+        assert [item.name for item in explicit] == [item.name for item in default]
+        assert sum(item.calories for item in explicit) <= 1500
+
+    def test_tolerance_of_one_food_allows_exactly_one_overshoot(self) -> None:
+        base = plan_meal(_bread_salad_steak(), [], 0, 1500)
+        stretched = plan_meal(
+            _bread_salad_steak(), [], 0, 1500, overshoot_tolerance=1100
+        )
+
+        assert [item.name for item in stretched] == [
+            *(item.name for item in base),
+            "Salad",
+        ]
+        total = sum(item.calories for item in stretched)
+        assert 1500 < total <= 1500 + 1100
+        assert stretched[-1].new_sp > base[-1].new_sp
+
+    def test_overshoot_skipped_when_it_would_not_raise_sp(self) -> None:
+        # Only another Bread fits the tolerance, and repeating it adds nothing
+        plan = plan_meal(_bread_salad_steak(), [], 0, 1000, overshoot_tolerance=600)
+
+        assert [item.name for item in plan] == ["Bread"]
+
+
+# --- Required foods tests ---
+
+
+class TestRequire:
+    """Staples eaten before the greedy loop."""
+
+    def test_required_foods_first_then_plan_remaining_budget(self) -> None:
+        plan = plan_meal(_bread_salad_steak(), [], 0, 4000, require=["bread", "Salad"])
+
+        reference = _bread_salad_steak()
+        reference.consume(reference.get_food("Bread"))
+        reference.consume(reference.get_food("Salad"))
+        rest = plan_meal(reference, [], 0, 4000 - 600 - 1100)
+
+        assert [item.name for item in plan[:2]] == ["Bread", "Salad"]
+        assert [item.name for item in plan[2:]] == [item.name for item in rest]
+        assert sum(item.calories for item in plan) <= 4000
+        assert plan[0].sp_gain > 0
+        assert math.isclose(plan[-1].new_sp, rest[-1].new_sp)
+
+    def test_unavailable_or_oversized_required_food_skipped(self) -> None:
+        manager = _bread_salad_steak()
+        manager.available[manager.get_food("Bread")] = 0
+
+        plan = plan_meal(manager, [], 0, 1200, require=["Bread", "Steak", "Pie"])
+
+        assert "Bread" not in [item.name for item in plan]
+        assert "Steak" not in [item.name for item in plan]
+
+
+# --- Max bites (stomach capacity) tests ---
+
+
+class TestMaxBites:
+    """The plan never holds more bites than the stomach capacity."""
+
+    def test_plan_never_exceeds_max_bites(self) -> None:
+        uncapped = plan_meal(_bread_salad_steak(), [], 0, 20000)
+
+        for cap in range(len(uncapped) + 2):
+            plan = plan_meal(_bread_salad_steak(), [], 0, 20000, max_bites=cap)
+
+            assert len(plan) == min(cap, len(uncapped))
+            assert [item.name for item in plan] == [
+                item.name for item in uncapped[:cap]
+            ]
+
+    def test_cap_counts_required_and_overshoot_bites(self) -> None:
+        required = plan_meal(
+            _bread_salad_steak(), [], 0, 4000, require=["Bread", "Salad"], max_bites=1
+        )
+        base = plan_meal(_bread_salad_steak(), [], 0, 1500)
+        capped = plan_meal(
+            _bread_salad_steak(),
+            [],
+            0,
+            1500,
+            overshoot_tolerance=1100,
+            max_bites=len(base),
+        )
+
+        assert [item.name for item in required] == ["Bread"]
+        assert [item.name for item in capped] == [item.name for item in base]
+
+
+# --- Zero-calorie food tests ---
+
+
+class TestZeroCalorieFoods:
+    """Zero-calorie foods never use up the budget, so they are never planned."""
+
+    @staticmethod
+    def _manager() -> DummyManager:
+        return _bread_salad_steak(make_food("Spice", 0, vitamins=40, available=1000))
+
+    def test_plan_terminates_without_zero_calorie_bites(self, caplog) -> None:
+        plan = plan_meal(self._manager(), [], 0, 3000)
+
+        assert plan
+        assert len(plan) < MAX_ITERATIONS
+        assert "Spice" not in {item.name for item in plan}
+        assert any("zero-calorie foods" in m and "Spice" in m for m in caplog.messages)
+
+    def test_required_and_craved_zero_calorie_food_skipped(self) -> None:
+        plan = plan_meal(self._manager(), ["Spice"], 0, 3000, require=["Spice"])
+
+        assert plan
+        assert "Spice" not in {item.name for item in plan}
+
+
+# --- Stop reason tests ---
+
+
+class TestStopReason:
+    """plan_meal_with_stop reports why the planning loop ended."""
+
+    def test_tiny_food_high_budget_hits_iteration_cap(self) -> None:
+        manager = DummyManager([make_food("Crumb", 150, available=10_000)])
+
+        plan, reason = plan_meal_with_stop(manager, [], 0, 150 * 10_000)
+
+        assert reason == STOP_ITERATION_CAP
+        assert len(plan) == MAX_ITERATIONS
+        assert manager.stomach[manager.get_food("Crumb")] == MAX_ITERATIONS
+
+    def test_plan_finished_on_the_last_iteration_is_not_capped(self) -> None:
+        def manager(available: int = 10_000) -> DummyManager:
+            return DummyManager([make_food("Crumb", 150, available=available)])
+
+        budget = 150 * MAX_ITERATIONS
+        plan, reason = plan_meal_with_stop(manager(), [], 0, budget)
+        assert len(plan) == MAX_ITERATIONS
+        assert reason == STOP_BUDGET
+
+        plan, reason = plan_meal_with_stop(manager(MAX_ITERATIONS), [], 0, 2 * budget)
+        assert len(plan) == MAX_ITERATIONS
+        assert reason == STOP_NO_FOOD
+
+        plan, reason = plan_meal_with_stop(
+            manager(), [], 0, 2 * budget, max_bites=MAX_ITERATIONS
+        )
+        assert reason == STOP_MAX_BITES
+
+    def test_other_stop_reasons(self) -> None:
+        def manager() -> DummyManager:
+            return DummyManager([make_food("Bread", 600, available=10)])
+
+        assert plan_meal_with_stop(manager(), [], 0, 1200)[1] == STOP_BUDGET
+        assert plan_meal_with_stop(manager(), [], 0, 1000)[1] == STOP_NO_FOOD
+        assert plan_meal_with_stop(manager(), [], 0, 9000)[1] == STOP_NO_FOOD
+        assert (
+            plan_meal_with_stop(manager(), [], 0, 3000, max_bites=2)[1]
+            == STOP_MAX_BITES
+        )
+
+
+# --- Distinct-food cap tests ---
+
+
+class TestMaxDistinct:
+    """Once the plan uses max_distinct foods it only repeats them."""
+
+    @staticmethod
+    def _manager() -> DummyManager:
+        return _bread_salad_steak(
+            make_food("Berries", 300, carbs=8, protein=1, fat=0, vitamins=12),
+            make_food("Nuts", 500, carbs=4, protein=10, fat=14, vitamins=2),
+        )
+
+    def test_distinct_foods_never_exceed_cap(self) -> None:
+        uncapped = plan_meal(self._manager(), [], 0, 15000)
+        assert len({item.name for item in uncapped}) > 2
+
+        for cap in (1, 2, 3):
+            plan = plan_meal(
+                self._manager(), [], 0, 15000, max_distinct=cap, overshoot_tolerance=500
+            )
+
+            assert plan
+            assert len({item.name for item in plan}) <= cap
+
+    def test_new_craving_past_cap_is_skipped(self) -> None:
+        manager = self._manager()
+        plan = plan_meal(
+            manager, ["Salad"], 0, 3000, require=["Bread"], max_distinct=1
+        )
+
+        assert {item.name for item in plan} == {"Bread"}
+        unmet = unmet_cravings(manager, ["Salad"], plan, 0, max_distinct=1)
+        assert [(u.name, u.reason) for u in unmet] == [
+            ("Salad", "past the distinct-food cap")
+        ]
+
+
+# --- Greedy lookahead tests ---
+
+
+class TestGreedyLookahead:
+    """lookahead=K scores bites by the SP reached K greedy bites later."""
+
+    @pytest.mark.parametrize("budget", [1000, 2000, 3000, 5000, 8000])
+    def test_two_steps_never_below_one(self, simple_manager_factory, budget) -> None:
+        greedy = plan_meal(simple_manager_factory(), [], 0, budget, lookahead=1)
+        lookahead = plan_meal(simple_manager_factory(), [], 0, budget, lookahead=2)
+
+        assert lookahead[-1].new_sp >= greedy[-1].new_sp - 1e-9
+
+    def test_two_steps_beat_greedy_past_a_threshold(
+        self, simple_manager_factory
+    ) -> None:
+        greedy = plan_meal(simple_manager_factory(), [], 0, 3000)
+        lookahead = plan_meal(simple_manager_factory(), [], 0, 3000, lookahead=2)
+
+        assert lookahead[-1].new_sp > greedy[-1].new_sp
+
+    @pytest.mark.parametrize("depth", [0, MAX_LOOKAHEAD + 1])
+    def test_depth_out_of_range_raises(self, simple_manager_factory, depth) -> None:
+        with pytest.raises(ValueError, match="lookahead"):
+            plan_meal(simple_manager_factory(), [], 0, 2000, lookahead=depth)
+
+
+# --- Zero-nutrient guard tests ---
+
+
+class TestAvoidZeroNutrient:
+    """Early bites cover all four nutrients when the guard is on."""
+
+    @staticmethod
+    def _manager() -> DummyManager:
+        # Meat has the best SP but no carbs or vitamins; Hash covers all four
+        return DummyManager(
+            [
+                make_food("Meat", 800, carbs=0, protein=30, fat=20, vitamins=0),
+                make_food("Fruit", 600, carbs=20, protein=0, fat=0, vitamins=20),
+                make_food("Hash", 700, carbs=8, protein=8, fat=6, vitamins=1),
+            ]
+        )
+
+    def _missing_after_each_bite(self, plan) -> list[list[str]]:
+        """Nutrients still absent from the stomach after each planned bite."""
+        catalog = self._manager()
+        stomach: dict[Food, int] = {}
+        missing = []
+        for item in plan:
+            food = catalog.get_food(item.name)
+            stomach[food] = stomach.get(food, 0) + 1
+            missing.append(_missing_nutrients(stomach))
+        return missing
+
+    def test_guard_covers_all_nutrients_from_the_first_bite(self) -> None:
+        plan = plan_meal(self._manager(), [], 0, 3000, zero_nutrient_tolerance=12.0)
+
+        assert plan[0].name == "Hash"
+        assert all(not missing for missing in self._missing_after_each_bite(plan))
+
+    def test_without_guard_first_bite_leaves_a_zero_nutrient(self) -> None:
+        plan = plan_meal(self._manager(), [], 0, 3000)
+
+        assert self._missing_after_each_bite(plan)[0] == ["carbs", "vitamins"]
+
+    def test_guard_respects_tolerance(self) -> None:
+        # Hash gives up about 10.6 SP against Meat; a 5 SP budget keeps Meat
+        plan = plan_meal(self._manager(), [], 0, 3000, zero_nutrient_tolerance=5.0)
+
+        assert plan[0].name == "Meat"
+
+
+# --- Plan objective tests ---
+
+
+class TestPlanObjective:
+    """Ranking bites for variety qualification instead of SP."""
+
+    def test_variety_objective_qualifies_more_foods(self) -> None:
+        sp_manager = _bread_salad_steak()
+        variety_manager = _bread_salad_steak()
+
+        plan_meal(sp_manager, [], 0, 5000)
+        plan_meal(variety_manager, [], 0, 5000, objective="variety")
+
+        assert len(variety_manager.unique_variety_foods()) > len(
+            sp_manager.unique_variety_foods()
+        )
+
+    def test_variety_ranking_finishes_cheapest_food_first(self) -> None:
+        manager = _bread_salad_steak()
+        manager.consume(manager.get_food("Bread"))
+
+        ranked = rank_candidates(manager, 5000, [], 0, objective="variety")
+
+        # Bread needs 3 more units (1800 cal); Salad 2 (2200); Steak 2 (2800)
+        assert [food.name for food, _ in ranked] == ["Bread", "Salad", "Steak"]
+
+
+# --- plan_from_state tests ---
+
+
+class TestPlanFromState:
+    """Planning on a private copy of the starting state."""
+
+    def test_parallel_plans_from_one_state_match(self) -> None:
+        manager = _bread_salad_steak()
+        available = dict(manager.available)
+
+        with ThreadPoolExecutor(max_workers=2) as pool:
+            futures = [
+                pool.submit(plan_from_state, manager, [], 0, 5000) for _ in range(2)
+            ]
+            (first, first_state), (second, second_state) = (
+                f.result() for f in futures
+            )
+
+        assert first == second
+        assert first_state == second_state
+        assert manager.available == available
+        assert not manager.stomach
+
+    def test_parallel_plans_keep_the_consume_hook(self) -> None:
+        manager = _bread_salad_steak()
+        eaten: list[str] = []
+
+        def hook(food) -> None:
+            eaten.append(food.name)
+
+        manager.on_consume = hook
+
+        with ThreadPoolExecutor(max_workers=4) as pool:
+            futures = [
+                pool.submit(plan_from_state, manager, [], 0, 5000) for _ in range(8)
+            ]
+            plans = [f.result()[0] for f in futures]
+
+        assert all(plan == plans[0] for plan in plans)
+        assert manager.on_consume is hook
+        assert eaten == []
+        plan_meal(manager, [], 0, 5000)
+        assert eaten == [item.name for item in plans[0]]
+
+    def test_plan_meal_commits_the_same_plan(self) -> None:
+        manager = _bread_salad_steak()
+        eaten: list[str] = []
+        manager.on_consume = lambda food: eaten.append(food.name)
+
+        pure, final_state = plan_from_state(manager, [], 0, 5000)
+        committed = plan_meal(manager, [], 0, 5000)
+
+        assert committed == pure
+        assert manager.snapshot() == final_state
+        assert eaten == [item.name for item in committed]
+
+
+# --- sweep_budgets tests ---
+
+
+class TestSweepBudgets:
+    """Planning the same state across a range of budgets."""
+
+    def test_sp_non_decreasing_with_budget(self) -> None:
+        # Same nutrient profile everywhere: extra bites can't dilute density,
+        # and each new food adds variety
+        manager = DummyManager(
+            [make_food(name, 500, available=4) for name in ("A", "B", "C", "D")]
+        )
+
+        points = sweep_budgets(manager, [], 0, range(500, 8001, 500))
+
+        sps = [point.final_sp for point in points]
+        assert sps == sorted(sps)
+        assert sps[0] < sps[-1]
+
+    def test_each_budget_starts_from_the_same_state(self) -> None:
+        manager = _bread_salad_steak()
+        available = dict(manager.available)
+
+        points = sweep_budgets(manager, [], 0, [3000, 3000])
+
+        assert points[0] == points[1]
+        assert all(point.calories <= point.budget for point in points)
+        assert manager.available == available
+        assert not manager.stomach
+
+
+# --- Deterministic name tie-break tests ---
+
+
+class TestNameTiebreak:
+    """Exactly tied candidates resolve by name, independent of catalog order."""
+
+    def test_identical_foods_pick_first_name_in_any_order(self) -> None:
+        for names in (["Zeta", "Alpha"], ["Alpha", "Zeta"]):
+            manager = DummyManager([make_food(name, calories=500) for name in names])
+
+            food, _ = _choose_next_bite(
+                manager,
+                remaining_calories=1000,
+                cravings=[],
+                cravings_satisfied=0,
+            )
+
+            assert food is not None
+            assert food.name == "Alpha"
+
+    def test_tied_cravings_pick_first_name(self) -> None:
+        manager = DummyManager(
+            [make_food("Zeta", calories=500), make_food("Alpha", calories=500)]
+        )
+
+        food = _pick_feasible_craving(manager, ["zeta", "alpha"], 1000, 0)
+
+        assert food is not None
+        assert food.name == "Alpha"
+
+    def test_repeated_plans_are_identical(self) -> None:
+        def run_plan(names: list[str]) -> list[tuple[str, float]]:
+            manager = DummyManager(
+                [make_food(name, calories=400, available=3) for name in names]
+            )
+            plan = plan_meal(manager, [], 0, 2000)
+            return [(item.name, item.sp_gain) for item in plan]
+
+        first = run_plan(["Gamma", "Beta", "Alpha"])
+
+        assert run_plan(["Gamma", "Beta", "Alpha"]) == first
+        assert run_plan(["Alpha", "Beta", "Gamma"]) == first
+
+    def test_stock_weighted_ties_favor_abundant_food(self) -> None:
+        picks = {"Alpha": 0, "Zeta": 0}
+        for seed in range(200):
+            manager = DummyManager(
+                [
+                    make_food("Alpha", calories=500, available=1),
+                    make_food("Zeta", calories=500, available=9),
+                ]
+            )
+
+            food, _ = _choose_next_bite(
+                manager,
+                remaining_calories=1000,
+                cravings=[],
+                cravings_satisfied=0,
+                rng=random.Random(seed),
+            )
+
+            assert food is not None
+            picks[food.name] += 1
+
+        assert picks["Zeta"] > 3 * picks["Alpha"]
+        assert picks["Alpha"] > 0
+
+    def test_same_seed_same_plan(self) -> None:
+        def run_plan(seed: int) -> list[str]:
+            manager = DummyManager(
+                [
+                    make_food(name, calories=400, available=stock)
+                    for name, stock in (("Alpha", 2), ("Beta", 5), ("Gamma", 8))
+                ]
+            )
+            plan = plan_meal(manager, [], 0, 2000, rng=random.Random(seed))
+            return [item.name for item in plan]
+
+        assert run_plan(3) == run_plan(3)
+
+    def test_different_seeds_can_differ_on_tie(self) -> None:
+        def first_bite(seed: int | None) -> str:
+            manager = DummyManager(
+                [
+                    make_food("Alpha", calories=500, available=5),
+                    make_food("Zeta", calories=500, available=5),
+                ]
+            )
+            rng = None if seed is None else random.Random(seed)
+            return plan_meal(manager, [], 0, 500, rng=rng)[0].name
+
+        assert {first_bite(seed) for seed in range(20)} == {"Alpha", "Zeta"}
+        # Without a seed the tie goes to the first name, every time
+        assert {first_bite(None) for _ in range(5)} == {"Alpha"}
+
+
+# --- History-aware ranking tests ---
+
+
+class TestHistoryPenalties:
+    """Recently eaten foods rank below fresh ones of equal value."""
+
+    def test_penalty_decays_with_age_and_lookback(self) -> None:
+        history = [
+            {"Name": "Stew"},
+            {"Name": "Apple"},
+            {"Name": "Bread"},
+            {"Name": "apple"},
+        ]
+
+        penalties = history_penalties(history, lookback=3, strength=1.0)
+
+        # Newest Apple wins over the older one; Stew is outside the window
+        assert penalties.keys() == {"apple", "bread"}
+        assert penalties["apple"] == 1.0
+        assert math.isclose(penalties["bread"], 2 / 3)
+
+    def test_no_history_no_penalties(self) -> None:
+        assert history_penalties([]) == {}
+
+    def test_recent_food_loses_tie_to_fresh_one(self) -> None:
+        manager = DummyManager(
+            [make_food("Alpha", calories=500), make_food("Zeta", calories=500)]
+        )
+        history = [
+            {"Name": "Alpha", "Calories": 500, "Timestamp": "2024-05-01T12:00:00"}
+        ]
+
+        food, _ = _choose_next_bite(
+            manager,
+            remaining_calories=1000,
+            cravings=[],
+            cravings_satisfied=0,
+            recent_penalties=history_penalties(history),
+        )
+
+        # Without history the name tie-break would pick Alpha
+        assert food is not None
+        assert food.name == "Zeta"
+
+
+# --- explain_why_not tests ---
+
+
+class TestExplainWhyNot:
+    """Reports on why a named food was not the next bite."""
+
+    @staticmethod
+    def _manager() -> DummyManager:
+        return DummyManager(
+            [
+                make_food("Steak", 900, carbs=10, protein=18, fat=12, vitamins=8),
+                make_food("Gruel", 500, carbs=2),
+                make_food("Feast", 3000, carbs=20, protein=20, fat=20, vitamins=20),
+            ]
+        )
+
+    def test_low_sp_food_is_below_best(self) -> None:
+        report = explain_why_not(self._manager(), "gruel", 2000, [], 0)
+
+        assert report.name == "Gruel"
+        assert report.excluded is None
+        assert report.best_name == "Steak"
+        assert report.rank_score < report.best_rank_score
+        assert report.gap > 0
+        assert report.in_tie_window == (report.gap <= TIEBREAK_SCORE_WINDOW_SP)
+        assert not report.picked
+
+    def test_best_food_is_picked(self) -> None:
+        report = explain_why_not(self._manager(), "Steak", 2000, [], 0)
+
+        assert report.picked
+        assert report.gap == 0
+
+    def test_over_budget_food_is_excluded(self) -> None:
+        report = explain_why_not(self._manager(), "Feast", 2000, [], 0)
+
+        assert report.excluded == "too large for the remaining budget"
+        assert report.rank_score is None
+        assert report.gap is None
+
+
+# --- tie_finalists tests ---
+
+
+class TestTieFinalists:
+    """Finalists behind the automatic pick, in selection order."""
+
+    def test_orders_by_primary_rank_then_proximity_then_name(self) -> None:
+        manager = DummyManager(
+            [
+                make_food("Zeta", 500),
+                make_food("Alpha", 500),
+                make_food("Feast", 1900),
+            ]
+        )
+        near = [(food, 1.0, 1.0) for food in manager.foods.values()]
+
+        finalists = tie_finalists(manager, near)
+
+        keys = [(f.primary_rank, f.proximity_bias) for f in finalists]
+        assert keys == sorted(keys, reverse=True)
+        names = [f.food.name for f in finalists]
+        assert names.index("Alpha") < names.index("Zeta")
+        assert {f.rank_score for f in finalists} == {1.0}
+
+    def test_first_finalist_is_the_chosen_bite(self) -> None:
+        manager = DummyManager(
+            [make_food(name, calories=500) for name in ("Zeta", "Alpha", "Mid")]
+        )
+        near = [(food, 0.0, 0.0) for food in manager.foods.values()]
+
+        food, _ = _choose_next_bite(manager, 1000, [], 0)
+
+        assert tie_finalists(manager, near)[0].food is food
+        assert food.name == "Alpha"
+
+
 # --- Proximity tie-breaking tests ---
 
 
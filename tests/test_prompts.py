@@ -1,7 +1,12 @@
 """Tests for interactive prompt functions."""
 
+import pytest
+
+from conftest import make_food
 from interface.prompts import (
     collect_user_constraints,
+    parse_whole_number,
+    prompt_bite_choice,
     prompt_current_calories,
     prompt_for_cravings_satisfied,
     prompt_for_tastiness,
@@ -10,6 +15,24 @@ from interface.prompts import (
 )
 
 
+class TestParseWholeNumber:
+    """Tests for parse_whole_number()."""
+
+    @pytest.mark.parametrize(
+        "text", ["2500", "2,500", "2 500", "2\u00a0500", "2'500", " 2500 "]
+    )
+    def test_accepts_thousands_separators(self, text: str) -> None:
+        assert parse_whole_number(text) == 2500
+
+    def test_accepts_repeated_groups(self) -> None:
+        assert parse_whole_number("1,200,000") == 1_200_000
+
+    @pytest.mark.parametrize("text", ["abc", "2.500", "25,00", "2,500 000", ""])
+    def test_rejects_invalid_or_ambiguous(self, text: str) -> None:
+        with pytest.raises(ValueError):
+            parse_whole_number(text)
+
+
 class TestPromptCurrentCalories:
     """Tests for prompt_current_calories()."""
 
@@ -24,6 +47,18 @@ class TestPromptCurrentCalories:
         monkeypatch.setattr("builtins.input", lambda _: next(responses))
         assert prompt_current_calories() == 0
 
+    def test_accepts_grouped_digits(self, monkeypatch) -> None:
+        """ "2,500" → 2500."""
+        monkeypatch.setattr("builtins.input", lambda _: "2,500")
+        assert prompt_current_calories() == 2500
+
+    def test_invalid_shows_expected_format(self, monkeypatch, capsys) -> None:
+        """ "lots" then "500" → 500, after a hint."""
+        responses = iter(["lots", "500"])
+        monkeypatch.setattr("builtins.input", lambda _: next(responses))
+        assert prompt_current_calories() == 500
+        assert "e.g. 2500 or 2,500" in capsys.readouterr().out
+
 
 class TestPromptMaxCalories:
     """Tests for prompt_max_calories()."""
@@ -68,6 +103,11 @@ class TestPromptForTastiness:
         monkeypatch.setattr("builtins.input", lambda _: next(responses))
         assert prompt_for_tastiness("Test Food") == 2
 
+    def test_skip_keeps_current_rating(self, monkeypatch) -> None:
+        """ "" with current=-1 → -1."""
+        monkeypatch.setattr("builtins.input", lambda _: "")
+        assert prompt_for_tastiness("Test Food", current=-1) == -1
+
 
 class TestPromptYesNo:
     """Tests for prompt_yes_no()."""
@@ -111,3 +151,26 @@ class TestCollectUserConstraints:
         assert cravings == []
         assert cravings_satisfied == 0
         assert remaining == 2000
+
+
+class TestPromptBiteChoice:
+    """Tests for prompt_bite_choice()."""
+
+    CANDIDATES = [
+        (make_food("Bannock", calories=600), 1.5),
+        (make_food("Crimson Salad", calories=1100), 0.8),
+    ]
+
+    def test_enter_takes_recommendation(self, monkeypatch) -> None:
+        monkeypatch.setattr("builtins.input", lambda _: "")
+        assert prompt_bite_choice(self.CANDIDATES).name == "Bannock"
+
+    def test_number_then_retry_on_invalid(self, monkeypatch, capsys) -> None:
+        responses = iter(["7", "2"])
+        monkeypatch.setattr("builtins.input", lambda _: next(responses))
+        assert prompt_bite_choice(self.CANDIDATES).name == "Crimson Salad"
+        assert "ΔSP +1.50  (recommended)" in capsys.readouterr().out
+
+    def test_q_stops(self, monkeypatch) -> None:
+        monkeypatch.setattr("builtins.input", lambda _: "q")
+        assert prompt_bite_choice(self.CANDIDATES) is None
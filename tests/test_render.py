@@ -1,12 +1,55 @@
 """Tests for meal plan display rendering."""
 
-from models.plan import MealPlanItem
-from interface.render import display_meal_plan
+import re
+
+import pytest
+
+from calculations import _unique_variety_names, get_sp, sp_breakdown
+from conftest import make_food
+from models.plan import MealPlanItem, UnmetCraving
+from planner import STOP_BUDGET, STOP_ITERATION_CAP, fmt_signed
+from interface.render import (
+    aggregate_plan,
+    display_meal_plan,
+    display_sp_formula,
+    meal_plan_to_markdown,
+)
+
+
+@pytest.mark.parametrize(
+    "value, precision, expected",
+    [
+        (1.23456, 0, "+1"),
+        (1.23456, 2, "+1.23"),
+        (-0.45678, 2, "-0.46"),
+        (-0.45678, 4, "-0.4568"),
+        (0.0, 3, "+0.000"),
+    ],
+)
+def test_fmt_signed_precision(value: float, precision: int, expected: str) -> None:
+    assert fmt_signed(value, precision) == expected
 
 
 class TestDisplayMealPlan:
     """Tests for display_meal_plan()."""
 
+    def test_iteration_cap_note(self, capsys) -> None:
+        """A plan cut short by the iteration cap says so below the table."""
+        item = MealPlanItem(
+            name="Crumb",
+            calories=150,
+            sp_gain=0.5,
+            new_sp=12.5,
+            craving=False,
+            variety_delta_pp=0.0,
+            tastiness_delta_pp=0.0,
+        )
+        display_meal_plan([item], stop_reason=STOP_ITERATION_CAP)
+        assert "iteration cap" in capsys.readouterr().out
+
+        display_meal_plan([item], stop_reason=STOP_BUDGET)
+        assert "iteration cap" not in capsys.readouterr().out
+
     def test_display_empty_plan(self, capsys) -> None:
         """Prints 'No meal plan generated.' for empty plan."""
         display_meal_plan([])
@@ -58,3 +101,163 @@ class TestDisplayMealPlan:
         display_meal_plan([item], notices=["Invalid craving: Pizza"])
         output = capsys.readouterr().out
         assert "Note: Invalid craving: Pizza" in output
+
+    def test_unmet_cravings_listed_below_table(self, capsys) -> None:
+        item = MealPlanItem("Bread", 500, 1.0, 13.0, False, 0.0)
+
+        display_meal_plan([item], unmet_cravings=[UnmetCraving("Pie", "no stock left")])
+
+        output = capsys.readouterr().out
+        assert output.index("Unsatisfied cravings:") > output.index("=====")
+        assert "  - Pie: no stock left" in output
+
+    def test_show_macros_columns(self, capsys) -> None:
+        """--show-macros adds density and balance bonus per row."""
+        item = MealPlanItem(
+            name="Bannock",
+            calories=600,
+            sp_gain=1.0,
+            new_sp=13.0,
+            craving=False,
+            variety_delta_pp=0.0,
+            density={"carbs": 12.0, "protein": 3.0, "fat": 8.0, "vitamins": 0.0},
+            balance_bonus_pp=-50.0,
+        )
+
+        display_meal_plan([item])
+        assert "Bal" not in capsys.readouterr().out
+
+        display_meal_plan([item], show_macros=True)
+        output = capsys.readouterr().out
+        assert "C  12.0 P   3.0 F   8.0 V   0.0" in output
+        assert "Bal -50.00 pp" in output
+
+
+class TestAggregatePlan:
+    """Tests for aggregate_plan() and the aggregated display."""
+
+    @staticmethod
+    def _plan() -> list[MealPlanItem]:
+        return [
+            MealPlanItem("Apple", 90, 1.0, 13.0, False, 0.0),
+            MealPlanItem("Apple", 90, 0.5, 13.5, False, 0.0),
+            MealPlanItem("Apple", 90, 0.25, 13.75, False, 0.0),
+            MealPlanItem("Bannock", 600, 2.0, 15.75, False, 0.0),
+        ]
+
+    def test_three_apples_collapse_into_one_row(self) -> None:
+        rows = aggregate_plan(self._plan())
+
+        assert [(row.name, row.count) for row in rows] == [
+            ("Apple", 3),
+            ("Bannock", 1),
+        ]
+        assert rows[0].calories == 270
+        assert rows[0].sp_gain == 1.75
+        assert rows[0].new_sp == 13.75
+
+    def test_display_shows_count_only_when_aggregated(self, capsys) -> None:
+        display_meal_plan(self._plan(), aggregate=True)
+        output = capsys.readouterr().out
+        assert "Apple ×3" in output
+        assert "270 cal" in output
+        assert "Bannock ×" not in output
+
+        display_meal_plan(self._plan())
+        assert capsys.readouterr().out.count("Apple") == 3
+
+
+class TestMealPlanToMarkdown:
+    """Tests for meal_plan_to_markdown()."""
+
+    def test_header_and_one_row_per_bite(self) -> None:
+        plan = [
+            MealPlanItem("Bannock", 600, 1.5, 13.5, False, 0.0),
+            MealPlanItem("Elk Wellington", 1400, 3.0, 16.5, True, 0.0),
+            MealPlanItem("Bannock", 600, 0.5, 17.0, False, 0.0),
+        ]
+
+        markdown = meal_plan_to_markdown(plan)
+
+        lines = markdown.splitlines()
+        assert lines[0] == "| # | Food | Calories | SP gain | Total SP | Tags |"
+        body = [line for line in lines[2:] if line.startswith("|")]
+        assert len(body) == 3
+        assert "Craving Satisfied" in body[1]
+        assert "3 bites, 2600 cal" in lines[-1]
+
+    def test_empty_plan(self) -> None:
+        assert meal_plan_to_markdown([]) == "No meal plan generated."
+
+    def test_iteration_cap_note(self) -> None:
+        item = MealPlanItem(
+            name="Crumb",
+            calories=150,
+            sp_gain=0.5,
+            new_sp=12.5,
+            craving=False,
+            variety_delta_pp=0.0,
+            tastiness_delta_pp=0.0,
+        )
+
+        capped = meal_plan_to_markdown([item], stop_reason=STOP_ITERATION_CAP)
+
+        assert capped.splitlines()[-1].startswith("Note: planning stopped")
+        assert "iteration cap" not in meal_plan_to_markdown([item])
+
+
+class TestDisplayPrecision:
+    """Tests for the precision argument of the plan renderers."""
+
+    ITEM = MealPlanItem(
+        name="Bannock",
+        calories=600,
+        sp_gain=1.23456,
+        new_sp=13.45678,
+        craving=False,
+        variety_delta_pp=0.0,
+        tastiness_delta_pp=0.0,
+    )
+
+    def test_meal_plan_default_two_decimals(self, capsys) -> None:
+        display_meal_plan([self.ITEM])
+        output = capsys.readouterr().out
+        assert "SP +1.23 ⇒ 13.46" in output
+
+    def test_meal_plan_custom_precision(self, capsys) -> None:
+        display_meal_plan([self.ITEM], precision=4)
+        assert "SP +1.2346 ⇒ 13.4568" in capsys.readouterr().out
+
+        display_meal_plan([self.ITEM], precision=0)
+        assert "SP +1 ⇒ 13" in capsys.readouterr().out
+
+    def test_markdown_custom_precision(self) -> None:
+        markdown = meal_plan_to_markdown([self.ITEM], precision=3)
+        assert "| +1.235 | 13.457 |" in markdown
+        assert "SP +1.235 ⇒ 13.457" in markdown.splitlines()[-1]
+
+
+class TestDisplaySpFormula:
+    """Tests for display_sp_formula()."""
+
+    def test_printed_terms_recompute_sp(self, capsys) -> None:
+        """The filled-in formula evaluates back to get_sp."""
+        stomach = {
+            make_food("Bannock", 600, carbs=12, protein=3, fat=8, tastiness=2): 4,
+            make_food("Salad", 1100, vitamins=22, tastiness=-1): 2,
+        }
+        variety = _unique_variety_names(stomach)
+        expected = get_sp(
+            stomach, [], 1, variety, server_mult=1.5, dinner_party_mult=2.0
+        )
+
+        display_sp_formula(
+            sp_breakdown(stomach, 1, variety, server_mult=1.5, dinner_party_mult=2.0)
+        )
+
+        filled = capsys.readouterr().out.splitlines()[1]
+        total, bal, var, taste, crav, party, base, server = (
+            float(n) for n in re.findall(r"[-+]?\d+\.\d+", filled)
+        )
+        recomputed = (total * (1 + bal + var + taste + crav) * party + base) * server
+        assert abs(recomputed - expected) < 1e-2
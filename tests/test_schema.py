@@ -0,0 +1,77 @@
+"""Tests for the food-state schema and whole-file validation."""
+
+import json
+
+from conftest import make_food
+from schema import (
+    FOOD_FIELDS,
+    food_json_schema,
+    validate_json,
+)
+
+# ---------------------------------------------------------------------------
+# TestValidateJson
+# ---------------------------------------------------------------------------
+
+
+class TestValidateJson:
+    """validate_json() reports every problem in one pass."""
+
+    def test_valid_file(self) -> None:
+        entries = [make_food(name, 600).to_dict() for name in ("Bannock", "Salad")]
+
+        assert validate_json(json.dumps(entries)) == []
+
+    def test_reports_all_errors_at_once(self) -> None:
+        bad_ranges = make_food("Bannock", 600).to_dict()
+        bad_ranges["Protein"] = -2
+        bad_ranges["Tastiness"] = 7
+        bad_types = make_food("Salad", 400).to_dict()
+        bad_types["Calories"] = "lots"
+        bad_types["Available"] = 1.5
+        del bad_types["Vitamins"]
+        unnamed = make_food("Stew", 900).to_dict()
+        del unnamed["Name"]
+        entries = [bad_ranges, bad_types, "Steak", unnamed]
+
+        errors = validate_json(json.dumps(entries))
+
+        assert errors == [
+            "Bannock: Protein is negative (-2)",
+            "Bannock: Tastiness 7 is not a valid rating",
+            "Salad: Calories must be a number (got string)",
+            "Salad: missing field 'Vitamins'",
+            "Salad: Available must be a whole number (got 1.5)",
+            "<entry 2>: expected an object but found string",
+            "<entry 3>: missing field 'Name'",
+        ]
+
+    def test_fats_alias_accepted(self) -> None:
+        entry = make_food("Bannock", 600).to_dict()
+        entry["Fats"] = entry.pop("Fat")
+
+        assert validate_json(json.dumps([entry])) == []
+
+    def test_top_level_problems(self) -> None:
+        assert validate_json("") == ["file is empty"]
+        assert validate_json('{"Name": "Bannock"}') == [
+            "expected a list of foods but found object"
+        ]
+        assert validate_json("[{]")[0].startswith("not valid JSON:")
+
+
+# ---------------------------------------------------------------------------
+# TestFoodJsonSchema
+# ---------------------------------------------------------------------------
+
+
+class TestFoodJsonSchema:
+    """food_json_schema() mirrors FOOD_FIELDS."""
+
+    def test_lists_every_field(self) -> None:
+        item = food_json_schema()["items"]
+
+        assert {spec.name for spec in FOOD_FIELDS} <= set(item["properties"])
+        assert "Fats" in item["properties"]
+        assert "Stomach" not in item["required"]
+        assert item["properties"]["Tastiness"]["enum"][-1] == 99
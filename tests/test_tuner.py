@@ -0,0 +1,1168 @@
+"""Tests for the knob tuner's scoring, frontier, and output helpers."""
+
+import csv
+import datetime
+import json
+import math
+import random
+from typing import Any
+
+import pytest
+
+from config import load_config
+from conftest import make_food
+from food_state_manager import FoodStateManager
+from tune.tuner import (
+    DEFAULT_RANGES,
+    KNOB_DECIMALS,
+    KNOB_NAMES,
+    METRIC_KEYS,
+    PERTURB_MIN_STEP_FRAC,
+    ProgressBar,
+    RANGE_LOW_CALORIE_THRESHOLD,
+    RANGE_SOFT_VARIETY_BIAS_STRENGTH,
+    SEARCH_COMPLETE,
+    SEARCH_PATIENCE,
+    SEARCH_TIME_LIMIT,
+    ScoreWeights,
+    baseline_plan,
+    baseline_theta,
+    budget_spread,
+    clamp_theta,
+    compare_plans,
+    config_defaults,
+    config_snippet,
+    evaluate_holdout,
+    evaluate_theta,
+    estimate_eta,
+    evaluate_theta_multi,
+    evaluate_warmstart,
+    format_duration,
+    freeze_ranges,
+    has_finite_metrics,
+    hill_climb,
+    hill_climb_pareto,
+    holdout_gap,
+    improves_within_tolerance,
+    is_dominated_by,
+    jitter_budgets,
+    load_best_knobs,
+    load_tuner_config,
+    main as tuner_main,
+    metric_bounds,
+    metric_key,
+    normalize_budget_weights,
+    parse_weights,
+    pareto_frontier,
+    perturb_theta,
+    plan_with_knobs,
+    rank_results,
+    read_csv,
+    reload_deps,
+    resume_results,
+    run_annealing,
+    run_random_search,
+    sample_theta,
+    select_balanced,
+    weighted_budget_average,
+    weighted_score,
+    write_best_json,
+    write_csv,
+    write_pareto_csv,
+)
+
+# ---------------------------------------------------------------------------
+# Helpers
+# ---------------------------------------------------------------------------
+
+
+@pytest.fixture(autouse=True)
+def _restore_planner_constants():
+    """Reload planner modules so tuner overrides don't leak into other tests."""
+    yield
+    reload_deps()
+
+
+def _sample_foods():
+    """Two-food catalog where a ~2000 kcal budget fits 1 or 2 Alphas."""
+    return [
+        make_food("Alpha", 1000, available=5),
+        make_food("Beta", 700, carbs=20, available=5),
+    ]
+
+
+def _tradeoff_foods():
+    """Catalog where the knobs trade SP against balance at 3000 kcal."""
+    return [
+        make_food("Bannock", 600, carbs=12, protein=3, fat=8, available=4),
+        make_food("Salad", 1100, carbs=5, vitamins=22, available=3),
+        make_food("Steak", 900, protein=20, fat=10, available=3),
+        make_food("Berries", 150, carbs=4, vitamins=6, available=8),
+        make_food("Nuts", 300, protein=6, fat=12, available=6),
+    ]
+
+
+def _make_result(
+    sp: float,
+    efficiency: float = 0.0,
+    variety: float = 0.0,
+    balance: float = 0.0,
+) -> dict[str, Any]:
+    """Build a minimal evaluation result with the given averaged metrics."""
+    return {
+        "theta": {
+            "SOFT_VARIETY_BIAS_STRENGTH": 1.0,
+            "LOW_CALORIE_THRESHOLD": 300,
+        },
+        "avg_final_sp": sp,
+        "avg_delta_sp_per_100kcal": efficiency,
+        "avg_variety_count": variety,
+        "avg_balance_ratio": balance,
+        "per_budget": [],
+    }
+
+
+# ---------------------------------------------------------------------------
+# TestWriteParetoCsv
+# ---------------------------------------------------------------------------
+
+
+class TestWriteParetoCsv:
+    """Pareto-only CSV export."""
+
+    def test_rows_match_frontier_and_one_balanced(self, tmp_path) -> None:
+        rows = [
+            _make_result(30.0, variety=1.0),
+            _make_result(20.0, variety=3.0),
+            _make_result(10.0, variety=0.5),  # dominated by both above
+            _make_result(25.0, variety=2.0, balance=0.5),
+        ]
+        pareto_indices = pareto_frontier(rows)
+        balanced_idx = select_balanced(rows, pareto_indices)
+        path = tmp_path / "pareto.csv"
+
+        write_pareto_csv(rows, pareto_indices, balanced_idx, path)
+
+        with open(path, newline="", encoding="utf-8") as f:
+            written = list(csv.DictReader(f))
+        assert len(written) == len(pareto_indices)
+        assert sum(1 for row in written if row["is_balanced"] == "True") == 1
+
+    def test_sorted_by_final_sp_descending(self, tmp_path) -> None:
+        rows = [
+            _make_result(20.0, variety=3.0),
+            _make_result(30.0, variety=1.0),
+        ]
+        path = tmp_path / "pareto.csv"
+
+        write_pareto_csv(rows, [0, 1], 0, path)
+
+        with open(path, newline="", encoding="utf-8") as f:
+            sps = [float(row["avg_final_sp"]) for row in csv.DictReader(f)]
+        assert sps == [30.0, 20.0]
+
+
+# ---------------------------------------------------------------------------
+# TestResumeCsv
+# ---------------------------------------------------------------------------
+
+
+class TestResumeCsv:
+    """Reading a prior results CSV back for --resume."""
+
+    def test_round_trip_preserves_knobs_and_metrics(self, tmp_path) -> None:
+        original = evaluate_theta(baseline_theta(), [2000], 0, foods=_sample_foods())
+        path = tmp_path / "results.csv"
+
+        write_csv([original], path)
+        (loaded,) = read_csv(path)
+
+        assert loaded["theta"] == pytest.approx(original["theta"])
+        assert loaded["avg_final_sp"] == pytest.approx(original["avg_final_sp"])
+        assert loaded["per_budget"] == original["per_budget"]
+
+    def test_csv_has_bites_and_balance_columns(self, tmp_path) -> None:
+        result = evaluate_theta(
+            baseline_theta(), [1000, 3000], 0, foods=_sample_foods()
+        )
+        path = tmp_path / "results.csv"
+
+        write_csv([result], path)
+
+        with open(path, newline="", encoding="utf-8") as f:
+            (row,) = list(csv.DictReader(f))
+        bites = [pb["bites"] for pb in result["per_budget"]]
+        assert float(row["avg_bites"]) == pytest.approx(sum(bites) / len(bites))
+        assert float(row["avg_balance_ratio"]) == pytest.approx(
+            result["avg_balance_ratio"]
+        )
+        assert result["avg_bites"] == pytest.approx(sum(bites) / len(bites))
+        assert read_csv(path)[0]["avg_bites"] == pytest.approx(result["avg_bites"])
+
+    def test_missing_knobs_filled_from_config(self, tmp_path) -> None:
+        path = tmp_path / "legacy.csv"
+        path.write_text(
+            "SOFT_VARIETY_BIAS_STRENGTH,avg_final_sp,per_budget\n2.5,40.0,[]\n",
+            encoding="utf-8",
+        )
+
+        (loaded,) = read_csv(path)
+
+        assert loaded["theta"]["SOFT_VARIETY_BIAS_STRENGTH"] == 2.5
+        assert loaded["theta"]["LOW_CALORIE_THRESHOLD"] == pytest.approx(
+            baseline_theta()["LOW_CALORIE_THRESHOLD"]
+        )
+
+    def test_resume_reevaluates_only_mismatched_budgets(self) -> None:
+        foods = _sample_foods()
+        matching = evaluate_theta(baseline_theta(), [2000], 0, foods=foods)
+        stale = {**matching, "avg_final_sp": -1.0, "per_budget": [{"budget": 900}]}
+
+        resumed = resume_results([matching, stale], [2000], 0, foods=foods)
+
+        assert resumed[0] is matching
+        assert resumed[1]["avg_final_sp"] == matching["avg_final_sp"]
+
+
+# ---------------------------------------------------------------------------
+# TestTunerConfig
+# ---------------------------------------------------------------------------
+
+
+class TestTunerConfig:
+    """Budgets, ranges, and search settings from a YAML file."""
+
+    SAMPLE = """\
+budgets: [3000, 6000]
+iters: 40
+seed: 7
+ranges:
+  SOFT_VARIETY_BIAS_STRENGTH: [2.0, 2.5]
+  LOW_CALORIE_THRESHOLD: [450, 300]
+hill_climb:
+  enabled: false
+  max_iterations: 5
+  factors: [0.8, 1.2]
+"""
+
+    def test_ranges_propagate_to_sampling(self, tmp_path) -> None:
+        path = tmp_path / "tuner.yml"
+        path.write_text(self.SAMPLE, encoding="utf-8")
+
+        config = load_tuner_config(path)
+        ranges = {**DEFAULT_RANGES, **config.ranges}
+        rng = random.Random(0)
+
+        assert config.ranges["LOW_CALORIE_THRESHOLD"] == (300.0, 450.0)
+        for _ in range(20):
+            theta = sample_theta(rng, ranges)
+            assert 2.0 <= theta["SOFT_VARIETY_BIAS_STRENGTH"] <= 2.5
+            assert 300 <= theta["LOW_CALORIE_THRESHOLD"] <= 450
+
+    def test_settings_become_argparse_defaults(self, tmp_path) -> None:
+        path = tmp_path / "tuner.yml"
+        path.write_text(self.SAMPLE, encoding="utf-8")
+
+        config = load_tuner_config(path)
+
+        assert config_defaults(config) == {
+            "budgets": "3000,6000",
+            "iters": 40,
+            "seed": 7,
+            "hill_climb": False,
+        }
+        assert config.hill_climb_factors == (0.8, 1.2)
+
+    def test_hill_climb_flag_overrides_config(
+        self, tmp_path, capsys, monkeypatch
+    ) -> None:
+        monkeypatch.setattr(
+            "tune.tuner._fresh_manager",
+            lambda foods: FoodStateManager(_sample_foods()),
+        )
+        path = tmp_path / "tuner.yml"
+        path.write_text(
+            "hill_climb:\n  enabled: false\n  max_iterations: 1\n", encoding="utf-8"
+        )
+        argv = [
+            *("--config", str(path)),
+            *"--iters 1 --budgets 2000".split(),
+            *("--csv", str(tmp_path / "trials.csv")),
+            *("--json", str(tmp_path / "best.json")),
+        ]
+
+        tuner_main(argv)
+        from_config = capsys.readouterr().out
+        tuner_main([*argv, "--hill-climb"])
+        from_flag = capsys.readouterr().out
+
+        assert "with hill climbing" not in from_config
+        assert "with hill climbing" in from_flag
+
+    def test_unknown_knob_rejected(self, tmp_path) -> None:
+        path = tmp_path / "tuner.yml"
+        path.write_text("ranges:\n  NOT_A_KNOB: [0, 1]\n", encoding="utf-8")
+
+        with pytest.raises(ValueError):
+            load_tuner_config(path)
+
+    def test_non_finite_range_rejected(self, tmp_path) -> None:
+        path = tmp_path / "tuner.yml"
+        path.write_text(
+            "ranges:\n  LOW_CALORIE_THRESHOLD: [0, .nan]\n", encoding="utf-8"
+        )
+
+        with pytest.raises(ValueError):
+            load_tuner_config(path)
+
+
+# ---------------------------------------------------------------------------
+# TestBudgetSpread
+# ---------------------------------------------------------------------------
+
+
+class TestBudgetSpread:
+    """Per-budget variance reported alongside the averages."""
+
+    def test_identical_budgets_have_zero_spread(self) -> None:
+        result = evaluate_theta(
+            baseline_theta(), [2000, 2000], 0, foods=_sample_foods()
+        )
+
+        assert result["std_final_sp"] == 0.0
+        assert result["std_variety_count"] == 0.0
+
+    def test_varied_budgets_have_positive_spread(self) -> None:
+        result = evaluate_theta(
+            baseline_theta(), [1000, 3000], 0, foods=_sample_foods()
+        )
+
+        assert result["std_final_sp"] > 0.0
+
+    def test_spread_of_known_values(self) -> None:
+        per_budget = [
+            {"final_sp": 10.0, "variety_count": 1},
+            {"final_sp": 20.0, "variety_count": 3},
+        ]
+
+        assert budget_spread(per_budget) == {
+            "std_final_sp": 5.0,
+            "std_variety_count": 1.0,
+        }
+
+
+# ---------------------------------------------------------------------------
+# TestBudgetWeights
+# ---------------------------------------------------------------------------
+
+
+class TestBudgetWeights:
+    """Weighted averaging across budgets."""
+
+    PER_BUDGET = [
+        {
+            "final_sp": 10.0,
+            "delta_sp_per_100kcal": 1.0,
+            "variety_count": 1,
+            "balance_ratio": 0.5,
+        },
+        {
+            "final_sp": 30.0,
+            "delta_sp_per_100kcal": 2.0,
+            "variety_count": 5,
+            "balance_ratio": 0.9,
+        },
+    ]
+
+    def test_skewed_weights_change_average(self) -> None:
+        uniform = weighted_budget_average(self.PER_BUDGET)
+        skewed = weighted_budget_average(self.PER_BUDGET, [1.0, 3.0])
+
+        assert uniform["avg_final_sp"] == 20.0
+        assert skewed["avg_final_sp"] == 25.0
+
+    def test_weights_are_normalized(self) -> None:
+        assert normalize_budget_weights([2.0, 6.0], 2) == [0.25, 0.75]
+        assert normalize_budget_weights(None, 4) == [0.25] * 4
+        with pytest.raises(ValueError):
+            normalize_budget_weights([1.0], 2)
+        with pytest.raises(ValueError):
+            normalize_budget_weights([0.0, 0.0], 2)
+
+    def test_evaluate_theta_uses_weights(self) -> None:
+        foods = _sample_foods()
+        uniform = evaluate_theta(baseline_theta(), [1000, 3000], 0, foods=foods)
+        skewed = evaluate_theta(
+            baseline_theta(), [1000, 3000], 0, foods=foods, budget_weights=[0, 1]
+        )
+
+        assert skewed["avg_final_sp"] == uniform["per_budget"][1]["final_sp"]
+        assert skewed["avg_final_sp"] != uniform["avg_final_sp"]
+
+
+# ---------------------------------------------------------------------------
+# TestWeightedScore
+# ---------------------------------------------------------------------------
+
+
+class TestWeightedScore:
+    """Weighted-sum ranking as an alternative to lexicographic order."""
+
+    def test_default_rank_is_sp_first(self) -> None:
+        high_sp = _make_result(30.0, variety=1.0)
+        high_variety = _make_result(29.9, variety=5.0)
+
+        ranked = rank_results([high_variety, high_sp])
+
+        assert ranked[0] is high_sp
+
+    def test_variety_weight_flips_winner(self) -> None:
+        high_sp = _make_result(30.0, variety=1.0)
+        high_variety = _make_result(20.0, variety=5.0)
+        results = [high_sp, high_variety]
+
+        sp_heavy = rank_results(results, ScoreWeights(sp=2.0, variety=1.0))
+        variety_heavy = rank_results(results, ScoreWeights(sp=1.0, variety=2.0))
+
+        assert sp_heavy[0] is high_sp
+        assert variety_heavy[0] is high_variety
+
+    def test_normalized_to_unit_range(self) -> None:
+        results = [
+            _make_result(10.0, 1.0, 2.0, 0.5),
+            _make_result(20.0, 2.0, 4.0, 1.0),
+        ]
+        bounds = metric_bounds(results)
+
+        assert weighted_score(results[0], ScoreWeights(), bounds) == 0.0
+        assert weighted_score(results[1], ScoreWeights(), bounds) == 4.0
+
+    def test_non_finite_result_ranks_last(self) -> None:
+        broken = _make_result(math.nan, variety=9.0)
+        results = [broken, _make_result(20.0), _make_result(10.0)]
+
+        assert rank_results(results)[-1] is broken
+        assert rank_results(results, ScoreWeights())[-1] is broken
+        assert pareto_frontier(results) == [1]
+
+    def test_nan_metric_key_sorts_below_every_number(self) -> None:
+        values = [1.0, math.nan, 3.0, -math.inf, 2.0]
+
+        ordered = sorted(values, key=metric_key, reverse=True)
+
+        assert ordered[:4] == [3.0, 2.0, 1.0, -math.inf]
+        assert math.isnan(ordered[-1])
+
+    def test_nan_weights_keep_input_order(self) -> None:
+        results = [_make_result(10.0), _make_result(30.0), _make_result(20.0)]
+
+        ranked = rank_results(results, ScoreWeights(sp=math.nan))
+
+        assert ranked == results
+
+    def test_zero_calorie_food_gives_finite_metrics(self) -> None:
+        foods = [
+            make_food("Water", 0, carbs=0, protein=0, fat=0, vitamins=0, available=5),
+            *_sample_foods(),
+        ]
+
+        result = evaluate_theta(baseline_theta(), [2000], 0, foods=foods)
+
+        assert has_finite_metrics(result)
+
+    def test_parse_weights(self) -> None:
+        assert parse_weights("1,0.5,2,0") == ScoreWeights(1.0, 0.5, 2.0, 0.0)
+        assert parse_weights("") == ScoreWeights()
+        with pytest.raises(ValueError):
+            parse_weights("1,2")
+
+
+# ---------------------------------------------------------------------------
+# TestObjective
+# ---------------------------------------------------------------------------
+
+
+class TestObjective:
+    """--objective picks which metric leads the ranking."""
+
+    RESULTS = [
+        _make_result(30.0, efficiency=1.0, variety=1.0, balance=0.1),
+        _make_result(20.0, efficiency=3.0, variety=2.0, balance=0.2),
+        _make_result(10.0, efficiency=2.0, variety=5.0, balance=0.3),
+        _make_result(5.0, efficiency=0.5, variety=3.0, balance=0.9),
+    ]
+
+    @pytest.mark.parametrize(
+        ("objective", "winner"),
+        [("sp", 0), ("efficiency", 1), ("variety", 2), ("balance", 3)],
+    )
+    def test_each_objective_changes_top_result(
+        self, objective: str, winner: int
+    ) -> None:
+        ranked = rank_results(self.RESULTS, objective=objective)
+
+        assert ranked[0] is self.RESULTS[winner]
+
+    def test_other_metrics_break_ties_in_default_order(self) -> None:
+        tied = [
+            _make_result(10.0, efficiency=1.0, variety=4.0),
+            _make_result(20.0, efficiency=1.0, variety=4.0),
+        ]
+
+        assert rank_results(tied, objective="variety")[0] is tied[1]
+
+    def test_emphasis_shifts_balanced_pick(self) -> None:
+        results = [
+            _make_result(10.0, variety=1.0),
+            _make_result(8.0, variety=1.5),
+            _make_result(3.0, variety=4.0),
+        ]
+
+        assert select_balanced(results, [0, 1, 2]) == 1
+        assert select_balanced(results, [0, 1, 2], emphasis="variety") == 2
+
+
+# ---------------------------------------------------------------------------
+# TestMultiSeed
+# ---------------------------------------------------------------------------
+
+
+class TestMultiSeed:
+    """Averaging knob metrics across budget-jitter seeds."""
+
+    def test_single_seed_matches_plain_evaluation(self) -> None:
+        theta = baseline_theta()
+        foods = _sample_foods()
+
+        single = evaluate_theta(theta, [2000], 0, foods=foods)
+        multi = evaluate_theta_multi(theta, [2000], 0, 1, foods=foods)
+
+        assert multi["avg_final_sp"] == single["avg_final_sp"]
+
+    def test_two_seeds_average_differs_from_each_run(self) -> None:
+        theta = baseline_theta()
+        foods = _sample_foods()
+        individual = [
+            evaluate_theta(theta, jitter_budgets([2000], k), 0, foods=foods)[
+                "avg_final_sp"
+            ]
+            for k in range(2)
+        ]
+
+        averaged = evaluate_theta_multi(theta, [2000], 0, 2, foods=foods)
+
+        assert averaged["avg_final_sp"] not in individual
+        assert averaged["avg_final_sp"] == pytest.approx(sum(individual) / 2)
+        assert averaged["std_final_sp_across_seeds"] > 0
+        assert averaged["per_budget"][0]["budget"] == 2000
+
+    def test_evaluation_does_not_mutate_catalog(self) -> None:
+        foods = _sample_foods()
+
+        evaluate_theta(baseline_theta(), [2000], 0, foods=foods)
+
+        assert all(food.stomach == 0 for food in foods)
+        assert all(food.available == 5 for food in foods)
+
+    def test_scarce_catalog_limits_bites(self) -> None:
+        def bites(available: int) -> int:
+            foods = [make_food("Only", 500, available=available)]
+            result = evaluate_theta(baseline_theta(), [3000], 0, foods=foods)
+            return result["per_budget"][0]["bites"]
+
+        assert bites(2) == 2
+        assert bites(50) == 6
+
+
+# ---------------------------------------------------------------------------
+# TestPatience
+# ---------------------------------------------------------------------------
+
+
+class TestPatience:
+    """Early stopping for random search (stale SP or time limit)."""
+
+    def test_flat_catalog_stops_after_patience(self) -> None:
+        # One food: every knob set plans the same meal, so SP never improves
+        foods = [make_food("Only", 500, available=50)]
+
+        results, reason = run_random_search(
+            [2000], DEFAULT_RANGES, 50, 0, foods=foods, patience=3
+        )
+
+        assert len(results) == 4
+        assert reason == SEARCH_PATIENCE
+
+    def test_no_patience_runs_every_iteration(self) -> None:
+        foods = [make_food("Only", 500, available=50)]
+
+        results, reason = run_random_search([2000], DEFAULT_RANGES, 5, 0, foods=foods)
+
+        assert len(results) == 5
+        assert reason == SEARCH_COMPLETE
+
+    def test_time_limit_stops_at_expected_iteration(self) -> None:
+        foods = [make_food("Only", 500, available=50)]
+        ticks = iter(range(100))  # one second per clock read
+
+        results, reason = run_random_search(
+            [2000],
+            DEFAULT_RANGES,
+            50,
+            0,
+            foods=foods,
+            progress=False,
+            max_seconds=2.5,
+            clock=lambda: next(ticks),
+        )
+
+        # Start reads 0; samples 1-3 read 1, 2, 3 and 3 > 2.5 stops the loop
+        assert len(results) == 3
+        assert reason == SEARCH_TIME_LIMIT
+
+    def test_iterations_win_when_reached_before_time_limit(self) -> None:
+        foods = [make_food("Only", 500, available=50)]
+
+        results, reason = run_random_search(
+            [2000], DEFAULT_RANGES, 4, 0, foods=foods, max_seconds=3600
+        )
+
+        assert len(results) == 4
+        assert reason == SEARCH_COMPLETE
+
+    def test_patience_follows_the_objective(self, monkeypatch) -> None:
+        # SP falls while variety keeps climbing
+        step = iter(range(100))
+
+        def evaluate(*args, **kwargs):
+            i = next(step)
+            return _make_result(10.0 - i, variety=float(i))
+
+        monkeypatch.setattr("tune.tuner.evaluate_theta_multi", evaluate)
+
+        by_sp, sp_reason = run_random_search(
+            [2000], DEFAULT_RANGES, 10, 0, patience=3, progress=False
+        )
+        by_variety, variety_reason = run_random_search(
+            [2000],
+            DEFAULT_RANGES,
+            10,
+            0,
+            patience=3,
+            progress=False,
+            objective="variety",
+        )
+
+        assert (len(by_sp), sp_reason) == (4, SEARCH_PATIENCE)
+        assert (len(by_variety), variety_reason) == (10, SEARCH_COMPLETE)
+
+    def test_patience_met_on_the_last_iteration_is_complete(self) -> None:
+        foods = [make_food("Only", 500, available=50)]
+
+        results, reason = run_random_search(
+            [2000], DEFAULT_RANGES, 4, 0, foods=foods, patience=3
+        )
+
+        assert len(results) == 4
+        assert reason == SEARCH_COMPLETE
+
+
+# ---------------------------------------------------------------------------
+# TestFreeze
+# ---------------------------------------------------------------------------
+
+
+class TestFreeze:
+    """Holding chosen knobs at their config values."""
+
+    FROZEN = ("LOW_CALORIE_THRESHOLD", "SOFT_VARIETY_BIAS_STRENGTH")
+
+    def test_frozen_knobs_equal_baseline_in_every_result(self) -> None:
+        baseline = baseline_theta()
+        ranges = freeze_ranges(DEFAULT_RANGES, self.FROZEN)
+
+        results, _ = run_random_search(
+            [2000], ranges, 6, 0, foods=_sample_foods(), progress=False
+        )
+        results.append(
+            hill_climb(
+                results[0],
+                [2000],
+                0,
+                ranges,
+                max_iterations=1,
+                frozen=self.FROZEN,
+            )
+        )
+        trajectory, _ = run_annealing(
+            [2000], ranges, 5, 0, foods=_sample_foods(), frozen=self.FROZEN
+        )
+        results += trajectory
+
+        for result in results:
+            for knob_name in self.FROZEN:
+                assert result["theta"][knob_name] == baseline[knob_name]
+        varied = {r["theta"]["PROXIMITY_APPROACH_WEIGHT"] for r in results}
+        assert len(varied) > 1
+
+    def test_unknown_knob_rejected(self) -> None:
+        with pytest.raises(ValueError, match="NOT_A_KNOB"):
+            freeze_ranges(DEFAULT_RANGES, ["NOT_A_KNOB"])
+
+
+# ---------------------------------------------------------------------------
+# TestParetoClimb
+# ---------------------------------------------------------------------------
+
+
+class TestParetoClimb:
+    """Hill climbing that accepts trade-offs within a tolerance."""
+
+    @staticmethod
+    def _front(results) -> set:
+        """Distinct metric tuples on the Pareto frontier of *results*."""
+        return {
+            tuple(results[i][key] for key in METRIC_KEYS)
+            for i in pareto_frontier(results)
+        }
+
+    def test_tolerance_allows_small_losses_only(self) -> None:
+        current = _make_result(10.0, efficiency=2.0, variety=3.0, balance=0.5)
+
+        trade = _make_result(11.0, efficiency=1.99, variety=3.0, balance=0.5)
+        too_costly = _make_result(11.0, efficiency=1.5, variety=3.0, balance=0.5)
+        no_gain = _make_result(10.0, efficiency=1.99, variety=3.0, balance=0.5)
+
+        assert improves_within_tolerance(current, trade, tolerance=0.02)
+        assert not improves_within_tolerance(current, too_costly, tolerance=0.02)
+        assert not improves_within_tolerance(current, no_gain, tolerance=0.02)
+
+    def test_help_shows_the_tolerance(self, capsys) -> None:
+        with pytest.raises(SystemExit):
+            tuner_main(["--help"])
+
+        help_text = " ".join(capsys.readouterr().out.split())
+        assert "more than 2% on the others" in help_text
+
+    def test_frontier_at_least_as_rich_as_dominance_climb(self) -> None:
+        foods = _tradeoff_foods()
+        rows, _ = run_random_search(
+            [3000], DEFAULT_RANGES, 4, 0, foods=foods, progress=False
+        )
+
+        classic = list(rows)
+        steps = []
+        for row in rows:
+            refined = hill_climb(
+                row, [3000], 0, DEFAULT_RANGES, max_iterations=1, foods=foods
+            )
+            if is_dominated_by(row, refined):
+                classic.append(refined)
+            steps += hill_climb_pareto(
+                row, [3000], 0, DEFAULT_RANGES, max_iterations=1, foods=foods
+            )
+
+        assert steps
+        assert len(self._front(rows + steps)) >= len(self._front(classic))
+
+
+# ---------------------------------------------------------------------------
+# TestHoldout
+# ---------------------------------------------------------------------------
+
+
+class TestHoldout:
+    """Re-evaluating the final pick on budgets kept out of tuning."""
+
+    def test_evaluates_only_the_holdout_budgets(self) -> None:
+        train = evaluate_theta(baseline_theta(), [2000], 0, foods=_sample_foods())
+
+        holdout = evaluate_holdout(train, [1200, 3000], 0, foods=_sample_foods())
+
+        assert [pb["budget"] for pb in holdout["per_budget"]] == [1200, 3000]
+        assert holdout["sp_gap"] == pytest.approx(holdout_gap(train, holdout))
+
+    def test_gap_is_relative_sp_drop(self) -> None:
+        assert holdout_gap({"avg_final_sp": 20.0}, {"avg_final_sp": 15.0}) == 0.25
+        assert holdout_gap({"avg_final_sp": 0.0}, {"avg_final_sp": 5.0}) == 0.0
+
+    def test_reported_apart_from_training_metrics(self, tmp_path, monkeypatch) -> None:
+        monkeypatch.setattr(
+            "tune.tuner._fresh_manager",
+            lambda foods: FoodStateManager(_sample_foods()),
+        )
+        json_path = tmp_path / "best.json"
+
+        tuner_main(
+            [
+                *"--iters 2 --no-hill-climb --budgets 2000 -q".split(),
+                *("--holdout", "1200,3000"),
+                *("--csv", str(tmp_path / "trials.csv")),
+                *("--json", str(json_path)),
+            ]
+        )
+
+        payload = json.loads(json_path.read_text(encoding="utf-8"))
+        assert [pb["budget"] for pb in payload["per_budget"]] == [2000]
+        assert payload["holdout"]["budgets"] == [1200, 3000]
+        assert [pb["budget"] for pb in payload["holdout"]["per_budget"]] == [
+            1200,
+            3000,
+        ]
+        assert set(payload["holdout"]["metrics"]) <= set(payload["metrics"])
+
+
+# ---------------------------------------------------------------------------
+# TestBaselinePlan
+# ---------------------------------------------------------------------------
+
+
+class TestBaselinePlan:
+    """The baseline's bite sequence for the largest budget."""
+
+    def test_plan_fits_the_largest_budget(self) -> None:
+        plan = baseline_plan([1200, 2500], foods=_sample_foods())
+
+        assert plan
+        assert sum(item.calories for item in plan) <= 2500
+        assert {item.name for item in plan} <= {"Alpha", "Beta"}
+
+    def test_flag_prints_the_bites(self, tmp_path, capsys, monkeypatch) -> None:
+        monkeypatch.setattr(
+            "tune.tuner._fresh_manager",
+            lambda foods: FoodStateManager(_sample_foods()),
+        )
+
+        tuner_main(
+            [
+                *"--iters 1 --no-hill-climb --budgets 1200,2500 -q".split(),
+                "--print-baseline-plan",
+                *("--csv", str(tmp_path / "trials.csv")),
+                *("--json", str(tmp_path / "best.json")),
+            ]
+        )
+
+        out = capsys.readouterr().out
+        assert "Baseline plan at 2500 cal:" in out
+        assert "MEAL PLAN" in out
+
+
+# ---------------------------------------------------------------------------
+# TestWarmstart
+# ---------------------------------------------------------------------------
+
+
+class TestWarmstart:
+    """--warmstart evaluates a prior best-result JSON's knobs first."""
+
+    def test_missing_knobs_keep_config_values(self, tmp_path, capsys) -> None:
+        path = tmp_path / "best.json"
+        path.write_text('{"knobs": {"LOW_CALORIE_THRESHOLD": 900}}')
+
+        warm = evaluate_warmstart(
+            path, [2000], 0, DEFAULT_RANGES, foods=_sample_foods()
+        )
+
+        expected = {**baseline_theta(), "LOW_CALORIE_THRESHOLD": 900.0}
+        assert warm["theta"] == pytest.approx(
+            clamp_theta(expected, DEFAULT_RANGES)
+        )
+        assert warm["theta"]["LOW_CALORIE_THRESHOLD"] == 500.0
+        warnings = [
+            line for line in capsys.readouterr().out.splitlines() if "[WARN]" in line
+        ]
+        assert warnings == [
+            "[WARN] Warm-start LOW_CALORIE_THRESHOLD clamped into its range: "
+            "900 -> 500"
+        ]
+
+    def test_warm_start_knobs_appear_in_results(self, tmp_path, monkeypatch) -> None:
+        monkeypatch.setattr(
+            "tune.tuner._fresh_manager",
+            lambda foods: FoodStateManager(_sample_foods()),
+        )
+        best_path = tmp_path / "prior.json"
+        best_path.write_text('{"knobs": {"LOW_CALORIE_THRESHOLD": 437}}')
+        csv_path = tmp_path / "trials.csv"
+
+        tuner_main(
+            [
+                *"--iters 2 --no-hill-climb --budgets 2000 -q".split(),
+                *("--warmstart", str(best_path)),
+                *("--csv", str(csv_path)),
+                *("--json", str(tmp_path / "best.json")),
+            ]
+        )
+
+        thresholds = [r["theta"]["LOW_CALORIE_THRESHOLD"] for r in read_csv(csv_path)]
+        assert 437.0 in thresholds
+        assert len(thresholds) == 3
+
+    def test_unreadable_file_exits(self, tmp_path, monkeypatch) -> None:
+        monkeypatch.setattr(
+            "tune.tuner._fresh_manager",
+            lambda foods: FoodStateManager(_sample_foods()),
+        )
+        with pytest.raises(SystemExit, match="Cannot warm-start"):
+            tuner_main(
+                [
+                    *"--iters 1 --budgets 2000 -q".split(),
+                    *("--warmstart", str(tmp_path / "missing.json")),
+                    *("--csv", str(tmp_path / "trials.csv")),
+                    *("--json", str(tmp_path / "best.json")),
+                ]
+            )
+
+
+# ---------------------------------------------------------------------------
+# TestQuiet
+# ---------------------------------------------------------------------------
+
+
+class TestQuiet:
+    """--quiet leaves only the top-candidates block on stdout."""
+
+    def test_quiet_prints_only_top_candidates(
+        self, tmp_path, capsys, monkeypatch
+    ) -> None:
+        monkeypatch.setattr(
+            "tune.tuner._fresh_manager",
+            lambda foods: FoodStateManager(_sample_foods()),
+        )
+        argv = [
+            *"--iters 3 --budgets 2000 --topk 2".split(),
+            *("--csv", str(tmp_path / "trials.csv")),
+            *("--json", str(tmp_path / "best.json")),
+        ]
+
+        tuner_main(argv)
+        loud = capsys.readouterr().out
+        tuner_main([*argv, "--quiet"])
+        quiet = capsys.readouterr().out
+
+        assert "Baseline" in loud and "% complete" in loud
+        lines = quiet.strip().splitlines()
+        assert lines[0].startswith("Top candidates")
+        assert len(lines) == 3
+        assert (tmp_path / "best.json").exists()
+
+
+# ---------------------------------------------------------------------------
+# TestProgress
+# ---------------------------------------------------------------------------
+
+
+class TestProgress:
+    """ETA helper and the throttled progress bar."""
+
+    def test_eta_uses_average_rate(self) -> None:
+        assert estimate_eta(25, 100, 10.0) == pytest.approx(30.0)
+        assert estimate_eta(100, 100, 40.0) == 0.0
+
+    def test_eta_unknown_before_first_iteration(self) -> None:
+        assert estimate_eta(0, 100, 5.0) is None
+        assert format_duration(None) == "--:--"
+
+    def test_format_duration(self) -> None:
+        assert format_duration(75.4) == "1:15"
+        assert format_duration(3725) == "1:02:05"
+
+    def test_updates_are_throttled(self, capsys) -> None:
+        now = [0.0]
+        bar = ProgressBar(10, clock=lambda: now[0], min_interval=1.0)
+
+        for i in range(1, 6):
+            now[0] = i * 0.3
+            bar.update(i)
+        bar.close()
+
+        out = capsys.readouterr().out
+        # Draws at 0.3s and 1.5s, then the final line from close()
+        assert out.count("\r") == 3
+        assert out.rstrip().endswith("5/10 3.3 it/s ETA 0:02")
+
+
+# ---------------------------------------------------------------------------
+# TestConfigSnippet
+# ---------------------------------------------------------------------------
+
+
+class TestConfigSnippet:
+    """Tuned knobs rendered as a config.yml block."""
+
+    KNOBS = {
+        "SOFT_VARIETY_BIAS_STRENGTH": 2.718281,
+        "LOW_CALORIE_THRESHOLD": 412.6,
+        "REPETITION_PENALTY_STRENGTH": 0.5,
+    }
+
+    def test_snippet_loads_as_config(self, tmp_path) -> None:
+        path = tmp_path / "config.yml"
+        path.write_text(config_snippet(self.KNOBS))
+
+        algorithm = load_config(path).algorithm
+
+        assert algorithm.soft_variety_bias_strength == 2.7183
+        assert algorithm.low_calorie_threshold == 413
+        assert algorithm.repetition_penalty_strength == 0.5
+
+    def test_header_is_dated(self) -> None:
+        snippet = config_snippet(
+            self.KNOBS, source="best.json", today=datetime.date(2025, 3, 1)
+        )
+
+        assert snippet.startswith("# Tuned knobs from best.json (2025-03-01)")
+        assert "  low_calorie_threshold: 413\n" in snippet
+
+    def test_emit_config_flag_writes_snippet(self, tmp_path, monkeypatch) -> None:
+        monkeypatch.setattr(
+            "tune.tuner._fresh_manager",
+            lambda foods: FoodStateManager(_sample_foods()),
+        )
+        snippet_path = tmp_path / "knobs.yml"
+
+        tuner_main(
+            [
+                *"--iters 2 --budgets 2000 --quiet".split(),
+                *("--csv", str(tmp_path / "trials.csv")),
+                *("--json", str(tmp_path / "best.json")),
+                *("--emit-config", str(snippet_path)),
+            ]
+        )
+
+        best = json.loads((tmp_path / "best.json").read_text())["knobs"]
+        algorithm = load_config(snippet_path).algorithm
+        assert algorithm.soft_variety_bias_strength == pytest.approx(
+            best["SOFT_VARIETY_BIAS_STRENGTH"], abs=1e-4
+        )
+        assert algorithm.low_calorie_threshold == round(best["LOW_CALORIE_THRESHOLD"])
+
+
+# ---------------------------------------------------------------------------
+# TestAnnealing
+# ---------------------------------------------------------------------------
+
+
+class TestAnnealing:
+    """Simulated annealing search over the knobs."""
+
+    RANGES = {
+        "SOFT_VARIETY_BIAS_STRENGTH": RANGE_SOFT_VARIETY_BIAS_STRENGTH,
+        "LOW_CALORIE_THRESHOLD": RANGE_LOW_CALORIE_THRESHOLD,
+    }
+
+    def test_thetas_stay_within_ranges(self) -> None:
+        trajectory, reason = run_annealing(
+            [2000], self.RANGES, 15, 0, foods=_sample_foods()
+        )
+
+        assert len(trajectory) == 16
+        assert reason == SEARCH_COMPLETE
+        for result in trajectory:
+            for knob_name, (lo, hi) in self.RANGES.items():
+                assert lo <= result["theta"][knob_name] <= hi
+
+    def test_best_beats_baseline(self) -> None:
+        trajectory, _ = run_annealing(
+            [6000], self.RANGES, 10, 1, foods=_tradeoff_foods()
+        )
+
+        start_sp = trajectory[0]["avg_final_sp"]
+        assert max(r["avg_final_sp"] for r in trajectory) > start_sp
+        assert trajectory[0]["theta"]["LOW_CALORIE_THRESHOLD"] == pytest.approx(
+            baseline_theta()["LOW_CALORIE_THRESHOLD"]
+        )
+
+    def test_knob_at_zero_still_moves(self) -> None:
+        ranges = {"SOFT_VARIETY_BIAS_STRENGTH": (0.0, 6.0)}
+        theta = {"SOFT_VARIETY_BIAS_STRENGTH": 0.0}
+
+        up = perturb_theta(
+            theta,
+            "SOFT_VARIETY_BIAS_STRENGTH",
+            1.1,
+            ranges,
+            min_step_frac=PERTURB_MIN_STEP_FRAC,
+        )
+        down = perturb_theta(
+            theta,
+            "SOFT_VARIETY_BIAS_STRENGTH",
+            0.9,
+            ranges,
+            min_step_frac=PERTURB_MIN_STEP_FRAC,
+        )
+        unfloored = perturb_theta(theta, "SOFT_VARIETY_BIAS_STRENGTH", 1.1, ranges)
+
+        assert up["SOFT_VARIETY_BIAS_STRENGTH"] == pytest.approx(0.3)
+        assert down["SOFT_VARIETY_BIAS_STRENGTH"] == 0.0
+        assert unfloored["SOFT_VARIETY_BIAS_STRENGTH"] == 0.0
+
+
+# ---------------------------------------------------------------------------
+# TestComparePlans
+# ---------------------------------------------------------------------------
+
+
+class TestComparePlans:
+    """Default vs tuned knob plans from the same starting state."""
+
+    TUNED = {"LOW_CALORIE_THRESHOLD": 900.0, "SOFT_VARIETY_BIAS_STRENGTH": 0.0}
+
+    def test_both_plans_respect_budget(self) -> None:
+        manager = FoodStateManager(_sample_foods())
+
+        comparison = compare_plans(manager, self.TUNED, [], 0, 2000)
+
+        for stats in comparison.values():
+            assert 0 < stats["calories"] <= 2000
+            assert stats["bites"] > 0
+
+    def test_plans_start_from_the_same_untouched_state(self) -> None:
+        manager = FoodStateManager(_sample_foods())
+
+        comparison = compare_plans(manager, baseline_theta(), [], 0, 2000)
+
+        assert comparison["default"] == comparison["tuned"]
+        assert sum(manager.stomach.values()) == 0
+        assert all(count == 5 for count in manager.available.values())
+
+    def test_knobs_are_restored_after_planning(self) -> None:
+        import constants
+        import planner
+
+        plan_with_knobs(
+            FoodStateManager(_sample_foods()), self.TUNED, [], 0, 2000
+        )
+
+        assert planner.LOW_CALORIE_THRESHOLD == constants.LOW_CALORIE_THRESHOLD
+
+    def test_load_best_knobs_reads_tuner_output(self, tmp_path) -> None:
+        path = tmp_path / "best.json"
+        path.write_text('{"knobs": {"LOW_CALORIE_THRESHOLD": 400}}')
+
+        assert load_best_knobs(path) == {"LOW_CALORIE_THRESHOLD": 400.0}
+
+    def test_best_json_round_trip(self, tmp_path) -> None:
+        path = tmp_path / "best.json"
+        theta = sample_theta(random.Random(11), DEFAULT_RANGES)
+        payload = {"knobs": theta, "metrics": {"avg_final_sp": 50.0}}
+
+        write_best_json(payload, path)
+        loaded = load_best_knobs(path)
+
+        assert list(loaded) == list(KNOB_NAMES)
+        # Written rounded: the int knob to 1, the rest to KNOB_DECIMALS places
+        for knob_name, value in theta.items():
+            if knob_name == "LOW_CALORIE_THRESHOLD":
+                tolerance = 0.5
+            else:
+                tolerance = 0.5 * 10**-KNOB_DECIMALS
+            assert abs(loaded[knob_name] - value) <= tolerance + 1e-12
+        assert json.loads(path.read_text())["metrics"] == {"avg_final_sp": 50.0}
+
+    def test_load_best_knobs_rejects_unknown_knob(self, tmp_path) -> None:
+        path = tmp_path / "best.json"
+        path.write_text('{"knobs": {"NOT_A_KNOB": 1}}')
+
+        with pytest.raises(ValueError):
+            load_best_knobs(path)
@@ -24,17 +24,24 @@ from __future__ import annotations
 
 import argparse
 import builtins
+import copy
 import csv
+import datetime
 import importlib
 import json
+import math
 import random
 import sys
+import time
 from contextlib import contextmanager
+from dataclasses import dataclass, field
 from pathlib import Path
 from types import ModuleType
-from collections.abc import Iterator
+from collections.abc import Callable, Iterator
 from typing import Any, Dict, Iterable, List, Tuple
 
+import yaml
+
 # Ensure the project root (parent of `tune/`) is importable BEFORE any project imports.
 PROJECT_ROOT = Path(__file__).resolve().parents[1]
 if str(PROJECT_ROOT) not in sys.path:
@@ -81,6 +88,10 @@ def _import_persistence() -> ModuleType:
 # Bind once so it's available at runtime.
 persistence = _import_persistence()
 
+from food_state_manager import FoodStateManager  # noqa: E402
+from interface.render import display_meal_plan, parse_precision  # noqa: E402
+from models.food import Food  # noqa: E402
+
 # -----------------------
 # Tunable default ranges
 # -----------------------
@@ -98,10 +109,49 @@ RANGE_LOW_CALORIE_PENALTY_STRENGTH: Tuple[float, float] = (0.0, 4.0)
 RANGE_BALANCED_DIET_IMPROVEMENT_STRENGTH: Tuple[float, float] = (0.0, 3.0)
 RANGE_REPETITION_PENALTY_STRENGTH: Tuple[float, float] = (0.0, 2.0)
 
+#: Default ranges keyed by knob name.
+DEFAULT_RANGES: Dict[str, Tuple[float, float]] = {
+    "SOFT_VARIETY_BIAS_STRENGTH": RANGE_SOFT_VARIETY_BIAS_STRENGTH,
+    "PROXIMITY_APPROACH_WEIGHT": RANGE_PROXIMITY_APPROACH_WEIGHT,
+    "PROXIMITY_OVERSHOOT_PENALTY": RANGE_PROXIMITY_OVERSHOOT_PENALTY,
+    "TIEBREAK_SCORE_WINDOW_SP": RANGE_TIEBREAK_SCORE_WINDOW_SP,
+    "LOW_CALORIE_THRESHOLD": RANGE_LOW_CALORIE_THRESHOLD,
+    "LOW_CALORIE_PENALTY_STRENGTH": RANGE_LOW_CALORIE_PENALTY_STRENGTH,
+    "BALANCED_DIET_IMPROVEMENT_STRENGTH": RANGE_BALANCED_DIET_IMPROVEMENT_STRENGTH,
+    "REPETITION_PENALTY_STRENGTH": RANGE_REPETITION_PENALTY_STRENGTH,
+}
+
+#: Max relative budget jitter applied per seed in multi-seed runs.
+BUDGET_JITTER_FRAC: float = 0.05
+
+#: Relative train/holdout SP drop that flags a pick as likely overfit.
+HOLDOUT_GAP_WARN_FRAC: float = 0.10
+
+#: Simulated annealing defaults (temperature in SP units)
+ANNEALING_INITIAL_TEMPERATURE: float = 1.0
+ANNEALING_COOLING: float = 0.95
+ANNEALING_FACTOR_RANGE: Tuple[float, float] = (0.7, 1.3)
+
 #: Hill climbing defaults
 HILL_CLIMB_MAX_ITERATIONS: int = 20
 HILL_CLIMB_FACTORS: Tuple[float, ...] = (0.9, 0.95, 1.05, 1.1)
 
+#: Why a search returned: every iteration ran, SP went flat, or time ran out
+SEARCH_COMPLETE: str = "complete"
+SEARCH_PATIENCE: str = "patience"
+SEARCH_TIME_LIMIT: str = "time_limit"
+
+#: Smallest annealing step as a fraction of the knob's range, so a knob
+#: at 0 (where scaling does nothing) can still move
+PERTURB_MIN_STEP_FRAC: float = 0.05
+
+#: Relative loss per metric that a Pareto climb step may accept
+PARETO_CLIMB_TOLERANCE: float = 0.02
+
+#: Progress bar width (cells) and minimum seconds between redraws
+PROGRESS_BAR_WIDTH: int = 20
+PROGRESS_MIN_INTERVAL: float = 0.25
+
 
 @contextmanager
 def override_constants(
@@ -276,21 +326,48 @@ def count_hard_variety(
     return c
 
 
+#: Tunable knob names, in stable output order.
+KNOB_NAMES: Tuple[str, ...] = (
+    "SOFT_VARIETY_BIAS_STRENGTH",
+    "PROXIMITY_APPROACH_WEIGHT",
+    "PROXIMITY_OVERSHOOT_PENALTY",
+    "TIEBREAK_SCORE_WINDOW_SP",
+    "LOW_CALORIE_THRESHOLD",
+    "LOW_CALORIE_PENALTY_STRENGTH",
+    "BALANCED_DIET_IMPROVEMENT_STRENGTH",
+    "REPETITION_PENALTY_STRENGTH",
+)
+
+
+#: Decimal places kept for float knobs in ``tuner_best.json`` and snippets.
+KNOB_DECIMALS: int = 4
+
+
+#: Averaged metrics used for ranking, in lexicographic priority order.
+METRIC_KEYS: Tuple[str, ...] = (
+    "avg_final_sp",
+    "avg_delta_sp_per_100kcal",
+    "avg_variety_count",
+    "avg_balance_ratio",
+)
+
+#: Metric ranked first for each ``--objective``.
+OBJECTIVE_METRICS: Dict[str, str] = {
+    "sp": "avg_final_sp",
+    "efficiency": "avg_delta_sp_per_100kcal",
+    "variety": "avg_variety_count",
+    "balance": "avg_balance_ratio",
+}
+
+#: Weight of the objective's axis in `select_balanced`'s distance.
+BALANCED_OBJECTIVE_WEIGHT: float = 2.0
+
+
 def safe_name_knobs(
     theta: Dict[str, float],
 ) -> Dict[str, float]:
     """Return a clean mapping for CSV/JSON dump with stable key order."""
-    keys = [
-        "SOFT_VARIETY_BIAS_STRENGTH",
-        "PROXIMITY_APPROACH_WEIGHT",
-        "PROXIMITY_OVERSHOOT_PENALTY",
-        "TIEBREAK_SCORE_WINDOW_SP",
-        "LOW_CALORIE_THRESHOLD",
-        "LOW_CALORIE_PENALTY_STRENGTH",
-        "BALANCED_DIET_IMPROVEMENT_STRENGTH",
-        "REPETITION_PENALTY_STRENGTH",
-    ]
-    return {k: float(theta[k]) for k in keys if k in theta}
+    return {k: float(theta[k]) for k in KNOB_NAMES if k in theta}
 
 
 # -------- search space --------
@@ -340,13 +417,307 @@ def sample_theta(
     }
 
 
+# -------- tuner config file --------
+
+
+@dataclass
+class TunerConfig:
+    """Tuner settings read from a YAML file.
+
+    ``None`` fields fall back to the CLI defaults; `ranges` only lists the
+    knobs the file overrides.
+    """
+
+    budgets: List[int] | None = None
+    budget_weights: List[float] | None = None
+    ranges: Dict[str, Tuple[float, float]] = field(default_factory=dict)
+    iters: int | None = None
+    seed: int | None = None
+    seeds: int | None = None
+    hill_climb: bool | None = None
+    hill_climb_max_iterations: int = HILL_CLIMB_MAX_ITERATIONS
+    hill_climb_factors: Tuple[float, ...] = HILL_CLIMB_FACTORS
+
+
+_TUNER_CONFIG_KEYS = (
+    "budgets",
+    "budget_weights",
+    "ranges",
+    "iters",
+    "seed",
+    "seeds",
+    "hill_climb",
+)
+
+
+def load_tuner_config(
+    path: Path,
+) -> TunerConfig:
+    """Read budgets, knob ranges, and search settings from a YAML file.
+
+    Example::
+
+        budgets: [5000, 10000]
+        budget_weights: [1, 3]
+        iters: 200
+        ranges:
+          SOFT_VARIETY_BIAS_STRENGTH: [1.0, 4.0]
+        hill_climb:
+          enabled: true
+          max_iterations: 10
+          factors: [0.9, 1.1]
+
+    Raises
+    ------
+    ValueError
+        On unknown keys or knob names, or a range that is not two finite
+        numbers.
+    """
+    with open(path, encoding="utf-8") as f:
+        data = yaml.safe_load(f) or {}
+    if not isinstance(data, dict):
+        raise ValueError(f"Tuner config must be a mapping: {path}")
+    unknown = sorted(set(data) - set(_TUNER_CONFIG_KEYS))
+    if unknown:
+        raise ValueError(f"Unknown tuner config keys: {', '.join(unknown)}")
+
+    ranges: Dict[str, Tuple[float, float]] = {}
+    for knob_name, bounds in (data.get("ranges") or {}).items():
+        if knob_name not in KNOB_NAMES:
+            raise ValueError(f"Unknown knob in tuner config: {knob_name}")
+        if not isinstance(bounds, (list, tuple)) or len(bounds) != 2:
+            raise ValueError(f"Range for {knob_name} must be [lo, hi]")
+        lo, hi = sorted(float(bound) for bound in bounds)
+        if not (math.isfinite(lo) and math.isfinite(hi)):
+            raise ValueError(f"Range for {knob_name} must be finite")
+        ranges[knob_name] = (lo, hi)
+
+    hill = data.get("hill_climb") or {}
+    budgets = data.get("budgets")
+    budget_weights = data.get("budget_weights")
+    return TunerConfig(
+        budgets=[int(b) for b in budgets] if budgets is not None else None,
+        budget_weights=(
+            [float(w) for w in budget_weights] if budget_weights is not None else None
+        ),
+        ranges=ranges,
+        iters=data.get("iters"),
+        seed=data.get("seed"),
+        seeds=data.get("seeds"),
+        hill_climb=hill.get("enabled"),
+        hill_climb_max_iterations=int(
+            hill.get("max_iterations", HILL_CLIMB_MAX_ITERATIONS)
+        ),
+        hill_climb_factors=tuple(
+            float(f) for f in hill.get("factors", HILL_CLIMB_FACTORS)
+        ),
+    )
+
+
+def config_defaults(
+    tuner_config: TunerConfig,
+) -> Dict[str, Any]:
+    """Argparse defaults taken from *tuner_config* (explicit flags still win)."""
+    defaults: Dict[str, Any] = {}
+    if tuner_config.budgets is not None:
+        defaults["budgets"] = ",".join(str(b) for b in tuner_config.budgets)
+    if tuner_config.budget_weights is not None:
+        defaults["budget_weights"] = ",".join(
+            str(w) for w in tuner_config.budget_weights
+        )
+    for key in ("iters", "seed", "seeds"):
+        value = getattr(tuner_config, key)
+        if value is not None:
+            defaults[key] = int(value)
+    if tuner_config.hill_climb is not None:
+        defaults["hill_climb"] = tuner_config.hill_climb
+    return defaults
+
+
 # -------- evaluation --------
 
 
+def _fresh_manager(
+    foods: List[Food] | None,
+) -> FoodStateManager:
+    """Build a new manager for one planning run.
+
+    Loads ``food_state.json`` when *foods* is ``None``; otherwise copies the
+    given foods so the planner never mutates the caller's records.
+    """
+    if foods is None:
+        # Suppress interactive tastiness rating prompts during tuner runs.
+        with suppress_interactive_prompts(persistence):
+            return persistence.load_food_state(
+                reset_stomach=False,
+                reset_tastiness=False,
+                skip_prompts=True,
+            )
+    return FoodStateManager([Food.from_dict(food.to_dict()) for food in foods])
+
+
+def load_best_knobs(
+    path: Path,
+) -> Dict[str, float]:
+    """Read the ``knobs`` mapping from a tuner best-result JSON file.
+
+    Reverses `write_best_json`, up to its `KNOB_DECIMALS` rounding.
+
+    Raises
+    ------
+    ValueError
+        If the file has no knob mapping, names an unknown knob, or holds a
+        value that is not a finite number.
+    """
+    with open(path, encoding="utf-8") as f:
+        data = json.load(f)
+    knobs = data.get("knobs") if isinstance(data, dict) else None
+    if not isinstance(knobs, dict):
+        raise ValueError(f"No knobs mapping in {path}")
+    theta: Dict[str, float] = {}
+    for knob_name, value in knobs.items():
+        if knob_name not in KNOB_NAMES:
+            raise ValueError(f"Unknown knob in {path}: {knob_name}")
+        if isinstance(value, bool) or not isinstance(value, (int, float)):
+            raise ValueError(f"Knob {knob_name} must be a number")
+        if not math.isfinite(value):
+            raise ValueError(f"Knob {knob_name} must be finite")
+        theta[knob_name] = float(value)
+    return theta
+
+
+def plan_with_knobs(
+    manager: FoodStateManager,
+    theta: Dict[str, float],
+    cravings: List[str],
+    cravings_satisfied: int,
+    remaining_calories: int,
+    **plan_kwargs: Any,
+) -> List[Any]:
+    """Run `planner.plan_meal` with *theta* overriding the planner knobs.
+
+    The planner is reloaded again afterwards, so later plans in the same
+    process go back to the configured values.
+
+    Parameters
+    ----------
+    manager
+        State to plan against; mutated like a normal `plan_meal` call.
+    theta
+        Knob names to values; knobs not listed keep their configured value.
+    cravings, cravings_satisfied, remaining_calories
+        Passed straight to `plan_meal`.
+    **plan_kwargs
+        Extra `plan_meal` keywords (multipliers, repeat cap, ...).
+
+    Returns
+    -------
+    list[MealPlanItem]
+        The plan `plan_meal` produced under the overrides.
+    """
+    try:
+        with override_constants(**theta):
+            _, _, planner = reload_deps()
+            return planner.plan_meal(
+                manager,
+                cravings,
+                cravings_satisfied,
+                remaining_calories,
+                **plan_kwargs,
+            )
+    finally:
+        reload_deps()
+
+
+def _plan_stats(
+    manager: FoodStateManager,
+    meal_plan: List[Any],
+    start_sp: float,
+) -> Dict[str, Any]:
+    """Final SP, calories, bites, and variety count of one finished plan."""
+    return {
+        "final_sp": float(meal_plan[-1].new_sp if meal_plan else start_sp),
+        "calories": sum(item.calories for item in meal_plan),
+        "bites": len(meal_plan),
+        "variety_count": len(manager.unique_variety_foods()),
+    }
+
+
+def compare_plans(
+    manager: FoodStateManager,
+    theta: Dict[str, float],
+    cravings: List[str],
+    cravings_satisfied: int,
+    remaining_calories: int,
+    **plan_kwargs: Any,
+) -> Dict[str, Dict[str, Any]]:
+    """Plan once with the configured knobs and once with *theta*.
+
+    Both runs start from their own copy of *manager*, so the numbers are
+    comparable and the caller's state is left untouched.
+
+    Returns
+    -------
+    dict
+        ``{"default": stats, "tuned": stats}`` where each stats dict has
+        'final_sp', 'calories', 'bites', and 'variety_count'.
+    """
+    import planner
+
+    start_sp = manager.get_current_sp(
+        cravings,
+        cravings_satisfied,
+        server_mult=plan_kwargs.get("server_mult", 1.0),
+        dinner_party_mult=plan_kwargs.get("dinner_party_mult", 1.0),
+    )
+    default_manager = copy.deepcopy(manager)
+    default_plan = planner.plan_meal(
+        default_manager,
+        list(cravings),
+        cravings_satisfied,
+        remaining_calories,
+        **plan_kwargs,
+    )
+    tuned_manager = copy.deepcopy(manager)
+    tuned_plan = plan_with_knobs(
+        tuned_manager,
+        theta,
+        list(cravings),
+        cravings_satisfied,
+        remaining_calories,
+        **plan_kwargs,
+    )
+    return {
+        "default": _plan_stats(default_manager, default_plan, start_sp),
+        "tuned": _plan_stats(tuned_manager, tuned_plan, start_sp),
+    }
+
+
+def baseline_plan(
+    budgets: Iterable[int],
+    *,
+    foods: List[Food] | None = None,
+) -> List[Any]:
+    """Bites the configured knobs plan for the largest of *budgets*.
+
+    Planned on a fresh manager, as in `evaluate_theta`.
+
+    Returns
+    -------
+    list[MealPlanItem]
+        The baseline plan, in eating order.
+    """
+    budget = max(1, int(max(budgets)))
+    return plan_with_knobs(_fresh_manager(foods), baseline_theta(), [], 0, budget)
+
+
 def evaluate_theta(
     theta: Dict[str, float],
     budgets: Iterable[int],
     seed: int,
+    *,
+    foods: List[Food] | None = None,
+    budget_weights: List[float] | None = None,
 ) -> Dict[str, Any]:
     """Evaluate one knob set across multiple calorie budgets.
 
@@ -361,6 +732,10 @@ def evaluate_theta(
         Calorie budgets to evaluate (e.g., 900, 1200, 1500).
     seed
         Random seed used to make the evaluation deterministic.
+    foods
+        Catalog to plan against; defaults to loading ``food_state.json``.
+    budget_weights
+        Relative weight of each budget in the averages; uniform if omitted.
 
     Returns
     -------
@@ -370,9 +745,15 @@ def evaluate_theta(
         - 'avg_final_sp': float
         - 'avg_delta_sp_per_100kcal': float
         - 'avg_variety_count': float
-        - 'per_budget': list of per-budget metric dicts
+        - 'std_final_sp', 'std_variety_count': spread across budgets
+        - 'avg_bites': mean units eaten per budget
+        - 'per_budget': list of per-budget metric dicts (including
+          'bites', units eaten within the catalog's real availability)
     """
 
+    import calculations
+    import constants
+
     # Deterministic per-theta seed; kept for potential future stochastic logic.
     (
         seed
@@ -384,62 +765,37 @@ def evaluate_theta(
 
     for budget in budgets:
         # Build a fresh manager; planner mutates state during planning.
-
-        # Build a fresh manager; planner mutates state during planning.
-        # Suppress interactive tastiness rating prompts during tuner runs.
-        with suppress_interactive_prompts(persistence):
-            manager = persistence.load_food_state(
-                reset_stomach=False,
-                reset_tastiness=False,
-            )
+        manager = _fresh_manager(foods)
 
         initial_sp: float = manager.get_current_sp(
             cravings=[],
             cravings_satisfied=0,
         )
+        initial_units: int = sum(manager.stomach.values())
 
-        # Apply overrides and reload the dependent modules so they read the new values.
-        with override_constants(**theta):
-            constants, calculations, planner = reload_deps()
-
-            # Guard against invalid budgets.
-            budget_int: int = max(1, int(budget))
-
-            # Run the planner. Support both kw/positional signatures.
-            try:
-                planner.plan_meal(
-                    manager=manager,
-                    cravings=[],
-                    cravings_satisfied=0,
-                    remaining_calories=budget_int,
-                )
-            except TypeError:
-                planner.plan_meal(
-                    manager,
-                    [],
-                    0,
-                    budget_int,
-                )
+        # Guard against invalid budgets.
+        budget_int: int = max(1, int(budget))
+        plan_with_knobs(manager, theta, [], 0, budget_int)
 
-            final_sp: float = manager.get_current_sp(
-                cravings=[],
-                cravings_satisfied=0,
-            )
-            delta_sp: float = final_sp - initial_sp
-            delta_sp_per_100kcal: float = (delta_sp / float(budget_int)) * 100.0
+        final_sp: float = manager.get_current_sp(
+            cravings=[],
+            cravings_satisfied=0,
+        )
+        delta_sp: float = final_sp - initial_sp
+        delta_sp_per_100kcal: float = (delta_sp / float(budget_int)) * 100.0
 
-            variety_threshold: int = getattr(
-                constants,
-                "VARIETY_CAL_THRESHOLD",
-                2_000,
-            )
-            variety_count: int = count_hard_variety(
-                manager.stomach,
-                variety_threshold,
-            )
+        variety_threshold: int = getattr(
+            constants,
+            "VARIETY_CAL_THRESHOLD",
+            2_000,
+        )
+        variety_count: int = count_hard_variety(
+            manager.stomach,
+            variety_threshold,
+        )
 
-            # Calculate balance ratio (min/max nutrient density)
-            balance_ratio: float = calculations.get_balanced_diet_ratio(manager.stomach)
+        # Calculate balance ratio (min/max nutrient density)
+        balance_ratio: float = calculations.get_balanced_diet_ratio(manager.stomach)
 
         per_budget.append(
             {
@@ -448,43 +804,404 @@ def evaluate_theta(
                 "delta_sp_per_100kcal": float(delta_sp_per_100kcal),
                 "variety_count": int(variety_count),
                 "balance_ratio": float(balance_ratio),
+                "bites": sum(manager.stomach.values()) - initial_units,
             },
         )
 
-    n: int = len(per_budget)
-    avg_final_sp: float = sum(x["final_sp"] for x in per_budget) / n
-    avg_delta_sp_per_100kcal: float = (
-        sum(x["delta_sp_per_100kcal"] for x in per_budget) / n
-    )
-    avg_variety_count: float = sum(x["variety_count"] for x in per_budget) / n
-    avg_balance_ratio: float = sum(x["balance_ratio"] for x in per_budget) / n
-
     return {
         "theta": safe_name_knobs(theta),
-        "avg_final_sp": float(avg_final_sp),
-        "avg_delta_sp_per_100kcal": float(avg_delta_sp_per_100kcal),
-        "avg_variety_count": float(avg_variety_count),
-        "avg_balance_ratio": float(avg_balance_ratio),
+        **weighted_budget_average(per_budget, budget_weights),
+        **budget_spread(per_budget),
+        "avg_bites": average_bites(per_budget),
         "per_budget": per_budget,
     }
 
 
+def normalize_budget_weights(
+    budget_weights: List[float] | None,
+    count: int,
+) -> List[float]:
+    """Weights summing to 1, one per budget (uniform when ``None``).
+
+    Raises
+    ------
+    ValueError
+        If the length doesn't match, a weight is negative, or all are zero.
+    """
+    if budget_weights is None:
+        return [1.0 / count] * count
+    if len(budget_weights) != count:
+        raise ValueError(
+            f"Got {len(budget_weights)} budget weights for {count} budgets"
+        )
+    if any(w < 0 for w in budget_weights) or sum(budget_weights) <= 0:
+        raise ValueError("Budget weights must be non-negative and not all zero")
+    total = sum(budget_weights)
+    return [w / total for w in budget_weights]
+
+
+def weighted_budget_average(
+    per_budget: List[Dict[str, Any]],
+    budget_weights: List[float] | None = None,
+) -> Dict[str, float]:
+    """Weighted mean of each per-budget metric, keyed ``avg_<metric>``."""
+    weights = normalize_budget_weights(budget_weights, len(per_budget))
+    return {
+        f"avg_{key}": float(sum(w * x[key] for w, x in zip(weights, per_budget)))
+        for key in (
+            "final_sp",
+            "delta_sp_per_100kcal",
+            "variety_count",
+            "balance_ratio",
+        )
+    }
+
+
+def _mean_std(
+    values: List[float],
+) -> Tuple[float, float]:
+    """Population mean and standard deviation."""
+    n = len(values)
+    mean = sum(values) / n
+    variance = sum((v - mean) ** 2 for v in values) / n
+    return mean, variance**0.5
+
+
+def budget_spread(
+    per_budget: List[Dict[str, Any]],
+) -> Dict[str, float]:
+    """Standard deviation of final SP and variety across budgets.
+
+    Low spread means a knob set behaves consistently whatever the budget,
+    which can be worth a slightly lower mean.
+    """
+    if not per_budget:
+        return {"std_final_sp": 0.0, "std_variety_count": 0.0}
+    _, std_final_sp = _mean_std([float(pb["final_sp"]) for pb in per_budget])
+    _, std_variety = _mean_std([float(pb["variety_count"]) for pb in per_budget])
+    return {"std_final_sp": std_final_sp, "std_variety_count": std_variety}
+
+
+def average_bites(
+    per_budget: List[Dict[str, Any]],
+) -> float:
+    """Unweighted mean of per-budget bites (0.0 when none are recorded)."""
+    bites = [float(pb["bites"]) for pb in per_budget if "bites" in pb]
+    return sum(bites) / len(bites) if bites else 0.0
+
+
+def jitter_budgets(
+    budgets: List[int],
+    seed: int,
+) -> List[int]:
+    """Scale each budget by a seeded factor in ``1 ± BUDGET_JITTER_FRAC``."""
+    rng = random.Random(seed)
+    return [
+        int(round(b * (1.0 + rng.uniform(-1.0, 1.0) * BUDGET_JITTER_FRAC)))
+        for b in budgets
+    ]
+
+
+def evaluate_theta_multi(
+    theta: Dict[str, float],
+    budgets: Iterable[int],
+    seed: int,
+    seed_count: int,
+    *,
+    foods: List[Food] | None = None,
+    budget_weights: List[float] | None = None,
+) -> Dict[str, Any]:
+    """Evaluate one knob set under several seeds and average the metrics.
+
+    Planning itself is deterministic, so each seed instead jitters every
+    budget by up to ``BUDGET_JITTER_FRAC``; knob sets that only shine at
+    one exact budget get averaged down. With ``seed_count == 1`` this is
+    identical to `evaluate_theta`.
+
+    Returns
+    -------
+    dict
+        Same shape as `evaluate_theta` with metrics averaged across seeds
+        (per-budget entries keep the nominal budget), plus ``'seed_count'``
+        and ``'std_final_sp_across_seeds'``.
+    """
+    budgets = list(budgets)
+    if seed_count <= 1:
+        return evaluate_theta(
+            theta, budgets, seed, foods=foods, budget_weights=budget_weights
+        )
+
+    runs = [
+        evaluate_theta(
+            theta,
+            jitter_budgets(budgets, seed + k),
+            seed,
+            foods=foods,
+            budget_weights=budget_weights,
+        )
+        for k in range(seed_count)
+    ]
+
+    averaged: Dict[str, Any] = {"theta": runs[0]["theta"]}
+    for key in METRIC_KEYS:
+        averaged[key] = sum(run[key] for run in runs) / seed_count
+    _, std_final_sp = _mean_std([run["avg_final_sp"] for run in runs])
+    averaged["std_final_sp_across_seeds"] = std_final_sp
+    averaged["seed_count"] = seed_count
+
+    # Average each budget's metrics position-wise under the nominal budget
+    per_budget: List[Dict[str, Any]] = []
+    for position, nominal_budget in enumerate(budgets):
+        merged: Dict[str, Any] = {"budget": nominal_budget}
+        for key in runs[0]["per_budget"][position]:
+            if key == "budget":
+                continue
+            merged[key] = (
+                sum(run["per_budget"][position][key] for run in runs) / seed_count
+            )
+        per_budget.append(merged)
+    averaged.update(budget_spread(per_budget))
+    averaged["avg_bites"] = average_bites(per_budget)
+    averaged["per_budget"] = per_budget
+    return averaged
+
+
+def run_tuner_multi(
+    thetas: List[Dict[str, float]],
+    budgets: Iterable[int],
+    seed: int,
+    seed_count: int,
+    *,
+    foods: List[Food] | None = None,
+) -> List[Dict[str, Any]]:
+    """Evaluate every knob set with `evaluate_theta_multi`."""
+    budgets = list(budgets)
+    return [
+        evaluate_theta_multi(theta, budgets, seed, seed_count, foods=foods)
+        for theta in thetas
+    ]
+
+
+def evaluate_holdout(
+    result: Dict[str, Any],
+    holdout_budgets: Iterable[int],
+    seed: int,
+    seed_count: int = 1,
+    *,
+    foods: List[Food] | None = None,
+) -> Dict[str, Any]:
+    """Re-evaluate a tuned result's knobs on budgets it was not tuned on.
+
+    Returns
+    -------
+    dict
+        `evaluate_theta_multi` metrics for the held-out budgets, plus
+        ``'sp_gap'`` from `holdout_gap` against the training metrics.
+    """
+    holdout = evaluate_theta_multi(
+        result["theta"], holdout_budgets, seed, seed_count, foods=foods
+    )
+    holdout["sp_gap"] = holdout_gap(result, holdout)
+    return holdout
+
+
+def evaluate_warmstart(
+    path: Path,
+    budgets: Iterable[int],
+    seed: int,
+    ranges: Dict[str, Tuple[float, float]],
+    seed_count: int = 1,
+    *,
+    foods: List[Food] | None = None,
+    budget_weights: List[float] | None = None,
+) -> Dict[str, Any]:
+    """Evaluate the knobs of a previous run's best-result JSON.
+
+    Knobs missing from the file keep their configured value, and every knob
+    is clamped into *ranges* so ``--freeze`` still holds. Each knob that
+    clamping changes is reported with a ``[WARN]`` line.
+
+    Raises
+    ------
+    ValueError
+        If the file holds no usable knobs (see `load_best_knobs`).
+    """
+    theta = {**baseline_theta(), **load_best_knobs(path)}
+    clamped = clamp_theta(theta, ranges)
+    for knob_name, value in theta.items():
+        if clamped[knob_name] != value:
+            print(
+                f"[WARN] Warm-start {knob_name} clamped into its range: "
+                f"{value:g} -> {clamped[knob_name]:g}"
+            )
+    return evaluate_theta_multi(
+        clamped,
+        budgets,
+        seed,
+        seed_count,
+        foods=foods,
+        budget_weights=budget_weights,
+    )
+
+
+def holdout_gap(
+    train: Dict[str, Any],
+    holdout: Dict[str, Any],
+) -> float:
+    """Relative drop in average final SP from training to holdout budgets.
+
+    Positive when the knobs do worse on the held-out budgets; 0.0 when the
+    training SP is zero.
+    """
+    train_sp = train["avg_final_sp"]
+    if train_sp == 0:
+        return 0.0
+    return (train_sp - holdout["avg_final_sp"]) / abs(train_sp)
+
+
+def baseline_theta() -> Dict[str, float]:
+    """Current knob values from the loaded config (the untuned baseline)."""
+    import constants
+
+    return {name: getattr(constants, name) for name in KNOB_NAMES}
+
+
+def has_finite_metrics(
+    m: Dict[str, Any],
+) -> bool:
+    """Whether every ranking metric of *m* is a real number (not NaN/inf)."""
+    return all(math.isfinite(m.get(key, 0.0)) for key in METRIC_KEYS)
+
+
+MetricKey = Tuple[int, float]
+
+#: Sort key of NaN (and of non-finite results): below every real number.
+WORST_METRIC_KEY: MetricKey = (0, 0.0)
+
+
+def metric_key(
+    value: float,
+) -> MetricKey:
+    """Totally ordered sort key for one metric, higher is better.
+
+    NaN compares False against everything, so a plain float key lets it
+    tie with any neighbour and scrambles sorts. Here NaN maps to
+    `WORST_METRIC_KEY`, below ``-inf``, so best-first sorts put it last
+    and equal keys keep their input order.
+    """
+    if math.isnan(value):
+        return WORST_METRIC_KEY
+    return (1, value)
+
+
 def score_metrics(
     m: Dict[str, Any],
-) -> Tuple[float, float, float, float]:
+    objective: str = "sp",
+) -> Tuple[MetricKey, MetricKey, MetricKey, MetricKey]:
     """
-    Lexicographic score tuple:
+    Lexicographic score tuple, by default:
       1) avg_final_sp
       2) avg_delta_sp_per_100kcal
       3) avg_variety_count
       4) avg_balance_ratio
-    Higher is better for all four.
+    Another *objective* (see `OBJECTIVE_METRICS`) moves its metric to the
+    front; the others keep this order. Higher is better for all four;
+    each position is a `metric_key`. A result with any non-finite metric
+    scores worst on every position.
+    """
+    if not has_finite_metrics(m):
+        return (WORST_METRIC_KEY,) * 4  # type: ignore[return-value]
+    lead = OBJECTIVE_METRICS[objective]
+    order = [lead, *(key for key in METRIC_KEYS if key != lead)]
+    keys = tuple(metric_key(m.get(key, 0.0)) for key in order)
+    return keys  # type: ignore[return-value]
+
+
+@dataclass(frozen=True)
+class ScoreWeights:
+    """Relative weights for the weighted-sum ranking.
+
+    Each metric is min-max normalized across the result set before
+    weighting, so weights express relative importance, not units.
+    """
+
+    sp: float = 1.0
+    efficiency: float = 1.0
+    variety: float = 1.0
+    balance: float = 1.0
+
+    def as_tuple(self) -> Tuple[float, float, float, float]:
+        """Weights in `METRIC_KEYS` order."""
+        return (self.sp, self.efficiency, self.variety, self.balance)
+
+
+def parse_weights(
+    arg: str,
+) -> ScoreWeights:
+    """Parse ``'sp,efficiency,variety,balance'`` into `ScoreWeights`."""
+    if not arg:
+        return ScoreWeights()
+    parts = [p.strip() for p in arg.split(",")]
+    if len(parts) != 4:
+        raise ValueError(f"Weights must be 'sp,eff,var,bal' (got: {arg})")
+    sp, efficiency, variety, balance = (float(p) for p in parts)
+    return ScoreWeights(sp, efficiency, variety, balance)
+
+
+def metric_bounds(
+    results: List[Dict[str, Any]],
+) -> Dict[str, Tuple[float, float]]:
+    """Min/max of each finite ranking metric across *results*."""
+    bounds: Dict[str, Tuple[float, float]] = {}
+    for key in METRIC_KEYS:
+        values = [r.get(key, 0.0) for r in results if has_finite_metrics(r)]
+        bounds[key] = (min(values), max(values)) if values else (0.0, 0.0)
+    return bounds
+
+
+def weighted_score(
+    m: Dict[str, Any],
+    weights: ScoreWeights,
+    bounds: Dict[str, Tuple[float, float]],
+) -> float:
+    """Weighted sum of min-max normalized metrics (higher is better).
+
+    A metric with no spread across the result set contributes its full
+    weight, so it never changes the ordering. Results with non-finite
+    metrics score ``-inf``.
+    """
+    if not has_finite_metrics(m):
+        return -math.inf
+    total = 0.0
+    for key, weight in zip(METRIC_KEYS, weights.as_tuple()):
+        lo, hi = bounds[key]
+        if abs(hi - lo) < 1e-10:
+            normalized = 1.0
+        else:
+            normalized = (m.get(key, 0.0) - lo) / (hi - lo)
+        total += weight * normalized
+    return total
+
+
+def rank_results(
+    results: List[Dict[str, Any]],
+    weights: ScoreWeights | None = None,
+    objective: str = "sp",
+) -> List[Dict[str, Any]]:
+    """Sort results best-first.
+
+    Uses lexicographic `score_metrics` led by *objective* by default, or
+    `weighted_score` when *weights* are given.
     """
-    return (
-        m["avg_final_sp"],
-        m["avg_delta_sp_per_100kcal"],
-        m["avg_variety_count"],
-        m.get("avg_balance_ratio", 0.0),
+    if weights is None:
+        return sorted(
+            results, key=lambda r: score_metrics(r, objective), reverse=True
+        )
+    bounds = metric_bounds(results)
+    return sorted(
+        results,
+        key=lambda r: metric_key(weighted_score(r, weights, bounds)),
+        reverse=True,
     )
 
 
@@ -526,21 +1243,28 @@ def pareto_frontier(
     """Extract Pareto-optimal (non-dominated) result indices.
 
     Returns indices of results that are not dominated by any other result.
+    Results with non-finite metrics are left out entirely, since NaN makes
+    every dominance comparison false.
     """
+    finite = [r for r in results if has_finite_metrics(r)]
     return [
         idx
         for idx, candidate in enumerate(results)
-        if not any(is_dominated_by(candidate, other) for other in results)
+        if has_finite_metrics(candidate)
+        and not any(is_dominated_by(candidate, other) for other in finite)
     ]
 
 
 def select_balanced(
     results: List[Dict[str, Any]],
     pareto_indices: List[int],
+    emphasis: str | None = None,
 ) -> int | None:
     """Select the most "balanced" result from Pareto frontier.
 
     Uses normalized Euclidean distance to the ideal point (max of each metric).
+    With *emphasis* (an `OBJECTIVE_METRICS` key), that metric's axis counts
+    ``BALANCED_OBJECTIVE_WEIGHT`` times as much.
     """
     if not pareto_indices:
         return None
@@ -563,10 +1287,11 @@ def select_balanced(
             return 1.0
         return (val - lo) / (hi - lo)
 
-    best_idx = pareto_indices[0]
-    best_distance = float("inf")
+    axis_weight = {name: 1.0 for name in OBJECTIVE_METRICS}
+    if emphasis is not None:
+        axis_weight[emphasis] = BALANCED_OBJECTIVE_WEIGHT
 
-    for idx in pareto_indices:
+    def distance_to_ideal(idx: int) -> float:
         r = results[idx]
         norm_sp = normalize(r["avg_final_sp"], min_sp, max_sp)
         norm_var = normalize(r["avg_variety_count"], min_var, max_var)
@@ -574,18 +1299,15 @@ def select_balanced(
         norm_eff = normalize(r["avg_delta_sp_per_100kcal"], min_eff, max_eff)
 
         # Euclidean distance to ideal (1, 1, 1, 1)
-        distance = (
-            (1.0 - norm_sp) ** 2
-            + (1.0 - norm_var) ** 2
-            + (1.0 - norm_bal) ** 2
-            + (1.0 - norm_eff) ** 2
+        return (
+            axis_weight["sp"] * (1.0 - norm_sp) ** 2
+            + axis_weight["variety"] * (1.0 - norm_var) ** 2
+            + axis_weight["balance"] * (1.0 - norm_bal) ** 2
+            + axis_weight["efficiency"] * (1.0 - norm_eff) ** 2
         ) ** 0.5
 
-        if distance < best_distance:
-            best_distance = distance
-            best_idx = idx
-
-    return best_idx
+    # Nearest first; the earliest index wins ties and a NaN distance never wins
+    return max(pareto_indices, key=lambda idx: metric_key(-distance_to_ideal(idx)))
 
 
 def perturb_theta(
@@ -593,11 +1315,22 @@ def perturb_theta(
     knob_name: str,
     factor: float,
     ranges: Dict[str, Tuple[float, float]],
+    *,
+    min_step_frac: float = 0.0,
 ) -> Dict[str, float]:
-    """Perturb a single knob by a factor, clamped to its range."""
-    new_theta = dict(theta)
+    """Perturb a single knob by a factor, clamped to its range.
+
+    With *min_step_frac* > 0, knobs with a range move at least that
+    fraction of the range in the direction of *factor*, so a knob at 0
+    can still move.
+    """
+    new_theta = dict(theta)
     lo, hi = ranges.get(knob_name, (0, 1e9))
     new_val = theta[knob_name] * factor
+    if min_step_frac > 0 and knob_name in ranges and factor != 1:
+        min_step = min_step_frac * (hi - lo)
+        if abs(new_val - theta[knob_name]) < min_step:
+            new_val = theta[knob_name] + math.copysign(min_step, factor - 1)
     new_val = max(lo, min(hi, new_val))
     if knob_name == "LOW_CALORIE_THRESHOLD":
         new_val = int(round(new_val))
@@ -613,13 +1346,20 @@ def hill_climb(
     *,
     max_iterations: int = HILL_CLIMB_MAX_ITERATIONS,
     factors: Tuple[float, ...] = HILL_CLIMB_FACTORS,
+    seed_count: int = 1,
+    budget_weights: List[float] | None = None,
+    frozen: Iterable[str] = (),
+    foods: List[Food] | None = None,
 ) -> Dict[str, Any]:
     """Refine a result using hill climbing.
 
-    Tries small perturbations to each knob, keeping changes that improve.
+    Tries small perturbations to each knob except the *frozen* ones,
+    keeping changes that improve. Plans against *foods* when given,
+    otherwise ``food_state.json``.
     """
     best = initial
-    knob_names = list(initial["theta"].keys())
+    frozen = set(frozen)
+    knob_names = [k for k in initial["theta"] if k not in frozen]
 
     for _ in range(max_iterations):
         improved = False
@@ -634,7 +1374,14 @@ def hill_climb(
                 if candidate_theta == best["theta"]:
                     continue
 
-                candidate = evaluate_theta(candidate_theta, budgets, seed)
+                candidate = evaluate_theta_multi(
+                    candidate_theta,
+                    budgets,
+                    seed,
+                    seed_count,
+                    foods=foods,
+                    budget_weights=budget_weights,
+                )
 
                 # Accept if candidate dominates current best
                 if is_dominated_by(best, candidate):
@@ -648,13 +1395,630 @@ def hill_climb(
     return best
 
 
+def improves_within_tolerance(
+    current: Dict[str, Any],
+    candidate: Dict[str, Any],
+    tolerance: float = PARETO_CLIMB_TOLERANCE,
+) -> bool:
+    """Whether *candidate* trades off acceptably against *current*.
+
+    True when at least one ranking metric is strictly better and none is
+    worse by more than *tolerance* (a fraction of the current value).
+    Unlike `is_dominated_by`, this lets a climb move along the frontier.
+    """
+    if not has_finite_metrics(candidate):
+        return False
+    better = False
+    for key in METRIC_KEYS:
+        old = current.get(key, 0.0)
+        new = candidate.get(key, 0.0)
+        if new > old:
+            better = True
+        elif old - new > tolerance * abs(old):
+            return False
+    return better
+
+
+def hill_climb_pareto(
+    initial: Dict[str, Any],
+    budgets: List[int],
+    seed: int,
+    ranges: Dict[str, Tuple[float, float]],
+    *,
+    max_iterations: int = HILL_CLIMB_MAX_ITERATIONS,
+    factors: Tuple[float, ...] = HILL_CLIMB_FACTORS,
+    seed_count: int = 1,
+    budget_weights: List[float] | None = None,
+    frozen: Iterable[str] = (),
+    foods: List[Food] | None = None,
+    tolerance: float = PARETO_CLIMB_TOLERANCE,
+) -> List[Dict[str, Any]]:
+    """Hill climb that walks trade-off fronts instead of chasing domination.
+
+    Same perturbations as `hill_climb`, but a step is accepted when it
+    passes `improves_within_tolerance` and no point seen on the walk
+    already matches or dominates it, so the walk cannot cycle between two
+    trade-offs. Every accepted point is returned (in order, without
+    *initial*) so all of them can join the Pareto frontier.
+    """
+    current = initial
+    frozen = set(frozen)
+    knob_names = [k for k in initial["theta"] if k not in frozen]
+    visited = {tuple(sorted(initial["theta"].items()))}
+    accepted: List[Dict[str, Any]] = []
+
+    def is_new_point(candidate: Dict[str, Any]) -> bool:
+        metrics = [candidate.get(key, 0.0) for key in METRIC_KEYS]
+        return not any(
+            is_dominated_by(candidate, seen)
+            or metrics == [seen.get(key, 0.0) for key in METRIC_KEYS]
+            for seen in (initial, *accepted)
+        )
+
+    for _ in range(max_iterations):
+        moved = False
+
+        for knob_name in knob_names:
+            for factor in factors:
+                candidate_theta = perturb_theta(
+                    current["theta"], knob_name, factor, ranges
+                )
+                key = tuple(sorted(candidate_theta.items()))
+                if key in visited:
+                    continue
+                visited.add(key)
+
+                candidate = evaluate_theta_multi(
+                    candidate_theta,
+                    budgets,
+                    seed,
+                    seed_count,
+                    foods=foods,
+                    budget_weights=budget_weights,
+                )
+                if improves_within_tolerance(
+                    current, candidate, tolerance
+                ) and is_new_point(candidate):
+                    current = candidate
+                    accepted.append(candidate)
+                    moved = True
+                    break
+
+        if not moved:
+            break
+
+    return accepted
+
+
+def freeze_ranges(
+    ranges: Dict[str, Tuple[float, float]],
+    frozen: Iterable[str],
+    baseline: Dict[str, float] | None = None,
+) -> Dict[str, Tuple[float, float]]:
+    """Pin each *frozen* knob's range to its baseline value.
+
+    Sampling from a ``(v, v)`` range always returns ``v``, so random search
+    holds frozen knobs at the config value while varying the rest.
+
+    Raises
+    ------
+    ValueError
+        If a frozen name is not a knob.
+    """
+    baseline = baseline_theta() if baseline is None else baseline
+    pinned = dict(ranges)
+    for knob_name in frozen:
+        if knob_name not in KNOB_NAMES:
+            raise ValueError(f"Unknown knob to freeze: {knob_name}")
+        pinned[knob_name] = (baseline[knob_name], baseline[knob_name])
+    return pinned
+
+
+def clamp_theta(
+    theta: Dict[str, float],
+    ranges: Dict[str, Tuple[float, float]],
+) -> Dict[str, float]:
+    """Clamp every knob into its sampling range."""
+    clamped = dict(theta)
+    for knob_name, (lo, hi) in ranges.items():
+        if knob_name in clamped:
+            clamped[knob_name] = max(lo, min(hi, clamped[knob_name]))
+    return clamped
+
+
+# -------- progress --------
+
+
+def estimate_eta(
+    done: int,
+    total: int,
+    elapsed: float,
+) -> float | None:
+    """Seconds left at the average rate so far, or ``None`` before any work."""
+    if done <= 0 or elapsed <= 0:
+        return None
+    return max(0, total - done) * (elapsed / done)
+
+
+def format_duration(
+    seconds: float | None,
+) -> str:
+    """``M:SS`` (or ``H:MM:SS``) for *seconds*; ``--:--`` when unknown."""
+    if seconds is None or not math.isfinite(seconds):
+        return "--:--"
+    minutes, secs = divmod(int(round(seconds)), 60)
+    hours, minutes = divmod(minutes, 60)
+    if hours:
+        return f"{hours}:{minutes:02d}:{secs:02d}"
+    return f"{minutes}:{secs:02d}"
+
+
+class ProgressBar:
+    """Single-line progress bar with rate and ETA.
+
+    Redraws at most every *min_interval* seconds so the evaluation loop
+    isn't slowed by console writes; `close` always draws the final state.
+    *clock* and *stream* are injectable for tests.
+    """
+
+    def __init__(
+        self,
+        total: int,
+        *,
+        clock: Callable[[], float] = time.monotonic,
+        stream: Any = None,
+        min_interval: float = PROGRESS_MIN_INTERVAL,
+    ) -> None:
+        self.total = max(1, total)
+        self.clock = clock
+        self.stream = stream if stream is not None else sys.stdout
+        self.min_interval = min_interval
+        self.start = clock()
+        self.done = 0
+        self._last_draw: float | None = None
+
+    def render(
+        self,
+        now: float,
+    ) -> str:
+        """The bar text for the current count at time *now*."""
+        elapsed = now - self.start
+        frac = min(1.0, self.done / self.total)
+        filled = int(frac * PROGRESS_BAR_WIDTH)
+        bar = "#" * filled + "-" * (PROGRESS_BAR_WIDTH - filled)
+        rate = self.done / elapsed if elapsed > 0 else 0.0
+        eta = format_duration(estimate_eta(self.done, self.total, elapsed))
+        return (
+            f"[{bar}] {frac * 100:3.0f}% complete "
+            f"{self.done}/{self.total} {rate:.1f} it/s ETA {eta}"
+        )
+
+    def update(
+        self,
+        done: int,
+    ) -> None:
+        """Record *done* iterations and redraw if the throttle allows."""
+        self.done = done
+        now = self.clock()
+        if self._last_draw is not None and now - self._last_draw < self.min_interval:
+            return
+        self._last_draw = now
+        print(f"\r{self.render(now)}", end="", file=self.stream, flush=True)
+
+    def close(self) -> None:
+        """Draw the final state and end the line."""
+        print(f"\r{self.render(self.clock())}", file=self.stream, flush=True)
+
+
+def run_random_search(
+    budgets: List[int],
+    ranges: Dict[str, Tuple[float, float]],
+    iterations: int,
+    seed: int,
+    *,
+    seed_count: int = 1,
+    foods: List[Food] | None = None,
+    budget_weights: List[float] | None = None,
+    patience: int | None = None,
+    progress: bool = True,
+    max_seconds: float | None = None,
+    clock: Callable[[], float] = time.monotonic,
+    objective: str = "sp",
+) -> Tuple[List[Dict[str, Any]], str]:
+    """Evaluate up to *iterations* knob sets sampled uniformly from *ranges*.
+
+    With *patience*, stops once the best `score_metrics` score under
+    *objective* hasn't improved for that many consecutive samples; with
+    *max_seconds*, once more than that many
+    seconds of *clock* time have passed (checked after each sample). The
+    caller can compare the result length with *iterations* to see how many
+    were skipped.
+
+    Returns
+    -------
+    tuple of (list of dict, str)
+        Evaluated results in sampling order, and why the search stopped:
+        `SEARCH_COMPLETE`, `SEARCH_PATIENCE` or `SEARCH_TIME_LIMIT`.
+    """
+    rng = random.Random(seed)
+    results: List[Dict[str, Any]] = []
+    best_score = None
+    stale = 0
+    bar = ProgressBar(iterations) if progress else None
+    stop_reason = SEARCH_COMPLETE
+    start = clock()
+    for i in range(1, iterations + 1):
+        metrics = evaluate_theta_multi(
+            sample_theta(rng, ranges),
+            budgets,
+            seed,
+            seed_count,
+            foods=foods,
+            budget_weights=budget_weights,
+        )
+        results.append(metrics)
+
+        score = score_metrics(metrics, objective)
+        if best_score is None or score > best_score:
+            best_score = score
+            stale = 0
+        else:
+            stale += 1
+
+        if bar is not None:
+            bar.update(i)
+
+        if i == iterations:
+            break
+        if patience is not None and stale >= patience:
+            stop_reason = SEARCH_PATIENCE
+            break
+        if max_seconds is not None and clock() - start > max_seconds:
+            stop_reason = SEARCH_TIME_LIMIT
+            break
+
+    if bar is not None:
+        bar.close()
+    return results, stop_reason
+
+
+def run_annealing(
+    budgets: List[int],
+    ranges: Dict[str, Tuple[float, float]],
+    iterations: int,
+    seed: int,
+    *,
+    temperature: float = ANNEALING_INITIAL_TEMPERATURE,
+    cooling: float = ANNEALING_COOLING,
+    seed_count: int = 1,
+    foods: List[Food] | None = None,
+    budget_weights: List[float] | None = None,
+    frozen: Iterable[str] = (),
+) -> Tuple[List[Dict[str, Any]], str]:
+    """Simulated annealing over the knobs, starting from the baseline.
+
+    Each step scales one random knob other than the *frozen* ones (via
+    `perturb_theta`) and moves to the
+    candidate if its SP is higher, or otherwise with probability
+    ``exp(ΔSP / T)``. The temperature decays by *cooling* per step.
+
+    Returns
+    -------
+    tuple of (list of dict, str)
+        Every evaluated result (the start point first), so the usual
+        Pareto/top-k reporting applies to the whole trajectory, and the
+        stop reason: always `SEARCH_COMPLETE`, as annealing never stops
+        early.
+    """
+    rng = random.Random(seed)
+    frozen = set(frozen)
+    knob_names = [k for k in ranges if k not in frozen]
+
+    current = evaluate_theta_multi(
+        clamp_theta(baseline_theta(), ranges),
+        budgets,
+        seed,
+        seed_count,
+        foods=foods,
+        budget_weights=budget_weights,
+    )
+    trajectory = [current]
+
+    for _ in range(iterations if knob_names else 0):
+        knob_name = rng.choice(knob_names)
+        factor = rng.uniform(*ANNEALING_FACTOR_RANGE)
+        candidate_theta = perturb_theta(
+            current["theta"],
+            knob_name,
+            factor,
+            ranges,
+            min_step_frac=PERTURB_MIN_STEP_FRAC,
+        )
+        candidate = evaluate_theta_multi(
+            candidate_theta,
+            budgets,
+            seed,
+            seed_count,
+            foods=foods,
+            budget_weights=budget_weights,
+        )
+        trajectory.append(candidate)
+
+        sp_delta = candidate["avg_final_sp"] - current["avg_final_sp"]
+        if sp_delta >= 0 or (
+            temperature > 0 and rng.random() < math.exp(sp_delta / temperature)
+        ):
+            current = candidate
+        temperature *= cooling
+
+    return trajectory, SEARCH_COMPLETE
+
+
+# -------- output --------
+
+#: Column order for CSV exports (knobs first, then averaged metrics).
+CSV_FIELDNAMES: Tuple[str, ...] = (
+    "SOFT_VARIETY_BIAS_STRENGTH",
+    "PROXIMITY_APPROACH_WEIGHT",
+    "PROXIMITY_OVERSHOOT_PENALTY",
+    "TIEBREAK_SCORE_WINDOW_SP",
+    "LOW_CALORIE_THRESHOLD",
+    "LOW_CALORIE_PENALTY_STRENGTH",
+    "BALANCED_DIET_IMPROVEMENT_STRENGTH",
+    "REPETITION_PENALTY_STRENGTH",
+    "avg_final_sp",
+    "avg_delta_sp_per_100kcal",
+    "avg_variety_count",
+    "avg_balance_ratio",
+    "avg_bites",
+    "std_final_sp",
+    "std_variety_count",
+    "per_budget",
+)
+
+
+def _flatten_result(
+    r: Dict[str, Any],
+) -> Dict[str, Any]:
+    """Flatten one result into a CSV row (per-budget list as JSON text)."""
+    return {
+        **r["theta"],
+        "avg_final_sp": r["avg_final_sp"],
+        "avg_delta_sp_per_100kcal": r["avg_delta_sp_per_100kcal"],
+        "avg_variety_count": r["avg_variety_count"],
+        "avg_balance_ratio": r.get("avg_balance_ratio", 0),
+        "avg_bites": r.get("avg_bites", average_bites(r["per_budget"])),
+        **budget_spread(r["per_budget"]),
+        "per_budget": json.dumps(r["per_budget"]),
+    }
+
+
+def write_csv(
+    rows: List[Dict[str, Any]],
+    path: Path,
+) -> None:
+    """Write every evaluated result to CSV, one row per trial."""
+    with open(
+        path,
+        "w",
+        newline="",
+        encoding="utf-8",
+    ) as f:
+        w = csv.DictWriter(
+            f,
+            fieldnames=list(CSV_FIELDNAMES),
+        )
+        w.writeheader()
+        for r in rows:
+            w.writerow(_flatten_result(r))
+
+
+def read_csv(
+    path: Path,
+) -> List[Dict[str, Any]]:
+    """Read results written by `write_csv` back into result dicts.
+
+    Knobs missing from the file (older CSVs) are filled from the current
+    config so every theta stays complete.
+    """
+    defaults = baseline_theta()
+    results: List[Dict[str, Any]] = []
+    with open(
+        path,
+        newline="",
+        encoding="utf-8",
+    ) as f:
+        for row in csv.DictReader(f):
+            theta = {
+                k: float(row[k]) if row.get(k) else defaults[k] for k in KNOB_NAMES
+            }
+            per_budget = json.loads(row.get("per_budget") or "[]")
+            results.append(
+                {
+                    "theta": theta,
+                    **{k: float(row.get(k) or 0.0) for k in METRIC_KEYS},
+                    **budget_spread(per_budget),
+                    "avg_bites": average_bites(per_budget),
+                    "per_budget": per_budget,
+                }
+            )
+    return results
+
+
+def resume_results(
+    prior: List[Dict[str, Any]],
+    budgets: List[int],
+    seed: int,
+    seed_count: int = 1,
+    *,
+    foods: List[Food] | None = None,
+    budget_weights: List[float] | None = None,
+) -> List[Dict[str, Any]]:
+    """Reuse prior results whose budgets match; re-evaluate the rest.
+
+    With *budget_weights*, reused rows have their averages recomputed from
+    the stored per-budget metrics.
+    """
+    resumed: List[Dict[str, Any]] = []
+    for r in prior:
+        prior_budgets = [pb["budget"] for pb in r["per_budget"]]
+        if prior_budgets != [max(1, int(b)) for b in budgets]:
+            resumed.append(
+                evaluate_theta_multi(
+                    r["theta"],
+                    budgets,
+                    seed,
+                    seed_count,
+                    foods=foods,
+                    budget_weights=budget_weights,
+                )
+            )
+        elif budget_weights is not None:
+            resumed.append(
+                {**r, **weighted_budget_average(r["per_budget"], budget_weights)}
+            )
+        else:
+            resumed.append(r)
+    return resumed
+
+
+def write_pareto_csv(
+    rows: List[Dict[str, Any]],
+    pareto_indices: List[int],
+    balanced_idx: int | None,
+    path: Path,
+) -> None:
+    """Write only the Pareto-optimal results to CSV.
+
+    Rows are sorted by ``avg_final_sp`` descending and carry an extra
+    ``is_balanced`` column marking the `select_balanced` pick.
+    """
+    ordered = sorted(
+        pareto_indices,
+        key=lambda idx: metric_key(rows[idx]["avg_final_sp"]),
+        reverse=True,
+    )
+    with open(
+        path,
+        "w",
+        newline="",
+        encoding="utf-8",
+    ) as f:
+        w = csv.DictWriter(
+            f,
+            fieldnames=[*CSV_FIELDNAMES, "is_balanced"],
+        )
+        w.writeheader()
+        for idx in ordered:
+            w.writerow(
+                {
+                    **_flatten_result(rows[idx]),
+                    "is_balanced": idx == balanced_idx,
+                }
+            )
+
+
+def config_snippet(
+    knobs: Dict[str, float],
+    *,
+    source: str = "tuner",
+    today: datetime.date | None = None,
+) -> str:
+    """Render *knobs* as an ``algorithm:`` block for ``config.yml``.
+
+    Knob names map to their lowercase config keys. ``LOW_CALORIE_THRESHOLD``
+    is written as an int, as `constants` declares it.
+
+    Parameters
+    ----------
+    knobs
+        Knob names to values (e.g. the ``knobs`` of ``tuner_best.json``).
+    source
+        Where the values came from, named in the header comment.
+    today
+        Date for the header comment; defaults to the current date.
+    """
+    today = today or datetime.date.today()
+    lines = [
+        f"# Tuned knobs from {source} ({today.isoformat()}); paste into config.yml",
+        "algorithm:",
+    ]
+    for knob_name, value in knobs_to_json(knobs).items():
+        lines.append(f"  {knob_name.lower()}: {value!r}")
+    return "\n".join(lines) + "\n"
+
+
+def knobs_to_json(
+    knobs: Dict[str, float],
+) -> Dict[str, float | int]:
+    """JSON-ready copy of *knobs* in `KNOB_NAMES` order.
+
+    ``LOW_CALORIE_THRESHOLD`` becomes an int, as `constants` declares it;
+    other knobs are rounded to `KNOB_DECIMALS` places. `load_best_knobs`
+    reads the result back.
+    """
+    out: Dict[str, float | int] = {}
+    for knob_name in KNOB_NAMES:
+        if knob_name not in knobs:
+            continue
+        value = knobs[knob_name]
+        if knob_name == "LOW_CALORIE_THRESHOLD":
+            out[knob_name] = int(round(value))
+        else:
+            out[knob_name] = round(float(value), KNOB_DECIMALS)
+    return out
+
+
+def write_best_json(
+    payload: Dict[str, Any],
+    path: Path,
+) -> None:
+    """Write a best-result *payload* to *path*, knobs via `knobs_to_json`.
+
+    The counterpart of `load_best_knobs`, which reads the ``knobs`` back.
+    """
+    with open(
+        path,
+        "w",
+        encoding="utf-8",
+    ) as f:
+        json.dump(
+            {**payload, "knobs": knobs_to_json(payload["knobs"])},
+            f,
+            indent=2,
+        )
+
+
+def write_config_snippet(
+    knobs: Dict[str, float],
+    path: Path,
+    *,
+    source: str = "tuner",
+) -> None:
+    """Write `config_snippet` for *knobs* to *path*."""
+    with open(
+        path,
+        "w",
+        encoding="utf-8",
+    ) as f:
+        f.write(config_snippet(knobs, source=source))
+
+
 # -------- main loop --------
 
 
-def main() -> None:
+def main(
+    argv: List[str] | None = None,
+) -> None:
     ap = argparse.ArgumentParser(
         description="Random-search tuner for planner knobs (minimal-touch)."
     )
+    ap.add_argument(
+        "--config",
+        type=str,
+        default=None,
+        help="YAML file with budgets, knob ranges, and search settings",
+    )
     ap.add_argument(
         "--iters",
         type=int,
@@ -667,6 +2031,15 @@ def main() -> None:
         default=123,
         help="Random seed (default: 123)",
     )
+    ap.add_argument(
+        "--seeds",
+        type=int,
+        default=1,
+        help=(
+            "Evaluate each knob set under N budget-jitter seeds and average "
+            "the metrics (default: 1)"
+        ),
+    )
     ap.add_argument(
         "--budgets",
         type=str,
@@ -675,6 +2048,24 @@ def main() -> None:
             f"Comma-separated calorie budgets to evaluate (default: {','.join(str(x) for x in DEFAULT_BUDGETS)})"
         ),
     )
+    ap.add_argument(
+        "--holdout",
+        type=str,
+        default="",
+        help=(
+            "Comma-separated calorie budgets kept out of tuning; the final pick "
+            "is re-evaluated on them to check it generalizes"
+        ),
+    )
+    ap.add_argument(
+        "--budget-weights",
+        type=str,
+        default="",
+        help=(
+            "Comma-separated relative weight per budget for the averages, "
+            "e.g. '1,3,1,1' (default: uniform)"
+        ),
+    )
     # Search space overrides
     ap.add_argument(
         "--soft",
@@ -725,9 +2116,84 @@ def main() -> None:
         help="Range for REPETITION_PENALTY_STRENGTH, e.g. '0,2' (default 0,2)",
     )
     ap.add_argument(
-        "--no-hill-climb",
+        "--freeze",
+        action="append",
+        default=[],
+        type=str.upper,
+        choices=KNOB_NAMES,
+        metavar="KNOB",
+        help="Hold KNOB at its config value while tuning the rest (repeatable)",
+    )
+    ap.add_argument(
+        "--resume",
+        type=str,
+        default=None,
+        help="Prior results CSV to merge into this run's frontier",
+    )
+    ap.add_argument(
+        "--warmstart",
+        type=str,
+        default=None,
+        metavar="BEST_JSON",
+        help=(
+            "Evaluate the knobs in a prior best-result JSON first and refine "
+            "them with the rest of the results"
+        ),
+    )
+    ap.add_argument(
+        "--search",
+        choices=("random", "annealing"),
+        default="random",
+        help="Search strategy (default: random)",
+    )
+    ap.add_argument(
+        "--temp",
+        type=float,
+        default=ANNEALING_INITIAL_TEMPERATURE,
+        help=(
+            "Initial annealing temperature in SP "
+            f"(default: {ANNEALING_INITIAL_TEMPERATURE})"
+        ),
+    )
+    ap.add_argument(
+        "--cooling",
+        type=float,
+        default=ANNEALING_COOLING,
+        help=f"Annealing temperature decay per step (default: {ANNEALING_COOLING})",
+    )
+    ap.add_argument(
+        "--patience",
+        type=int,
+        default=None,
+        help=(
+            "Stop random search after K samples without improving the "
+            "--objective score (default: run all iterations)"
+        ),
+    )
+    ap.add_argument(
+        "--max-seconds",
+        type=float,
+        default=None,
+        metavar="T",
+        help=(
+            "Stop random search once T seconds have passed; whichever of "
+            "--iters and this limit comes first wins (default: no limit)"
+        ),
+    )
+    ap.add_argument(
+        "--hill-climb",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Refine the best results with hill climbing (default: on)",
+    )
+    ap.add_argument(
+        "--pareto-climb",
         action="store_true",
-        help="Disable hill climbing refinement",
+        help=(
+            "Hill climb along trade-offs: accept steps that improve any "
+            "metric without losing more than "
+            f"{PARETO_CLIMB_TOLERANCE * 100:.0f}%% on the others, keeping every step"
+        ),
     )
 
     ap.add_argument(
@@ -736,99 +2202,281 @@ def main() -> None:
         default=str((DEFAULT_OUTPUT_DIR / "tuner_results.csv")),
         help="Output CSV path (default: tune/tuner_results.csv)",
     )
+    ap.add_argument(
+        "--pareto-csv",
+        type=str,
+        default=None,
+        help="Optional CSV path for the Pareto frontier only (default: not written)",
+    )
+    ap.add_argument(
+        "--emit-config",
+        type=str,
+        default=None,
+        metavar="PATH",
+        help="Also write the picked knobs as a config.yml algorithm block",
+    )
     ap.add_argument(
         "--json",
         type=str,
         default="tuner_best.json",
         help="Output JSON path for best result (default: tuner_best.json)",
     )
+    ap.add_argument(
+        "--rank",
+        choices=("lexicographic", "weighted"),
+        default="lexicographic",
+        help="Ranking for top-k and fallback best: lexicographic SP-first or weighted sum",
+    )
+    ap.add_argument(
+        "--objective",
+        choices=tuple(OBJECTIVE_METRICS),
+        default=None,
+        help=(
+            "Metric ranked first for lexicographic ranking and favored by the "
+            "balanced pick (default: sp, with an unbiased balanced pick)"
+        ),
+    )
+    ap.add_argument(
+        "--weights",
+        type=str,
+        default="",
+        help="Weights 'sp,eff,var,bal' for --rank weighted, e.g. '1,0.5,2,1' (default 1,1,1,1)",
+    )
     ap.add_argument(
         "--topk",
         type=int,
         default=10,
         help="How many top rows to print (default: 10)",
     )
+    ap.add_argument(
+        "--precision",
+        type=parse_precision,
+        default=2,
+        metavar="N",
+        help="Decimals shown for SP values in printed results (default: 2)",
+    )
+
+    ap.add_argument(
+        "--sort",
+        choices=("trial", "bites"),
+        default="trial",
+        help="CSV row order: evaluation order, or fewest average bites first",
+    )
+
+    ap.add_argument(
+        "--print-baseline-plan",
+        action="store_true",
+        help="Print the baseline's bites for the largest budget before tuning",
+    )
+
+    ap.add_argument(
+        "--quiet",
+        "-q",
+        action="store_true",
+        help="Print only the top candidates (no progress or status lines)",
+    )
 
-    args = ap.parse_args()
+    args = ap.parse_args(argv)
+
+    def status(*parts: Any) -> None:
+        """Informational output, silenced by --quiet."""
+        if not args.quiet:
+            print(*parts)
+
+    # File values become defaults, so flags given on the command line win
+    tuner_config = TunerConfig()
+    if args.config:
+        tuner_config = load_tuner_config(Path(args.config))
+        ap.set_defaults(**config_defaults(tuner_config))
+        args = ap.parse_args(argv)
 
     # Parse budgets string into a concrete list of ints.
     budgets: list[int] = [int(b.strip()) for b in args.budgets.split(",") if b.strip()]
     if not budgets:
         raise SystemExit("No budgets provided.")
+    holdout_budgets = [int(b.strip()) for b in args.holdout.split(",") if b.strip()]
+    budget_weights: list[float] | None = [
+        float(w.strip()) for w in args.budget_weights.split(",") if w.strip()
+    ] or None
+    try:
+        normalize_budget_weights(budget_weights, len(budgets))
+    except ValueError as exc:
+        raise SystemExit(str(exc)) from exc
+
+    weights = parse_weights(args.weights) if args.rank == "weighted" else None
+    objective = args.objective or "sp"
+
+    def objective_score(
+        m: Dict[str, Any],
+    ) -> Tuple[MetricKey, MetricKey, MetricKey, MetricKey]:
+        return score_metrics(m, objective)
 
-    # Default ranges
+    # Default ranges, overridden by the config file, then by flags
+    base_ranges = {**DEFAULT_RANGES, **tuner_config.ranges}
     ranges = {
         "SOFT_VARIETY_BIAS_STRENGTH": parse_range(
-            args.soft, RANGE_SOFT_VARIETY_BIAS_STRENGTH
+            args.soft, base_ranges["SOFT_VARIETY_BIAS_STRENGTH"]
         ),
         "PROXIMITY_APPROACH_WEIGHT": parse_range(
-            args.proximity_approach, RANGE_PROXIMITY_APPROACH_WEIGHT
+            args.proximity_approach, base_ranges["PROXIMITY_APPROACH_WEIGHT"]
         ),
         "PROXIMITY_OVERSHOOT_PENALTY": parse_range(
-            args.proximity_overshoot, RANGE_PROXIMITY_OVERSHOOT_PENALTY
+            args.proximity_overshoot, base_ranges["PROXIMITY_OVERSHOOT_PENALTY"]
         ),
         "TIEBREAK_SCORE_WINDOW_SP": parse_range(
-            args.tiebreak_window, RANGE_TIEBREAK_SCORE_WINDOW_SP
+            args.tiebreak_window, base_ranges["TIEBREAK_SCORE_WINDOW_SP"]
         ),
         "LOW_CALORIE_THRESHOLD": parse_range(
-            args.low_cal_threshold, RANGE_LOW_CALORIE_THRESHOLD
+            args.low_cal_threshold, base_ranges["LOW_CALORIE_THRESHOLD"]
         ),
         "LOW_CALORIE_PENALTY_STRENGTH": parse_range(
-            args.low_cal_penalty, RANGE_LOW_CALORIE_PENALTY_STRENGTH
+            args.low_cal_penalty, base_ranges["LOW_CALORIE_PENALTY_STRENGTH"]
         ),
         "BALANCED_DIET_IMPROVEMENT_STRENGTH": parse_range(
-            args.balance_strength, RANGE_BALANCED_DIET_IMPROVEMENT_STRENGTH
+            args.balance_strength, base_ranges["BALANCED_DIET_IMPROVEMENT_STRENGTH"]
         ),
         "REPETITION_PENALTY_STRENGTH": parse_range(
-            args.rep_strength, RANGE_REPETITION_PENALTY_STRENGTH
+            args.rep_strength, base_ranges["REPETITION_PENALTY_STRENGTH"]
         ),
     }
+    ranges = freeze_ranges(ranges, args.freeze)
+    if args.freeze:
+        status(f"Frozen at config values: {', '.join(args.freeze)}")
 
-    rng = random.Random(
-        args.seed,
+    # Baseline: current config knobs, evaluated once (planning is deterministic)
+    baseline = evaluate_theta(
+        baseline_theta(), budgets, args.seed, budget_weights=budget_weights
     )
+    status(
+        f"Baseline (config knobs): SP={baseline['avg_final_sp']:.{args.precision}f} "
+        f"variety={baseline['avg_variety_count']:.1f} "
+        f"balance={baseline.get('avg_balance_ratio', 0):.3f}"
+    )
+    if args.print_baseline_plan:
+        print(f"Baseline plan at {max(budgets)} cal:")
+        display_meal_plan(baseline_plan(budgets), precision=args.precision)
+
     rows = []
     best = None  # tuple(score, metrics)
 
-    for i in range(1, args.iters + 1):
-        theta = sample_theta(
-            rng,
-            ranges,
-        )
-        metrics = evaluate_theta(
-            theta,
+    if args.resume:
+        rows = resume_results(
+            read_csv(Path(args.resume)),
             budgets,
-            seed=args.seed,
+            args.seed,
+            args.seeds,
+            budget_weights=budget_weights,
         )
-        score = score_metrics(
-            metrics,
+        if rows:
+            top_row = max(rows, key=objective_score)
+            best = (objective_score(top_row), top_row)
+        status(f"Resumed {len(rows)} results from {args.resume}")
+
+    # Start from a previous run's pick; it is climbed even off the frontier
+    warm = None
+    if args.warmstart:
+        try:
+            warm = evaluate_warmstart(
+                Path(args.warmstart),
+                budgets,
+                args.seed,
+                ranges,
+                args.seeds,
+                budget_weights=budget_weights,
+            )
+        except (OSError, ValueError) as exc:
+            raise SystemExit(f"Cannot warm-start from {args.warmstart}: {exc}") from exc
+        rows.append(warm)
+        status(
+            f"Warm start ({args.warmstart}): "
+            f"SP={warm['avg_final_sp']:.{args.precision}f} "
+            f"variety={warm['avg_variety_count']:.1f}"
         )
 
-        rows.append(metrics)
-
-        if (best is None) or (score > best[0]):
-            best = (score, metrics)
-
-        # Progress indicator every 10%
-        if i % max(1, args.iters // 10) == 0:
-            pct = (i / args.iters) * 100
-            print(f"\r{pct:.0f}% complete", end="", flush=True)
-
-    print()  # Newline after progress
+    if args.search == "annealing":
+        trajectory, _ = run_annealing(
+            budgets,
+            ranges,
+            args.iters,
+            args.seed,
+            temperature=args.temp,
+            cooling=args.cooling,
+            seed_count=args.seeds,
+            budget_weights=budget_weights,
+            frozen=args.freeze,
+        )
+        rows += trajectory
+        top_row = max(rows, key=objective_score)
+        best = (objective_score(top_row), top_row)
+    else:
+        sampled, stop_reason = run_random_search(
+            budgets,
+            ranges,
+            args.iters,
+            args.seed,
+            seed_count=args.seeds,
+            budget_weights=budget_weights,
+            patience=args.patience,
+            progress=not args.quiet,
+            max_seconds=args.max_seconds,
+            objective=objective,
+        )
+        if stop_reason != SEARCH_COMPLETE:
+            reason = (
+                f"time limit of {args.max_seconds:g}s reached"
+                if stop_reason == SEARCH_TIME_LIMIT
+                else f"no {objective} gain for {args.patience} iterations"
+            )
+            status(
+                f"Stopped early: {reason} after {len(sampled)} iterations "
+                f"({args.iters - len(sampled)} skipped)"
+            )
+        rows += sampled
+        if rows:
+            top_row = max(rows, key=objective_score)
+            best = (objective_score(top_row), top_row)
 
     # Compute Pareto frontier
     pareto_indices = pareto_frontier(rows)
-    print(f"\nPareto frontier: {len(pareto_indices)} non-dominated solutions")
+    status(f"\nPareto frontier: {len(pareto_indices)} non-dominated solutions")
 
     # Hill climbing refinement
-    if not args.no_hill_climb and pareto_indices:
-        print(
-            f"Refining {len(pareto_indices)} Pareto-optimal results with hill climbing..."
-        )
+    climb_indices = pareto_indices.copy()
+    if warm is not None and has_finite_metrics(warm):
+        warm_idx = next(idx for idx, row in enumerate(rows) if row is warm)
+        if warm_idx not in climb_indices:
+            climb_indices.append(warm_idx)
+    if args.hill_climb and climb_indices:
+        status(f"Refining {len(climb_indices)} results with hill climbing...")
         refined_count = 0
-        for idx in pareto_indices.copy():
+        for idx in climb_indices:
             original = rows[idx]
-            refined = hill_climb(original, budgets, args.seed, ranges)
+            if args.pareto_climb:
+                steps = hill_climb_pareto(
+                    original,
+                    budgets,
+                    args.seed,
+                    ranges,
+                    max_iterations=tuner_config.hill_climb_max_iterations,
+                    factors=tuner_config.hill_climb_factors,
+                    seed_count=args.seeds,
+                    budget_weights=budget_weights,
+                    frozen=args.freeze,
+                )
+                refined_count += len(steps)
+                rows += steps
+                continue
+            refined = hill_climb(
+                original,
+                budgets,
+                args.seed,
+                ranges,
+                max_iterations=tuner_config.hill_climb_max_iterations,
+                factors=tuner_config.hill_climb_factors,
+                seed_count=args.seeds,
+                budget_weights=budget_weights,
+                frozen=args.freeze,
+            )
 
             # Check if refinement improved
             if is_dominated_by(original, refined):
@@ -836,23 +2484,24 @@ def main() -> None:
                 rows.append(refined)
 
         if refined_count > 0:
-            print(f"  {refined_count} results improved by hill climbing")
+            status(f"  {refined_count} results improved by hill climbing")
             # Recompute Pareto frontier with refined results
             pareto_indices = pareto_frontier(rows)
-            print(
+            status(
                 f"  Updated Pareto frontier: {len(pareto_indices)} non-dominated solutions"
             )
         else:
-            print("  No improvements found (already at local optima)")
+            status("  No improvements found (already at local optima)")
 
     # Select balanced pick from Pareto frontier
-    balanced_idx = select_balanced(rows, pareto_indices)
+    balanced_idx = select_balanced(rows, pareto_indices, emphasis=args.objective)
     if balanced_idx is not None:
         balanced = rows[balanced_idx]
-        print(
-            f"Balanced pick: SP={balanced['avg_final_sp']:.2f} "
+        status(
+            f"Balanced pick: SP={balanced['avg_final_sp']:.{args.precision}f} "
             f"variety={balanced['avg_variety_count']:.1f} "
-            f"balance={balanced.get('avg_balance_ratio', 0):.3f}"
+            f"balance={balanced.get('avg_balance_ratio', 0):.3f} "
+            f"sd(SP)={balanced.get('std_final_sp', 0):.{args.precision}f}"
         )
 
     # Resolve output paths and ensure directories exist
@@ -862,49 +2511,29 @@ def main() -> None:
     json_path.parent.mkdir(parents=True, exist_ok=True)
 
     # Write CSV (flatten rows for CSV output)
-    fieldnames = [
-        "SOFT_VARIETY_BIAS_STRENGTH",
-        "PROXIMITY_APPROACH_WEIGHT",
-        "PROXIMITY_OVERSHOOT_PENALTY",
-        "TIEBREAK_SCORE_WINDOW_SP",
-        "LOW_CALORIE_THRESHOLD",
-        "LOW_CALORIE_PENALTY_STRENGTH",
-        "BALANCED_DIET_IMPROVEMENT_STRENGTH",
-        "REPETITION_PENALTY_STRENGTH",
-        "avg_final_sp",
-        "avg_delta_sp_per_100kcal",
-        "avg_variety_count",
-        "avg_balance_ratio",
-        "per_budget",
-    ]
-    with open(
-        csv_path,
-        "w",
-        newline="",
-        encoding="utf-8",
-    ) as f:
-        w = csv.DictWriter(
-            f,
-            fieldnames=fieldnames,
+    if args.sort == "bites":
+        # Fewest bites first; negating keeps NaN last in the best-first sort
+        write_csv(
+            sorted(
+                rows, key=lambda r: metric_key(-r.get("avg_bites", 0.0)), reverse=True
+            ),
+            csv_path,
         )
-        w.writeheader()
-        for r in rows:
-            flat = {
-                **r["theta"],
-                "avg_final_sp": r["avg_final_sp"],
-                "avg_delta_sp_per_100kcal": r["avg_delta_sp_per_100kcal"],
-                "avg_variety_count": r["avg_variety_count"],
-                "avg_balance_ratio": r.get("avg_balance_ratio", 0),
-                "per_budget": json.dumps(r["per_budget"]),
-            }
-            w.writerow(flat)
+    else:
+        write_csv(rows, csv_path)
+
+    if args.pareto_csv:
+        pareto_csv_path = Path(args.pareto_csv)
+        pareto_csv_path.parent.mkdir(parents=True, exist_ok=True)
+        write_pareto_csv(rows, pareto_indices, balanced_idx, pareto_csv_path)
 
     # Write JSON for balanced (or best) pick
-    best_result = (
-        rows[balanced_idx]
-        if balanced_idx is not None
-        else (best[1] if best else rows[0])
-    )
+    if balanced_idx is not None:
+        best_result = rows[balanced_idx]
+    elif weights is not None:
+        best_result = rank_results(rows, weights, objective)[0]
+    else:
+        best_result = best[1] if best else rows[0]
     best_payload = {
         "knobs": best_result["theta"],
         "metrics": {
@@ -912,37 +2541,61 @@ def main() -> None:
             "avg_delta_sp_per_100kcal": best_result["avg_delta_sp_per_100kcal"],
             "avg_variety_count": best_result["avg_variety_count"],
             "avg_balance_ratio": best_result.get("avg_balance_ratio", 0),
+            "std_final_sp": best_result.get("std_final_sp", 0),
+            "std_variety_count": best_result.get("std_variety_count", 0),
         },
         "per_budget": best_result["per_budget"],
     }
-    with open(
-        json_path,
-        "w",
-        encoding="utf-8",
-    ) as f:
-        json.dump(
-            best_payload,
-            f,
-            indent=2,
+    if holdout_budgets:
+        holdout = evaluate_holdout(best_result, holdout_budgets, args.seed, args.seeds)
+        status(
+            f"Holdout ({','.join(str(b) for b in holdout_budgets)}): "
+            f"SP={holdout['avg_final_sp']:.{args.precision}f} "
+            f"variety={holdout['avg_variety_count']:.1f} "
+            f"(train SP={best_result['avg_final_sp']:.{args.precision}f}, "
+            f"gap {holdout['sp_gap']:+.1%})"
         )
+        if holdout["sp_gap"] > HOLDOUT_GAP_WARN_FRAC:
+            print(
+                f"[WARN] Holdout SP is {holdout['sp_gap']:.0%} below training; "
+                "the knobs may be overfit to --budgets"
+            )
+        best_payload["holdout"] = {
+            "budgets": holdout_budgets,
+            "metrics": {key: holdout[key] for key in METRIC_KEYS},
+            "sp_gap": holdout["sp_gap"],
+            "per_budget": holdout["per_budget"],
+        }
+    write_best_json(best_payload, json_path)
+
+    if args.emit_config:
+        snippet_path = Path(args.emit_config)
+        snippet_path.parent.mkdir(parents=True, exist_ok=True)
+        write_config_snippet(best_result["theta"], snippet_path, source=json_path.name)
+        status(f"Config snippet written to {snippet_path}")
 
     # Pretty print the top-k
-    print("\nTop candidates (by score):")
-    top = sorted(
-        rows,
-        key=lambda r: score_metrics(r),
-        reverse=True,
-    )[: args.topk]
+    ranking = args.rank if weights is not None else f"{args.rank} {objective}-first"
+    print(f"\nTop candidates (by {ranking} score):")
+    top = rank_results(rows, weights, objective)[: args.topk]
     for j, r in enumerate(top, 1):
         t = r["theta"]
+        spread = (
+            f" ±{r['std_final_sp_across_seeds']:.{args.precision}f}"
+            if "std_final_sp_across_seeds" in r
+            else ""
+        )
         print(
-            f"{j:>2}. SP={r['avg_final_sp']:.2f} | var={r['avg_variety_count']:.1f} | "
-            f"bal={r.get('avg_balance_ratio', 0):.3f} || "
+            f"{j:>2}. SP={r['avg_final_sp']:.{args.precision}f}{spread}"
+            f" | var={r['avg_variety_count']:.1f} | "
+            f"bal={r.get('avg_balance_ratio', 0):.3f} | "
+            f"sd(SP)={r.get('std_final_sp', 0):.{args.precision}f} "
+            f"sd(var)={r.get('std_variety_count', 0):.1f} || "
             f"soft={t['SOFT_VARIETY_BIAS_STRENGTH']:.2f} low_cal={t['LOW_CALORIE_THRESHOLD']:.0f}"
         )
 
-    print(f"\nBest saved to: {json_path}")
-    print(f"All trials saved to: {csv_path}")
+    status(f"\nBest saved to: {json_path}")
+    status(f"All trials saved to: {csv_path}")
 
 
 if __name__ == "__main__":